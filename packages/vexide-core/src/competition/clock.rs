@@ -0,0 +1,205 @@
+use core::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{self, Poll},
+    time::Duration,
+};
+
+use super::{status, CompetitionMode};
+use crate::time::Instant;
+
+/// The lengths of the timed periods of a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchDurations {
+    /// The length of the autonomous period.
+    pub autonomous: Duration,
+
+    /// The length of the driver control period.
+    pub driver: Duration,
+}
+
+impl MatchDurations {
+    /// The periods of a standard head-to-head match: 15 seconds of autonomous followed by 1
+    /// minute and 45 seconds of driver control.
+    pub const MATCH: Self = Self {
+        autonomous: Duration::from_secs(15),
+        driver: Duration::from_secs(105),
+    };
+
+    /// The periods of a skills run: 1 minute for both programming skills and driver skills.
+    pub const SKILLS: Self = Self {
+        autonomous: Duration::from_secs(60),
+        driver: Duration::from_secs(60),
+    };
+
+    /// Returns the length of the period for a competition mode, or `None` for
+    /// [`CompetitionMode::Disabled`].
+    pub const fn of(&self, mode: CompetitionMode) -> Option<Duration> {
+        match mode {
+            CompetitionMode::Disabled => None,
+            CompetitionMode::Autonomous => Some(self.autonomous),
+            CompetitionMode::Driver => Some(self.driver),
+        }
+    }
+}
+
+impl Default for MatchDurations {
+    fn default() -> Self {
+        Self::MATCH
+    }
+}
+
+/// Keeps track of time within the current period of a match.
+///
+/// The clock restarts whenever the robot enters [`CompetitionMode::Autonomous`] or
+/// [`CompetitionMode::Driver`], based on the competition status reported by either a
+/// competition switch or field control. Robots aren't told how long each period is, so the
+/// clock assumes the lengths given by its [`MatchDurations`]. If the clock is created partway
+/// through a period, it assumes the period has just started.
+///
+/// The clock checks the competition status whenever it is queried, so it doesn't need to be
+/// updated in the background.
+///
+/// # Examples
+///
+/// ```no_run
+/// let clock = MatchClock::new(MatchDurations::MATCH);
+///
+/// // Deploy the end-game mechanism with 10 seconds left in driver control.
+/// clock.at(Duration::from_secs(10)).await;
+/// climber.deploy();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchClock {
+    durations: MatchDurations,
+    mode: Cell<CompetitionMode>,
+    period_start: Cell<Option<Instant>>,
+}
+
+impl MatchClock {
+    /// Creates a new clock using the given period lengths.
+    pub fn new(durations: MatchDurations) -> Self {
+        let clock = Self {
+            durations,
+            mode: Cell::new(CompetitionMode::Disabled),
+            period_start: Cell::new(None),
+        };
+        clock.sync();
+
+        clock
+    }
+
+    /// Checks the competition status, restarting the clock if the mode has changed.
+    fn sync(&self) {
+        let mode = status().mode();
+
+        if mode != self.mode.get() || self.period_start.get().is_none() {
+            self.mode.set(mode);
+            self.period_start.set(match mode {
+                CompetitionMode::Disabled => None,
+                CompetitionMode::Autonomous | CompetitionMode::Driver => Some(Instant::now()),
+            });
+        }
+    }
+
+    /// Returns the period lengths used by this clock.
+    pub const fn durations(&self) -> MatchDurations {
+        self.durations
+    }
+
+    /// Sets the period lengths used by this clock.
+    pub fn set_durations(&mut self, durations: MatchDurations) {
+        self.durations = durations;
+    }
+
+    /// Returns the current competition mode.
+    pub fn mode(&self) -> CompetitionMode {
+        self.sync();
+        self.mode.get()
+    }
+
+    /// Returns when the current autonomous or driver control period started, or `None` if the
+    /// robot is disabled.
+    pub fn period_start(&self) -> Option<Instant> {
+        self.sync();
+        self.period_start.get()
+    }
+
+    /// Returns the time since the current autonomous or driver control period started, or
+    /// `None` if the robot is disabled.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.period_start().map(|start| start.elapsed())
+    }
+
+    /// Returns the time left in the current autonomous or driver control period, or `None` if
+    /// the robot is disabled.
+    ///
+    /// This is zero once the period should have ended.
+    pub fn remaining(&self) -> Option<Duration> {
+        let elapsed = self.elapsed()?;
+        let duration = self.durations.of(self.mode.get())?;
+
+        Some(duration.saturating_sub(elapsed))
+    }
+
+    /// Returns a future that completes once `remaining` time is left in driver control.
+    ///
+    /// If the robot is already in driver control with less than `remaining` time left, the
+    /// future completes immediately. Otherwise, it waits for the next driver control period.
+    pub const fn at(&self, remaining: Duration) -> MatchTrigger<'_> {
+        self.at_in(CompetitionMode::Driver, remaining)
+    }
+
+    /// Returns a future that completes once `remaining` time is left in autonomous.
+    ///
+    /// If the robot is already in autonomous with less than `remaining` time left, the future
+    /// completes immediately. Otherwise, it waits for the next autonomous period.
+    pub const fn autonomous_at(&self, remaining: Duration) -> MatchTrigger<'_> {
+        self.at_in(CompetitionMode::Autonomous, remaining)
+    }
+
+    const fn at_in(&self, mode: CompetitionMode, remaining: Duration) -> MatchTrigger<'_> {
+        MatchTrigger {
+            clock: self,
+            mode,
+            remaining,
+        }
+    }
+}
+
+impl Default for MatchClock {
+    fn default() -> Self {
+        Self::new(MatchDurations::default())
+    }
+}
+
+/// A future that completes at a point in a match period.
+///
+/// See [`MatchClock::at`] for more information.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct MatchTrigger<'a> {
+    clock: &'a MatchClock,
+    mode: CompetitionMode,
+    remaining: Duration,
+}
+
+impl Future for MatchTrigger<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        if self.clock.mode() == self.mode
+            && self
+                .clock
+                .remaining()
+                .is_some_and(|remaining| remaining <= self.remaining)
+        {
+            return Poll::Ready(());
+        }
+
+        // TODO: This should probably be done on a timer in the reactor.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
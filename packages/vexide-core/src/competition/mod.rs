@@ -9,13 +9,32 @@ use core::{
     marker::{PhantomData, PhantomPinned},
     ops::ControlFlow,
     pin::{pin, Pin},
+    sync::atomic::{AtomicBool, Ordering},
     task::{self, Poll},
 };
 
 use bitflags::bitflags;
 use futures_core::Stream;
 use pin_project::pin_project;
-use vex_sdk::vexCompetitionStatus;
+
+use crate::sync::Mutex;
+
+mod clock;
+mod simulator;
+
+pub use clock::{MatchClock, MatchDurations, MatchTrigger};
+pub use simulator::{
+    reset_status_source, set_status_source, start_practice_match, stop_practice_match,
+    HardwareStatus, ManualStatus, MatchScript, MatchSimulator, PracticeMatchError, StatusSource,
+};
+
+/// The source of competition status installed with [`set_status_source`], or `None` to use
+/// [`HardwareStatus`].
+static STATUS_SOURCE: Mutex<Option<Box<dyn StatusSource>>> = Mutex::new(None);
+
+/// Whether [`STATUS_SOURCE`] holds a source, so that [`status`] can skip locking it when
+/// reading from the hardware.
+static HAS_STATUS_SOURCE: AtomicBool = AtomicBool::new(false);
 
 bitflags! {
     /// The status bits returned by [`vex_sdk::vexCompetitionStatus`].
@@ -111,8 +130,20 @@ impl CompetitionStatus {
 }
 
 /// Gets the current competition status flags.
+///
+/// These are read from the brain unless a different [`StatusSource`] has been installed with
+/// [`set_status_source`].
 pub fn status() -> CompetitionStatus {
-    CompetitionStatus::from_bits_retain(unsafe { vexCompetitionStatus() })
+    if !HAS_STATUS_SOURCE.load(Ordering::Acquire) {
+        return HardwareStatus.status();
+    }
+
+    // The lock can only be held here if a source is calling this function from its own
+    // `status` method, in which case it gets the hardware status instead of deadlocking.
+    match STATUS_SOURCE.try_lock().as_deref() {
+        Some(Some(source)) => source.status(),
+        _ => HardwareStatus.status(),
+    }
 }
 
 /// Checks if the robot is connected to a competition control system.
@@ -722,3 +753,93 @@ pub trait CompeteExt: Compete {
 }
 
 impl<R: Compete> CompeteExt for R {}
+
+#[cfg(test)]
+mod test {
+    use alloc::{rc::Rc, sync::Arc, vec::Vec};
+    use core::{
+        cell::RefCell,
+        sync::atomic::AtomicU32,
+        task::{Context, RawWaker, RawWakerVTable, Waker},
+        time::Duration,
+    };
+
+    use super::*;
+
+    struct Robot {
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Compete for Robot {
+        async fn disconnected(&mut self) {
+            self.log.borrow_mut().push("disconnected");
+        }
+
+        async fn disabled(&mut self) {
+            self.log.borrow_mut().push("disabled");
+        }
+
+        async fn autonomous(&mut self) {
+            self.log.borrow_mut().push("autonomous");
+        }
+
+        async fn driver(&mut self) {
+            self.log.borrow_mut().push("driver");
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(core::ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+
+        unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+    }
+
+    #[test]
+    fn compete_follows_match_script() {
+        // `MatchSimulator` reads the brain's clock, so the script is played with a fake one.
+        let script = MatchScript::from_durations(MatchDurations::MATCH, Duration::from_secs(3));
+        let elapsed = Arc::new(AtomicU32::new(0));
+        set_status_source({
+            let elapsed = elapsed.clone();
+            move || script.status_at(Duration::from_secs(elapsed.load(Ordering::Relaxed).into()))
+        });
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut runtime = pin!(Robot { log: log.clone() }.compete());
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        for seconds in [0, 1, 5, 16, 19, 100, 126, 130] {
+            elapsed.store(seconds, Ordering::Relaxed);
+            for _ in 0..3 {
+                assert!(runtime.as_mut().poll(&mut cx).is_pending());
+            }
+        }
+
+        // Unplugging the competition switch runs the disconnect task, and then driver control
+        // since that's the mode reported without competition control.
+        set_status_source(CompetitionStatus::empty);
+        for _ in 0..3 {
+            assert!(runtime.as_mut().poll(&mut cx).is_pending());
+        }
+        reset_status_source();
+
+        assert_eq!(
+            *log.borrow(),
+            [
+                "disabled",
+                "autonomous",
+                "disabled",
+                "driver",
+                "disabled",
+                "disconnected",
+                "driver"
+            ]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+extern crate alloc;
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use snafu::Snafu;
+use vex_sdk::vexCompetitionStatus;
+
+use super::{CompetitionMode, CompetitionStatus, MatchDurations, HAS_STATUS_SOURCE, STATUS_SOURCE};
+use crate::time::Instant;
+
+/// A source of competition status, used by [`status`](super::status) and everything built on
+/// top of it.
+///
+/// By default, the status is read from the brain with [`HardwareStatus`]. Installing a
+/// different source with [`set_status_source`] allows competition programs to be run without
+/// a competition switch or field controller, such as in tests or practice matches.
+///
+/// This is implemented for closures returning a [`CompetitionStatus`].
+pub trait StatusSource: Send {
+    /// Returns the current competition status.
+    ///
+    /// Calling [`competition::status`](super::status) from here returns the status reported by
+    /// [`HardwareStatus`], rather than calling this method again.
+    fn status(&self) -> CompetitionStatus;
+}
+
+impl<F: Fn() -> CompetitionStatus + Send> StatusSource for F {
+    fn status(&self) -> CompetitionStatus {
+        self()
+    }
+}
+
+/// Replaces the source of competition status for the whole program.
+pub fn set_status_source(source: impl StatusSource + 'static) {
+    let mut current = STATUS_SOURCE.lock_blocking();
+    *current = Some(Box::new(source));
+    HAS_STATUS_SOURCE.store(true, Ordering::Release);
+}
+
+/// Restores the default source of competition status, [`HardwareStatus`].
+pub fn reset_status_source() {
+    let mut current = STATUS_SOURCE.lock_blocking();
+    HAS_STATUS_SOURCE.store(false, Ordering::Release);
+    *current = None;
+}
+
+/// Reads the competition status reported by a competition switch or field controller.
+///
+/// This is the default [`StatusSource`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardwareStatus;
+
+impl StatusSource for HardwareStatus {
+    fn status(&self) -> CompetitionStatus {
+        CompetitionStatus::from_bits_retain(unsafe { vexCompetitionStatus() })
+    }
+}
+
+/// A competition status that is set by hand.
+///
+/// Clones share the same status, so one clone can be installed with [`set_status_source`]
+/// while another is used to change the status. This doesn't touch any hardware, so it can be
+/// used to test competition programs on a host machine.
+///
+/// # Examples
+///
+/// ```no_run
+/// let status = ManualStatus::new(CompetitionStatus::CONNECTED | CompetitionStatus::DISABLED);
+/// competition::set_status_source(status.clone());
+///
+/// // ... poll the competition runtime ...
+///
+/// status.set(CompetitionStatus::CONNECTED | CompetitionStatus::AUTONOMOUS);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualStatus {
+    bits: Arc<AtomicU32>,
+}
+
+impl ManualStatus {
+    /// Creates a new status source with an initial status.
+    pub fn new(status: CompetitionStatus) -> Self {
+        Self {
+            bits: Arc::new(AtomicU32::new(status.bits())),
+        }
+    }
+
+    /// Changes the status reported by this source and all of its clones.
+    pub fn set(&self, status: CompetitionStatus) {
+        self.bits.store(status.bits(), Ordering::Relaxed);
+    }
+
+    /// Changes the mode reported by this source, keeping its connection flags.
+    pub fn set_mode(&self, mode: CompetitionMode) {
+        let status = self.status() - CompetitionStatus::DISABLED - CompetitionStatus::AUTONOMOUS;
+        self.set(status | mode_flags(mode));
+    }
+}
+
+impl StatusSource for ManualStatus {
+    fn status(&self) -> CompetitionStatus {
+        CompetitionStatus::from_bits_retain(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+/// Returns the status flags that select a competition mode.
+const fn mode_flags(mode: CompetitionMode) -> CompetitionStatus {
+    match mode {
+        CompetitionMode::Disabled => CompetitionStatus::DISABLED,
+        CompetitionMode::Autonomous => CompetitionStatus::AUTONOMOUS,
+        CompetitionMode::Driver => CompetitionStatus::empty(),
+    }
+}
+
+/// A timeline of competition modes, as if set by a competition switch.
+///
+/// # Examples
+///
+/// ```no_run
+/// let script = MatchScript::new()
+///     .then(CompetitionMode::Disabled, Duration::from_secs(3))
+///     .then(CompetitionMode::Autonomous, Duration::from_secs(15))
+///     .then(CompetitionMode::Disabled, Duration::from_secs(2))
+///     .then(CompetitionMode::Driver, Duration::from_secs(105));
+///
+/// assert_eq!(script.status_at(Duration::from_secs(5)).mode(), CompetitionMode::Autonomous);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct MatchScript {
+    steps: Vec<(CompetitionMode, Duration)>,
+}
+
+impl MatchScript {
+    /// Creates an empty script.
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Creates a script for a full match: disabled for `pause`, then autonomous, then disabled
+    /// for `pause` again, then driver control, using the period lengths in `durations`.
+    pub fn from_durations(durations: MatchDurations, pause: Duration) -> Self {
+        Self::new()
+            .then(CompetitionMode::Disabled, pause)
+            .then(CompetitionMode::Autonomous, durations.autonomous)
+            .then(CompetitionMode::Disabled, pause)
+            .then(CompetitionMode::Driver, durations.driver)
+    }
+
+    /// Returns the script with a step added to the end.
+    pub fn then(mut self, mode: CompetitionMode, duration: Duration) -> Self {
+        self.steps.push((mode, duration));
+        self
+    }
+
+    /// Returns the steps of the script.
+    pub fn steps(&self) -> &[(CompetitionMode, Duration)] {
+        &self.steps
+    }
+
+    /// Returns the total length of the script.
+    pub fn duration(&self) -> Duration {
+        self.steps.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// Returns the competition status at a time since the start of the script.
+    ///
+    /// The robot is always reported as connected to a competition switch. Once the script has
+    /// ended, the robot is disabled.
+    pub fn status_at(&self, elapsed: Duration) -> CompetitionStatus {
+        let mut step_end = Duration::ZERO;
+        let mut mode = CompetitionMode::Disabled;
+
+        for &(step_mode, duration) in &self.steps {
+            step_end += duration;
+            if elapsed < step_end {
+                mode = step_mode;
+                break;
+            }
+        }
+
+        CompetitionStatus::CONNECTED | mode_flags(mode)
+    }
+}
+
+/// Plays a [`MatchScript`] in real time, using the brain's clock.
+///
+/// The script starts when the simulator is created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchSimulator {
+    script: MatchScript,
+    start: Instant,
+}
+
+impl MatchSimulator {
+    /// Starts playing a script.
+    pub fn new(script: MatchScript) -> Self {
+        Self {
+            script,
+            start: Instant::now(),
+        }
+    }
+
+    /// Restarts the script from the beginning.
+    pub fn restart(&mut self) {
+        self.start = Instant::now();
+    }
+
+    /// Returns the script being played.
+    pub const fn script(&self) -> &MatchScript {
+        &self.script
+    }
+
+    /// Returns `true` if the script has ended.
+    pub fn is_finished(&self) -> bool {
+        self.start.elapsed() >= self.script.duration()
+    }
+}
+
+impl StatusSource for MatchSimulator {
+    fn status(&self) -> CompetitionStatus {
+        self.script.status_at(self.start.elapsed())
+    }
+}
+
+/// Starts a practice match on the brain, without a competition switch or field controller.
+///
+/// The script plays in real time, and the robot stays disabled once it ends. Call
+/// [`stop_practice_match`] to go back to reading the real competition status.
+///
+/// # Errors
+///
+/// Returns [`PracticeMatchError::CompetitionConnected`] if the robot is connected to a
+/// competition switch or field controller, since a practice match must never override a
+/// real one.
+///
+/// # Examples
+///
+/// Starting a practice match when the brain's screen is touched:
+///
+/// ```no_run
+/// if screen.touch_status().state == TouchState::Pressed {
+///     competition::start_practice_match(MatchScript::from_durations(
+///         MatchDurations::MATCH,
+///         Duration::from_secs(3),
+///     ))?;
+/// }
+/// ```
+pub fn start_practice_match(script: MatchScript) -> Result<(), PracticeMatchError> {
+    if HardwareStatus.status().is_connected() {
+        return Err(PracticeMatchError::CompetitionConnected);
+    }
+
+    set_status_source(MatchSimulator::new(script));
+
+    Ok(())
+}
+
+/// Stops a practice match started with [`start_practice_match`].
+///
+/// This restores the default source of competition status, [`HardwareStatus`].
+pub fn stop_practice_match() {
+    reset_status_source();
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when starting a practice match.
+pub enum PracticeMatchError {
+    /// The robot is connected to a competition switch or field controller.
+    CompetitionConnected,
+}
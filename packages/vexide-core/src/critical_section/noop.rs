@@ -1,4 +1,4 @@
-//! Critical section implementation for WebAssembly.
+//! Critical section implementation for WebAssembly and host tests.
 
 struct NoopCriticalSection;
 critical_section::set_impl!(NoopCriticalSection);
@@ -0,0 +1,237 @@
+//! Filesystem manipulation operations.
+//!
+//! This module provides basic access to files stored on the brain's micro SD card. It aims to
+//! provide a very similar API to the Rust standard library's `std::fs` module, although VEXos
+//! only supports a small subset of the operations that a desktop filesystem does.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vexide::core::fs;
+//!
+//! fs::write("log.txt", b"Hello, world!")?;
+//! let contents = fs::read_to_string("log.txt")?;
+//! ```
+
+extern crate alloc;
+
+use alloc::{ffi::CString, string::String, vec::Vec};
+
+use no_std_io::io::{self, Read, Seek, SeekFrom, Write};
+use vex_sdk::{
+    vexFileClose, vexFileDriveStatus, vexFileOpen, vexFileOpenCreate, vexFileRead, vexFileSeek,
+    vexFileSize, vexFileSync, vexFileTell, vexFileWrite, FIL, FRESULT,
+};
+
+/// `whence` value used by [`vexFileSeek`] for seeking relative to the start of a file.
+const SEEK_SET: i32 = 0;
+
+/// Returns an error if no SD card is inserted into the brain.
+fn validate_drive() -> io::Result<()> {
+    if unsafe { vexFileDriveStatus(0) } {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No SD card is inserted into the brain.",
+        ))
+    }
+}
+
+fn path_to_cstring(path: &str) -> io::Result<CString> {
+    CString::new(path).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Path contained a non-terminating NUL byte.",
+        )
+    })
+}
+
+/// An object providing access to an open file on the SD card.
+///
+/// Files are automatically closed when they go out of scope.
+#[derive(Debug)]
+pub struct File {
+    fd: *mut FIL,
+    writable: bool,
+}
+
+impl File {
+    /// Attempts to open a file in read-only mode.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no SD card is inserted or if `path` does not
+    /// already exist.
+    pub fn open(path: &str) -> io::Result<Self> {
+        validate_drive()?;
+
+        let path = path_to_cstring(path)?;
+        let fd = unsafe { vexFileOpen(path.as_ptr(), b"r\0".as_ptr().cast()) };
+
+        if fd.is_null() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "Could not open the requested file.",
+            ));
+        }
+
+        Ok(Self {
+            fd,
+            writable: false,
+        })
+    }
+
+    /// Opens a file in write-only mode.
+    ///
+    /// This function will create a file if it does not exist, and will truncate it if it does.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no SD card is inserted or if the file could not
+    /// be created.
+    pub fn create(path: &str) -> io::Result<Self> {
+        validate_drive()?;
+
+        let path = path_to_cstring(path)?;
+        let fd = unsafe { vexFileOpenCreate(path.as_ptr()) };
+
+        if fd.is_null() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Could not create the requested file.",
+            ));
+        }
+
+        Ok(Self { fd, writable: true })
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn len(&self) -> u64 {
+        unsafe { vexFileSize(self.fd) }.max(0) as u64
+    }
+
+    /// Returns `true` if the file contains no data.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.writable {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "File was not opened for reading.",
+            ));
+        }
+
+        let read = unsafe { vexFileRead(buf.as_mut_ptr().cast(), 1, buf.len() as u32, self.fd) };
+
+        if read < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Internal read error occurred.",
+            ));
+        }
+
+        Ok(read as usize)
+    }
+}
+
+impl Write for File {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.writable {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "File was not opened for writing.",
+            ));
+        }
+
+        // The SDK doesn't actually mutate the buffer passed to it here, despite taking a `*mut`.
+        let written =
+            unsafe { vexFileWrite(buf.as_ptr().cast_mut().cast(), 1, buf.len() as u32, self.fd) };
+
+        if written < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Internal write error occurred.",
+            ));
+        }
+
+        Ok(written as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        unsafe { vexFileSync(self.fd) }
+        Ok(())
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        // VEXos only accepts unsigned offsets, so we always seek from the start of the file.
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len().checked_add_signed(offset),
+            SeekFrom::Current(offset) => {
+                (unsafe { vexFileTell(self.fd) }.max(0) as u64).checked_add_signed(offset)
+            }
+        }
+        .and_then(|position| u32::try_from(position).ok())
+        .ok_or(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Attempted to seek to an invalid position.",
+        ))?;
+
+        if unsafe { vexFileSeek(self.fd, position, SEEK_SET) } != FRESULT::FR_OK {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Internal seek error occurred.",
+            ));
+        }
+
+        Ok(position as u64)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        unsafe { vexFileClose(self.fd) }
+    }
+}
+
+/// Reads the entire contents of a file into a bytes vector.
+///
+/// This is a convenience function for using [`File::open`] and [`Read::read_to_end`].
+pub fn read(path: &str) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut bytes = Vec::with_capacity(file.len() as usize);
+
+    file.read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Reads the entire contents of a file into a string.
+///
+/// # Errors
+///
+/// In addition to the errors returned by [`File::open`], this function will return an error of
+/// kind [`io::ErrorKind::InvalidData`] if the contents of the file are not valid UTF-8.
+pub fn read_to_string(path: &str) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "File did not contain valid UTF-8.",
+        )
+    })
+}
+
+/// Writes a slice as the entire contents of a file.
+///
+/// This function will create a file if it does not exist, and will entirely replace its
+/// contents if it does.
+pub fn write(path: &str, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    File::create(path)?.write_all(contents.as_ref())
+}
@@ -6,6 +6,7 @@
 //! - Competition state handling: [`competition`]
 //! - Critical-section implementation: [`critical_section`]
 //! - Serial terminal printing: [`io`]
+//! - SD card file access: [`fs`]
 //! - No-std [`Instant`](time::Instant)s: [`time`]
 //! - Synchronization primitives: [`sync`]
 //! - Program control: [`program`]
@@ -18,6 +19,7 @@ pub mod allocator;
 pub mod competition;
 pub mod critical_section;
 pub mod float;
+pub mod fs;
 pub mod io;
 pub mod program;
 pub mod sync;
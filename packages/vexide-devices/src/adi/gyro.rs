@@ -0,0 +1,167 @@
+//! ADI gyroscope device.
+//!
+//! The Cortex-era yaw-rate gyroscope measures rotation around a single axis. It calibrates
+//! itself whenever its port is configured, which takes [`AdiGyro::CALIBRATION_TIME`]. The
+//! robot must be kept still during this time, and readings are unavailable until it finishes.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use snafu::Snafu;
+use vex_sdk::{vexDeviceAdiValueGet, vexDeviceAdiValueSet};
+use vexide_core::{float::Float, time::Instant};
+
+use super::{AdiDevice, AdiDeviceType, AdiPort};
+use crate::PortError;
+
+/// Cortex-era yaw-rate gyroscope.
+#[derive(Debug, PartialEq)]
+pub struct AdiGyro {
+    port: AdiPort,
+    multiplier: f64,
+    calibration_start: Instant,
+}
+
+impl AdiGyro {
+    /// The time taken by the gyro to calibrate after its port is configured.
+    pub const CALIBRATION_TIME: Duration = Duration::from_millis(1300);
+
+    /// Create a new gyro from an [`AdiPort`].
+    ///
+    /// The gyro starts calibrating immediately. `multiplier` scales every reading, and can be
+    /// used to correct for gyros that consistently over or under-report rotation. Use `1.0` for
+    /// no correction.
+    pub fn new(port: AdiPort, multiplier: f64) -> Self {
+        port.configure(AdiDeviceType::Gyro);
+
+        Self {
+            port,
+            multiplier,
+            calibration_start: Instant::now(),
+        }
+    }
+
+    /// Restarts calibration of the gyro, returning a future that completes once it has
+    /// finished.
+    ///
+    /// The robot must be kept still until the future completes.
+    pub fn calibrate(&mut self) -> AdiGyroCalibrateFuture {
+        self.port.configure(AdiDeviceType::Undefined);
+        self.port.configure(AdiDeviceType::Gyro);
+        self.calibration_start = Instant::now();
+
+        AdiGyroCalibrateFuture {
+            calibration_start: self.calibration_start,
+        }
+    }
+
+    /// Returns `true` if the gyro is still calibrating.
+    pub fn is_calibrating(&self) -> bool {
+        self.calibration_start.elapsed() < Self::CALIBRATION_TIME
+    }
+
+    /// Returns the multiplier applied to every reading.
+    pub const fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Sets the multiplier applied to every reading.
+    pub fn set_multiplier(&mut self, multiplier: f64) {
+        self.multiplier = multiplier;
+    }
+
+    /// Returns the total rotation of the gyro in degrees since it was calibrated or reset.
+    ///
+    /// Counterclockwise rotation is positive. Unlike [`AdiGyro::heading`], this is not wrapped,
+    /// so a full counterclockwise turn reads 360 degrees.
+    pub fn rotation(&self) -> Result<f64, AdiGyroError> {
+        self.port.validate_expander()?;
+        self.port.configure(self.device_type());
+
+        if self.is_calibrating() {
+            return Err(AdiGyroError::StillCalibrating);
+        }
+
+        let raw =
+            unsafe { vexDeviceAdiValueGet(self.port.device_handle(), self.port.internal_index()) };
+
+        // The gyro reports its rotation in tenths of a degree.
+        Ok(raw as f64 / 10.0 * self.multiplier)
+    }
+
+    /// Returns the heading of the gyro in degrees, from 0 to 360.
+    pub fn heading(&self) -> Result<f64, AdiGyroError> {
+        Ok(self.rotation()?.rem_euclid(360.0))
+    }
+
+    /// Resets the rotation of the gyro to zero.
+    pub fn reset(&mut self) -> Result<(), AdiGyroError> {
+        self.port.validate_expander()?;
+        self.port.configure(self.device_type());
+
+        if self.is_calibrating() {
+            return Err(AdiGyroError::StillCalibrating);
+        }
+
+        unsafe {
+            vexDeviceAdiValueSet(self.port.device_handle(), self.port.internal_index(), 0);
+        }
+
+        Ok(())
+    }
+}
+
+impl AdiDevice for AdiGyro {
+    type PortIndexOutput = u8;
+
+    fn port_index(&self) -> Self::PortIndexOutput {
+        self.port.index()
+    }
+
+    fn expander_port_index(&self) -> Option<u8> {
+        self.port.expander_index()
+    }
+
+    fn device_type(&self) -> AdiDeviceType {
+        AdiDeviceType::Gyro
+    }
+}
+
+/// Future that waits for an [`AdiGyro`] to finish calibrating, created with
+/// [`AdiGyro::calibrate`].
+#[derive(Debug, Clone, Copy)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct AdiGyroCalibrateFuture {
+    calibration_start: Instant,
+}
+
+impl Future for AdiGyroCalibrateFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.calibration_start.elapsed() >= AdiGyro::CALIBRATION_TIME {
+            return Poll::Ready(());
+        }
+
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when interacting with an ADI gyro.
+pub enum AdiGyroError {
+    /// The gyro is still calibrating.
+    StillCalibrating,
+
+    /// Generic port related error.
+    #[snafu(display("{source}"), context(false))]
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+}
@@ -9,25 +9,31 @@ pub mod addrled;
 pub mod analog;
 pub mod digital;
 pub mod encoder;
+pub mod gyro;
 pub mod light_sensor;
 pub mod line_tracker;
 pub mod motor;
 pub mod potentiometer;
 pub mod pwm;
 pub mod range_finder;
+pub mod servo;
 pub mod solenoid;
+pub mod switch;
 
 pub use accelerometer::{AdiAccelerometer, Sensitivity};
 pub use analog::AdiAnalogIn;
 pub use digital::{AdiDigitalIn, AdiDigitalOut};
 pub use encoder::AdiEncoder;
+pub use gyro::AdiGyro;
 pub use light_sensor::AdiLightSensor;
 pub use line_tracker::AdiLineTracker;
 pub use motor::AdiMotor;
 pub use potentiometer::{AdiPotentiometer, PotentiometerType};
 pub use pwm::AdiPwmOut;
 pub use range_finder::AdiRangeFinder;
+pub use servo::AdiServo;
 pub use solenoid::AdiSolenoid;
+pub use switch::{AdiSwitch, SwitchType};
 use vex_sdk::{
     vexDeviceAdiPortConfigGet, vexDeviceAdiPortConfigSet, vexDeviceGetByIndex,
     V5_AdiPortConfiguration, V5_DeviceT,
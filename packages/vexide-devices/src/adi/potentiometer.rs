@@ -6,6 +6,9 @@ use super::{analog, AdiDevice, AdiDeviceType, AdiPort};
 use crate::PortError;
 
 /// Analog potentiometer ADI device.
+///
+/// This drives both the EDR potentiometer and the V2 (smart) potentiometer, selected with
+/// [`PotentiometerType`]. There is no separate driver for the V2 potentiometer.
 #[derive(Debug, Eq, PartialEq)]
 pub struct AdiPotentiometer {
     potentiometer_type: PotentiometerType,
@@ -65,7 +68,7 @@ pub enum PotentiometerType {
     /// EDR potentiometer.
     Legacy,
 
-    /// V2 potentiometer.
+    /// V2 potentiometer, configured as [`AdiDeviceType::PotentimeterV2`].
     V2,
 }
 
@@ -0,0 +1,86 @@
+//! ADI servo device.
+
+use vex_sdk::{vexDeviceAdiValueGet, vexDeviceAdiValueSet};
+
+use super::{AdiDevice, AdiDeviceType, AdiPort};
+use crate::PortError;
+
+/// Cortex-era servo motor.
+///
+/// Unlike a motor, a servo turns to a target angle and holds it. Its range of motion is
+/// [`AdiServo::MAX_ANGLE`] degrees in either direction from center.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AdiServo {
+    port: AdiPort,
+}
+
+impl AdiServo {
+    /// The largest angle (in degrees) that the servo can turn to in either direction from
+    /// center.
+    pub const MAX_ANGLE: f64 = 50.0;
+
+    /// Create a new servo from an [`AdiPort`].
+    pub fn new(port: AdiPort) -> Self {
+        port.configure(AdiDeviceType::Servo);
+
+        Self { port }
+    }
+
+    /// Sets the angle that the servo should turn to, in degrees from center.
+    ///
+    /// Angles outside of `-MAX_ANGLE..=MAX_ANGLE` are clamped to the servo's range.
+    pub fn set_target(&mut self, angle: f64) -> Result<(), PortError> {
+        let angle = angle.clamp(-Self::MAX_ANGLE, Self::MAX_ANGLE);
+
+        self.set_raw_target((angle / Self::MAX_ANGLE * i8::MAX as f64) as i8)
+    }
+
+    /// Sets the raw PWM target of the servo as an i8 from [-127, 127], where 0 is center.
+    pub fn set_raw_target(&mut self, pwm: i8) -> Result<(), PortError> {
+        self.port.validate_expander()?;
+        self.port.configure(self.device_type());
+
+        unsafe {
+            vexDeviceAdiValueSet(
+                self.port.device_handle(),
+                self.port.internal_index(),
+                pwm as i32,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the last set target of the servo in degrees from center.
+    pub fn target(&self) -> Result<f64, PortError> {
+        Ok(self.raw_target()? as f64 / i8::MAX as f64 * Self::MAX_ANGLE)
+    }
+
+    /// Returns the last set raw PWM target of the servo as an i8 from [-127, 127].
+    pub fn raw_target(&self) -> Result<i8, PortError> {
+        self.port.validate_expander()?;
+        self.port.configure(self.device_type());
+
+        Ok(
+            // Legacy PWM devices report their output out of 256 (u8), the same as `AdiMotor`.
+            (unsafe { vexDeviceAdiValueGet(self.port.device_handle(), self.port.internal_index()) }
+                - i8::MAX as i32) as i8,
+        )
+    }
+}
+
+impl AdiDevice for AdiServo {
+    type PortIndexOutput = u8;
+
+    fn port_index(&self) -> Self::PortIndexOutput {
+        self.port.index()
+    }
+
+    fn expander_port_index(&self) -> Option<u8> {
+        self.port.expander_index()
+    }
+
+    fn device_type(&self) -> AdiDeviceType {
+        AdiDeviceType::Servo
+    }
+}
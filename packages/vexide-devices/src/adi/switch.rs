@@ -0,0 +1,191 @@
+//! ADI limit switch and bumper switch devices.
+
+use vex_sdk::vexDeviceAdiValueGet;
+
+use super::{AdiDevice, AdiDeviceType, AdiPort};
+use crate::PortError;
+
+/// A limit switch or bumper switch.
+///
+/// Besides reading whether the switch is pressed, this keeps track of the switch's previous
+/// state to detect when it is pressed or released, similar to controller buttons. Every read
+/// through [`AdiSwitch::was_pressed`] or [`AdiSwitch::was_released`] records both kinds of
+/// edge, so checking for one never hides the other.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AdiSwitch {
+    port: AdiPort,
+    switch_type: SwitchType,
+    edges: EdgeLatch,
+}
+
+impl AdiSwitch {
+    /// Create a new switch from an [`AdiPort`].
+    ///
+    /// The switch is read once to find its starting state, so a switch that is already held
+    /// down isn't reported as being pressed.
+    pub fn new(port: AdiPort, switch_type: SwitchType) -> Self {
+        port.configure(match switch_type {
+            SwitchType::Legacy => AdiDeviceType::Switch,
+            SwitchType::V2 => AdiDeviceType::SwitchV2,
+        });
+
+        let mut switch = Self {
+            port,
+            switch_type,
+            edges: EdgeLatch::new(),
+        };
+
+        if let Ok(pressed) = switch.is_pressed() {
+            switch.edges.observe(pressed);
+        }
+
+        switch
+    }
+
+    /// Get the type of switch.
+    pub fn switch_type(&self) -> Result<SwitchType, PortError> {
+        // Configuration check not necessary since we don't fetch from the SDK.
+        self.port.validate_expander()?;
+
+        Ok(self.switch_type)
+    }
+
+    /// Returns `true` if the switch is currently pressed.
+    pub fn is_pressed(&self) -> Result<bool, PortError> {
+        self.port.validate_expander()?;
+        self.port.configure(self.device_type());
+
+        Ok(
+            unsafe { vexDeviceAdiValueGet(self.port.device_handle(), self.port.internal_index()) }
+                != 0,
+        )
+    }
+
+    /// Returns `true` if the switch has been pressed since the last call to this function.
+    pub fn was_pressed(&mut self) -> Result<bool, PortError> {
+        self.edges.observe(self.is_pressed()?);
+
+        Ok(core::mem::take(&mut self.edges.press_pending))
+    }
+
+    /// Returns `true` if the switch has been released since the last call to this function.
+    pub fn was_released(&mut self) -> Result<bool, PortError> {
+        self.edges.observe(self.is_pressed()?);
+
+        Ok(core::mem::take(&mut self.edges.release_pending))
+    }
+}
+
+/// Latches the presses and releases seen across reads of a switch.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+struct EdgeLatch {
+    /// Whether the switch was pressed when it was last read, or `None` if it hasn't been read.
+    last_pressed: Option<bool>,
+
+    /// Whether a press has been seen that [`AdiSwitch::was_pressed`] hasn't returned yet.
+    press_pending: bool,
+
+    /// Whether a release has been seen that [`AdiSwitch::was_released`] hasn't returned yet.
+    release_pending: bool,
+}
+
+impl EdgeLatch {
+    const fn new() -> Self {
+        Self {
+            last_pressed: None,
+            press_pending: false,
+            release_pending: false,
+        }
+    }
+
+    /// Records a reading of the switch, latching a press or release if it changed since the
+    /// last reading.
+    ///
+    /// The first reading only sets the starting state.
+    fn observe(&mut self, pressed: bool) {
+        match self.last_pressed.replace(pressed) {
+            Some(false) if pressed => self.press_pending = true,
+            Some(true) if !pressed => self.release_pending = true,
+            _ => {}
+        }
+    }
+}
+
+impl AdiDevice for AdiSwitch {
+    type PortIndexOutput = u8;
+
+    fn port_index(&self) -> Self::PortIndexOutput {
+        self.port.index()
+    }
+
+    fn expander_port_index(&self) -> Option<u8> {
+        self.port.expander_index()
+    }
+
+    fn device_type(&self) -> AdiDeviceType {
+        match self.switch_type {
+            SwitchType::Legacy => AdiDeviceType::Switch,
+            SwitchType::V2 => AdiDeviceType::SwitchV2,
+        }
+    }
+}
+
+/// The type of switch device.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SwitchType {
+    /// Cortex-era limit switch or bumper switch.
+    Legacy,
+
+    /// V2 bumper switch.
+    V2,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn held_at_startup_is_not_a_press() {
+        let mut edges = EdgeLatch::new();
+
+        edges.observe(true);
+        edges.observe(true);
+        assert!(!edges.press_pending);
+        assert!(!edges.release_pending);
+
+        edges.observe(false);
+        assert!(!edges.press_pending);
+        assert!(edges.release_pending);
+    }
+
+    #[test]
+    fn press_and_release_are_latched_until_taken() {
+        let mut edges = EdgeLatch::new();
+        edges.observe(false);
+
+        edges.observe(true);
+        edges.observe(true);
+        assert!(core::mem::take(&mut edges.press_pending));
+        assert!(!core::mem::take(&mut edges.press_pending));
+
+        edges.observe(false);
+        assert!(core::mem::take(&mut edges.release_pending));
+        assert!(!core::mem::take(&mut edges.release_pending));
+        assert!(!edges.press_pending);
+    }
+
+    #[test]
+    fn press_and_release_between_takes_are_both_kept() {
+        let mut edges = EdgeLatch::new();
+        edges.observe(false);
+
+        // The switch is pressed and released while only `was_released` is reading it.
+        edges.observe(true);
+        edges.observe(false);
+        assert!(core::mem::take(&mut edges.release_pending));
+
+        // A later `was_pressed` still sees the press.
+        edges.observe(false);
+        assert!(core::mem::take(&mut edges.press_pending));
+    }
+}
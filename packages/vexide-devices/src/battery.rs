@@ -4,26 +4,43 @@ use vex_sdk::{
     vexBatteryCapacityGet, vexBatteryCurrentGet, vexBatteryTemperatureGet, vexBatteryVoltageGet,
 };
 
-/// Get the robot's battery capacity.
-/// TODO: Determine units
+#[cfg(feature = "units")]
+use crate::units::{Current, Temperature, Voltage};
+
+/// Get the robot's battery capacity as a percentage from 0 to 100.
 pub fn capacity() -> f64 {
     unsafe { vexBatteryCapacityGet() }
 }
 
-/// Get the current temperature of the robot's battery.
-/// TODO: Determine units
+/// Get the current temperature of the robot's battery in degrees Celsius.
 pub fn temperature() -> f64 {
     unsafe { vexBatteryTemperatureGet() }
 }
 
-/// Get the electric current of the robot's battery.
-/// TODO: Determine units
+/// Get the electric current of the robot's battery in milliamps.
 pub fn current() -> i32 {
     unsafe { vexBatteryCurrentGet() }
 }
 
-/// Get the robot's battery voltage.
-/// TODO: Determine units
+/// Get the robot's battery voltage in millivolts.
 pub fn voltage() -> i32 {
     unsafe { vexBatteryVoltageGet() }
 }
+
+/// Get the current temperature of the robot's battery.
+#[cfg(feature = "units")]
+pub fn temperature_typed() -> Temperature {
+    Temperature::from_celsius(temperature())
+}
+
+/// Get the electric current of the robot's battery.
+#[cfg(feature = "units")]
+pub fn current_typed() -> Current {
+    Current::from_milliamps(current() as f64)
+}
+
+/// Get the robot's battery voltage.
+#[cfg(feature = "units")]
+pub fn voltage_typed() -> Voltage {
+    Voltage::from_millivolts(voltage() as f64)
+}
@@ -0,0 +1,529 @@
+//! Named actions bound to controller inputs.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use vex_sdk::V5_ControllerIndex;
+
+use super::{state::read_state, ControllerId, ControllerInput, ControllerState};
+
+/// A button on a controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ControllerButton {
+    /// The A button.
+    A,
+    /// The B button.
+    B,
+    /// The X button.
+    X,
+    /// The Y button.
+    Y,
+    /// The up arrow button.
+    Up,
+    /// The down arrow button.
+    Down,
+    /// The left arrow button.
+    Left,
+    /// The right arrow button.
+    Right,
+    /// The top left trigger.
+    L1,
+    /// The bottom left trigger.
+    L2,
+    /// The top right trigger.
+    R1,
+    /// The bottom right trigger.
+    R2,
+}
+
+impl ControllerButton {
+    /// Every button on a controller.
+    pub const ALL: [Self; 12] = [
+        Self::A,
+        Self::B,
+        Self::X,
+        Self::Y,
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::L1,
+        Self::L2,
+        Self::R1,
+        Self::R2,
+    ];
+}
+
+impl From<ControllerButton> for V5_ControllerIndex {
+    fn from(button: ControllerButton) -> Self {
+        match button {
+            ControllerButton::A => V5_ControllerIndex::ButtonA,
+            ControllerButton::B => V5_ControllerIndex::ButtonB,
+            ControllerButton::X => V5_ControllerIndex::ButtonX,
+            ControllerButton::Y => V5_ControllerIndex::ButtonY,
+            ControllerButton::Up => V5_ControllerIndex::ButtonUp,
+            ControllerButton::Down => V5_ControllerIndex::ButtonDown,
+            ControllerButton::Left => V5_ControllerIndex::ButtonLeft,
+            ControllerButton::Right => V5_ControllerIndex::ButtonRight,
+            ControllerButton::L1 => V5_ControllerIndex::ButtonL1,
+            ControllerButton::L2 => V5_ControllerIndex::ButtonL2,
+            ControllerButton::R1 => V5_ControllerIndex::ButtonR1,
+            ControllerButton::R2 => V5_ControllerIndex::ButtonR2,
+        }
+    }
+}
+
+/// A joystick axis on a controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ControllerAxis {
+    /// The x axis of the left joystick.
+    LeftX,
+    /// The y axis of the left joystick.
+    LeftY,
+    /// The x axis of the right joystick.
+    RightX,
+    /// The y axis of the right joystick.
+    RightY,
+}
+
+impl ControllerAxis {
+    /// Every joystick axis on a controller.
+    pub const ALL: [Self; 4] = [Self::LeftX, Self::LeftY, Self::RightX, Self::RightY];
+}
+
+impl From<ControllerAxis> for V5_ControllerIndex {
+    fn from(axis: ControllerAxis) -> Self {
+        match axis {
+            ControllerAxis::LeftX => V5_ControllerIndex::Axis4,
+            ControllerAxis::LeftY => V5_ControllerIndex::Axis3,
+            ControllerAxis::RightX => V5_ControllerIndex::Axis1,
+            ControllerAxis::RightY => V5_ControllerIndex::Axis2,
+        }
+    }
+}
+
+/// How a [`ButtonBinding`] activates its action.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingMode {
+    /// The action is active while the buttons are held.
+    #[default]
+    Hold,
+
+    /// The action turns on when the buttons are pressed, and off when they are pressed again.
+    Toggle,
+}
+
+/// A button, or combination of buttons, that activates an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonBinding {
+    /// The controller the buttons are on.
+    pub controller: ControllerId,
+
+    /// The buttons that must all be held to activate the action.
+    pub buttons: Vec<ControllerButton>,
+
+    /// How the binding activates its action.
+    pub mode: BindingMode,
+}
+
+impl ButtonBinding {
+    /// Creates a binding that activates its action while all of `buttons` are held.
+    pub fn hold(controller: ControllerId, buttons: &[ControllerButton]) -> Self {
+        Self {
+            controller,
+            buttons: buttons.into(),
+            mode: BindingMode::Hold,
+        }
+    }
+
+    /// Creates a binding that toggles its action each time all of `buttons` are pressed.
+    pub fn toggle(controller: ControllerId, buttons: &[ControllerButton]) -> Self {
+        Self {
+            controller,
+            buttons: buttons.into(),
+            mode: BindingMode::Toggle,
+        }
+    }
+
+    /// Returns `true` if every button in this binding is also in `other`, and `other` has more
+    /// buttons.
+    fn is_shadowed_by(&self, other: &Self) -> bool {
+        self.controller == other.controller
+            && other.buttons.len() > self.buttons.len()
+            && self
+                .buttons
+                .iter()
+                .all(|button| other.buttons.contains(button))
+    }
+}
+
+/// A joystick axis that controls an analog action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisBinding {
+    /// The controller the joystick is on.
+    pub controller: ControllerId,
+
+    /// The joystick axis.
+    pub axis: ControllerAxis,
+
+    /// Whether to invert the axis.
+    pub inverted: bool,
+}
+
+impl AxisBinding {
+    /// Creates a new axis binding.
+    pub const fn new(controller: ControllerId, axis: ControllerAxis) -> Self {
+        Self {
+            controller,
+            axis,
+            inverted: false,
+        }
+    }
+
+    /// Returns the same binding with its axis inverted.
+    pub const fn inverted(mut self) -> Self {
+        self.inverted = !self.inverted;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BindingState {
+    binding: ButtonBinding,
+
+    /// Whether all of the binding's buttons were held at the last update.
+    held: bool,
+
+    /// Whether the binding is currently activating its action.
+    engaged: bool,
+
+    /// Whether a [`BindingMode::Toggle`] binding is currently toggled on.
+    toggled: bool,
+}
+
+impl BindingState {
+    const fn is_active(&self) -> bool {
+        match self.binding.mode {
+            BindingMode::Hold => self.engaged,
+            BindingMode::Toggle => self.toggled,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+struct ButtonAction {
+    bindings: Vec<BindingState>,
+    active: bool,
+    was_active: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AxisAction {
+    binding: AxisBinding,
+    value: f32,
+}
+
+/// A set of named robot actions and the controller inputs bound to them.
+///
+/// Rather than reading a specific button like `controller.button_a`, subsystems query actions
+/// by name, such as `"intake"` or `"climb"`. Bindings can then be changed at runtime (for
+/// example, to suit a different driver) without touching any subsystem code.
+///
+/// Button actions can be bound to single buttons or to combinations of buttons on either
+/// controller, and can either be held or toggled. An action may have several bindings, and is
+/// active if any of them are. While a combination of buttons is held, bindings that use only
+/// some of those buttons on the same controller won't activate, so binding both `L1 + A` and
+/// `A` to different actions works as expected.
+///
+/// Inputs are sampled when [`ActionMap::update`] is called, which should happen once per loop
+/// of driver control. If a controller is disconnected or the robot is not in driver control,
+/// its buttons are treated as released and its joysticks as centered. To drive the actions
+/// from something other than the physical controllers, such as a
+/// [`Playback`](super::Playback), use [`ActionMap::update_from`] instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut actions = ActionMap::new();
+/// actions.bind("intake", ButtonBinding::hold(ControllerId::Primary, &[ControllerButton::R1]));
+/// actions.bind(
+///     "climb",
+///     ButtonBinding::toggle(ControllerId::Primary, &[ControllerButton::L1, ControllerButton::A]),
+/// );
+/// actions.bind_axis("drive", AxisBinding::new(ControllerId::Primary, ControllerAxis::LeftY));
+///
+/// loop {
+///     actions.update();
+///
+///     intake.set_running(actions.is_active("intake"));
+///     if actions.just_activated("climb") {
+///         climber.deploy();
+///     }
+///
+///     sleep(Controller::UPDATE_INTERVAL).await;
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ActionMap {
+    buttons: BTreeMap<String, ButtonAction>,
+    axes: BTreeMap<String, AxisAction>,
+}
+
+impl ActionMap {
+    /// Creates an empty action map.
+    pub const fn new() -> Self {
+        Self {
+            buttons: BTreeMap::new(),
+            axes: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a button binding to an action, keeping any bindings it already has.
+    pub fn bind(&mut self, action: impl Into<String>, binding: ButtonBinding) {
+        self.buttons
+            .entry(action.into())
+            .or_default()
+            .bindings
+            .push(BindingState {
+                binding,
+                held: false,
+                engaged: false,
+                toggled: false,
+            });
+    }
+
+    /// Replaces every button binding of an action with a single new binding.
+    pub fn rebind(&mut self, action: impl Into<String>, binding: ButtonBinding) {
+        let action = action.into();
+        self.unbind(&action);
+        self.bind(action, binding);
+    }
+
+    /// Removes every button binding from an action.
+    pub fn unbind(&mut self, action: &str) {
+        if let Some(action) = self.buttons.get_mut(action) {
+            action.bindings.clear();
+        }
+    }
+
+    /// Returns the button bindings of an action.
+    pub fn bindings(&self, action: &str) -> impl Iterator<Item = &ButtonBinding> {
+        self.buttons
+            .get(action)
+            .into_iter()
+            .flat_map(|action| action.bindings.iter().map(|state| &state.binding))
+    }
+
+    /// Binds an analog action to a joystick axis, replacing its previous binding.
+    pub fn bind_axis(&mut self, action: impl Into<String>, binding: AxisBinding) {
+        self.axes.insert(
+            action.into(),
+            AxisAction {
+                binding,
+                value: 0.0,
+            },
+        );
+    }
+
+    /// Removes the joystick axis binding from an analog action.
+    pub fn unbind_axis(&mut self, action: &str) {
+        self.axes.remove(action);
+    }
+
+    /// Returns the joystick axis bound to an analog action.
+    pub fn axis_binding(&self, action: &str) -> Option<&AxisBinding> {
+        self.axes.get(action).map(|action| &action.binding)
+    }
+
+    /// Samples both physical controllers and updates the state of each action.
+    pub fn update(&mut self) {
+        let primary = read_state(ControllerId::Primary).unwrap_or(ControllerState::NEUTRAL);
+        let partner = read_state(ControllerId::Partner).unwrap_or(ControllerState::NEUTRAL);
+
+        self.update_states([primary, partner]);
+    }
+
+    /// Updates the state of each action from the given inputs instead of the physical
+    /// controllers.
+    ///
+    /// Bindings on [`ControllerId::Primary`] read from `primary`, and bindings on
+    /// [`ControllerId::Partner`] read from `partner`. Each input is read once, so every binding
+    /// sees the same snapshot. Pass [`ControllerState::NEUTRAL`] for a controller that isn't
+    /// used. Inputs that fail to read are treated as released and centered.
+    pub fn update_from(&mut self, primary: &impl ControllerInput, partner: &impl ControllerInput) {
+        let primary = primary.state().unwrap_or(ControllerState::NEUTRAL);
+        let partner = partner.state().unwrap_or(ControllerState::NEUTRAL);
+
+        self.update_states([primary, partner]);
+    }
+
+    /// Updates the state of each action from a snapshot of each controller, indexed by
+    /// [`ControllerId`].
+    fn update_states(&mut self, states: [ControllerState; 2]) {
+        let is_held = |binding: &ButtonBinding| {
+            let state = &states[binding.controller as usize];
+            !binding.buttons.is_empty()
+                && binding
+                    .buttons
+                    .iter()
+                    .all(|&button| state.is_pressed(button))
+        };
+
+        // Combos that are currently held, which take priority over bindings that use a subset
+        // of their buttons.
+        let held_combos: Vec<ButtonBinding> = self
+            .buttons
+            .values()
+            .flat_map(|action| &action.bindings)
+            .filter(|state| state.binding.buttons.len() > 1 && is_held(&state.binding))
+            .map(|state| state.binding.clone())
+            .collect();
+
+        for action in self.buttons.values_mut() {
+            for state in &mut action.bindings {
+                let held = is_held(&state.binding);
+
+                if !held {
+                    state.engaged = false;
+                } else if !state.held
+                    && !held_combos
+                        .iter()
+                        .any(|combo| state.binding.is_shadowed_by(combo))
+                {
+                    state.engaged = true;
+                    state.toggled = !state.toggled;
+                }
+
+                state.held = held;
+            }
+
+            action.was_active = action.active;
+            action.active = action.bindings.iter().any(BindingState::is_active);
+        }
+
+        for action in self.axes.values_mut() {
+            let binding = action.binding;
+            let value = states[binding.controller as usize].axis(binding.axis);
+
+            action.value = if binding.inverted { -value } else { value };
+        }
+    }
+
+    /// Returns `true` if the action is currently active.
+    ///
+    /// Unknown actions are never active.
+    pub fn is_active(&self, action: &str) -> bool {
+        self.buttons.get(action).is_some_and(|action| action.active)
+    }
+
+    /// Returns `true` if the action became active during the last [`ActionMap::update`].
+    pub fn just_activated(&self, action: &str) -> bool {
+        self.buttons
+            .get(action)
+            .is_some_and(|action| action.active && !action.was_active)
+    }
+
+    /// Returns `true` if the action stopped being active during the last
+    /// [`ActionMap::update`].
+    pub fn just_deactivated(&self, action: &str) -> bool {
+        self.buttons
+            .get(action)
+            .is_some_and(|action| !action.active && action.was_active)
+    }
+
+    /// Returns the value of an analog action from -1.0 to 1.0, as of the last
+    /// [`ActionMap::update`].
+    ///
+    /// Unbound actions have a value of 0.0.
+    pub fn axis(&self, action: &str) -> f32 {
+        self.axes.get(action).map_or(0.0, |action| action.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::controller::{ControllerAxis, Playback, Recording};
+
+    fn state(buttons: &[ControllerButton], left_y: i8) -> ControllerState {
+        let mut state = ControllerState::NEUTRAL;
+        for &button in buttons {
+            state.set_pressed(button, true);
+        }
+        state.set_axis_raw(ControllerAxis::LeftY, left_y);
+        state
+    }
+
+    #[test]
+    fn playback_drives_actions() {
+        let mut recording = Recording::new();
+        recording.push(Duration::ZERO, state(&[], 0));
+        recording.push(
+            Duration::from_millis(100),
+            state(&[ControllerButton::R1], 127),
+        );
+        recording.push(
+            Duration::from_millis(200),
+            state(&[ControllerButton::R1, ControllerButton::L1], -127),
+        );
+        recording.push(Duration::from_millis(300), state(&[], 0));
+
+        let mut actions = ActionMap::new();
+        actions.bind(
+            "intake",
+            ButtonBinding::hold(ControllerId::Primary, &[ControllerButton::R1]),
+        );
+        actions.bind(
+            "climb",
+            ButtonBinding::toggle(
+                ControllerId::Primary,
+                &[ControllerButton::L1, ControllerButton::R1],
+            ),
+        );
+        actions.bind_axis(
+            "drive",
+            AxisBinding::new(ControllerId::Primary, ControllerAxis::LeftY),
+        );
+
+        let mut playback = Playback::manual(recording);
+        let partner = ControllerState::NEUTRAL;
+
+        actions.update_from(&playback, &partner);
+        assert!(!actions.is_active("intake"));
+        assert!(!actions.is_active("climb"));
+        assert_eq!(actions.axis("drive"), 0.0);
+
+        playback.seek(Duration::from_millis(100));
+        actions.update_from(&playback, &partner);
+        assert!(actions.just_activated("intake"));
+        assert_eq!(actions.axis("drive"), 1.0);
+
+        playback.advance(Duration::from_millis(100));
+        actions.update_from(&playback, &partner);
+        assert!(actions.is_active("intake"));
+        assert!(actions.just_activated("climb"));
+        assert_eq!(actions.axis("drive"), -1.0);
+
+        playback.advance(Duration::from_millis(100));
+        actions.update_from(&playback, &partner);
+        assert!(actions.just_deactivated("intake"));
+        assert!(actions.is_active("climb"));
+        assert_eq!(actions.axis("drive"), 0.0);
+    }
+
+    #[test]
+    fn bindings_read_their_own_controller() {
+        let mut actions = ActionMap::new();
+        actions.bind(
+            "intake",
+            ButtonBinding::hold(ControllerId::Partner, &[ControllerButton::A]),
+        );
+
+        actions.update_from(&state(&[ControllerButton::A], 0), &ControllerState::NEUTRAL);
+        assert!(!actions.is_active("intake"));
+
+        actions.update_from(&ControllerState::NEUTRAL, &state(&[ControllerButton::A], 0));
+        assert!(actions.is_active("intake"));
+    }
+}
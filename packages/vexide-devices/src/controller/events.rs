@@ -0,0 +1,204 @@
+//! Asynchronous button events.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use vex_sdk::V5_ControllerIndex;
+use vexide_core::time::Instant;
+
+use super::{button_pressed, Button, Controller, ControllerId};
+
+/// An event produced by a controller button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonEvent {
+    /// The button was pressed down.
+    Pressed,
+
+    /// The button was released.
+    Released,
+
+    /// The button has been held down for the contained duration.
+    ///
+    /// This is first produced once the button has been held for
+    /// [`ButtonEventConfig::hold_threshold`], and then again every
+    /// [`ButtonEventConfig::hold_repeat`] until it is released.
+    Held(Duration),
+
+    /// The button was pressed twice within [`ButtonEventConfig::double_tap_window`].
+    ///
+    /// This is produced immediately after the second [`ButtonEvent::Pressed`] event.
+    DoubleTap,
+}
+
+/// Timing settings for [`ButtonEvents`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonEventConfig {
+    /// How long the button must be held before [`ButtonEvent::Held`] is produced.
+    pub hold_threshold: Duration,
+
+    /// How often [`ButtonEvent::Held`] is repeated while the button stays held, or `None` to
+    /// only produce it once per press.
+    pub hold_repeat: Option<Duration>,
+
+    /// The maximum time between two presses for them to count as a [`ButtonEvent::DoubleTap`].
+    pub double_tap_window: Duration,
+}
+
+impl Default for ButtonEventConfig {
+    fn default() -> Self {
+        Self {
+            hold_threshold: Duration::from_millis(500),
+            hold_repeat: None,
+            double_tap_window: Duration::from_millis(300),
+        }
+    }
+}
+
+/// A stream of events from a single controller button.
+///
+/// The button is sampled every [`Controller::UPDATE_INTERVAL`] while the stream is being
+/// polled. Each stream tracks its own state, so any number of tasks can subscribe to the same
+/// button using [`Button::events`] without interfering with each other.
+///
+/// If the controller disconnects or the robot leaves driver control, the button is treated as
+/// released.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+///
+/// let mut events = peripherals.primary_controller.button_a.events();
+///
+/// while let Some(event) = events.next().await {
+///     match event {
+///         ButtonEvent::Pressed => println!("A pressed"),
+///         ButtonEvent::DoubleTap => println!("A double tapped"),
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ButtonEvents {
+    id: ControllerId,
+    channel: V5_ControllerIndex,
+    config: ButtonEventConfig,
+
+    /// The time of the most recent sample, or `None` if the button has not been sampled yet.
+    last_sample: Option<Instant>,
+
+    /// When the button was pressed, if it is currently held down.
+    pressed_at: Option<Instant>,
+
+    /// When the next [`ButtonEvent::Held`] event should be produced.
+    next_hold: Option<Instant>,
+
+    /// When the button was last pressed, if it may still become a double tap.
+    last_tap: Option<Instant>,
+
+    /// An event waiting to be returned on the next poll.
+    pending: Option<ButtonEvent>,
+}
+
+impl ButtonEvents {
+    /// Returns the timing settings used by this stream.
+    pub const fn config(&self) -> &ButtonEventConfig {
+        &self.config
+    }
+
+    /// Samples the button, returning an event if its state changed.
+    fn sample(&mut self, now: Instant) -> Option<ButtonEvent> {
+        let pressed = button_pressed(self.id, self.channel).unwrap_or(false);
+
+        match (pressed, self.pressed_at) {
+            (true, None) => {
+                self.pressed_at = Some(now);
+                self.next_hold = Some(now + self.config.hold_threshold);
+
+                match self.last_tap.take() {
+                    Some(last_tap) if now - last_tap <= self.config.double_tap_window => {
+                        self.pending = Some(ButtonEvent::DoubleTap);
+                    }
+                    _ => self.last_tap = Some(now),
+                }
+
+                Some(ButtonEvent::Pressed)
+            }
+            (false, Some(_)) => {
+                self.pressed_at = None;
+                self.next_hold = None;
+
+                Some(ButtonEvent::Released)
+            }
+            (true, Some(pressed_at)) => {
+                let next_hold = self.next_hold?;
+                if now < next_hold {
+                    return None;
+                }
+
+                self.next_hold = self.config.hold_repeat.map(|repeat| next_hold + repeat);
+
+                Some(ButtonEvent::Held(now - pressed_at))
+            }
+            (false, None) => None,
+        }
+    }
+}
+
+impl Stream for ButtonEvents {
+    type Item = ButtonEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(event) = this.pending.take() {
+            return Poll::Ready(Some(event));
+        }
+
+        // TODO: This should probably be done on a timer in the reactor.
+        cx.waker().wake_by_ref();
+
+        let now = Instant::now();
+        if this
+            .last_sample
+            .is_some_and(|last_sample| now - last_sample < Controller::UPDATE_INTERVAL)
+        {
+            return Poll::Pending;
+        }
+        this.last_sample = Some(now);
+
+        match this.sample(now) {
+            Some(event) => Poll::Ready(Some(event)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Button {
+    /// Returns a stream of events from this button using the default timing settings.
+    ///
+    /// Any number of streams can be created for the same button, and each will receive every
+    /// event independently.
+    pub fn events(&self) -> ButtonEvents {
+        self.events_with_config(ButtonEventConfig::default())
+    }
+
+    /// Returns a stream of events from this button using custom timing settings.
+    pub const fn events_with_config(&self, config: ButtonEventConfig) -> ButtonEvents {
+        ButtonEvents {
+            id: self.id,
+            channel: self.channel,
+            config,
+            last_sample: None,
+            pressed_at: None,
+            next_hold: None,
+            last_tap: None,
+            pending: None,
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Scrollable menus on the controller screen.
+
+use alloc::{format, string::String, vec::Vec};
+use core::ops::RangeInclusive;
+
+use vexide_core::float::Float;
+
+use super::{
+    state::read_state, writer::Delay, Controller, ControllerButton, ControllerError, ControllerId,
+    ControllerInput, ControllerScreen, ControllerState, ScreenWriter,
+};
+
+/// An entry in a [`Menu`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuItem {
+    /// An item that can be chosen by pressing A, such as an autonomous routine.
+    Action {
+        /// The text shown for the item.
+        label: String,
+    },
+
+    /// A number that can be adjusted with the left and right buttons, such as a tuning
+    /// constant.
+    Number {
+        /// The text shown before the value.
+        label: String,
+
+        /// The current value.
+        value: f64,
+
+        /// The range the value is kept within.
+        range: RangeInclusive<f64>,
+
+        /// How much each press of the left or right button changes the value.
+        step: f64,
+
+        /// The number of decimal places shown.
+        precision: usize,
+    },
+}
+
+impl MenuItem {
+    /// Creates an item that can be chosen by pressing A.
+    pub fn action(label: impl Into<String>) -> Self {
+        Self::Action {
+            label: label.into(),
+        }
+    }
+
+    /// Creates a number that can be adjusted in increments of `step` within `range`.
+    ///
+    /// The number of decimal places shown is chosen based on `step`.
+    pub fn number(
+        label: impl Into<String>,
+        value: f64,
+        range: RangeInclusive<f64>,
+        step: f64,
+    ) -> Self {
+        let precision = if step.fract() == 0.0 {
+            0
+        } else if (step * 10.0).fract() == 0.0 {
+            1
+        } else {
+            2
+        };
+
+        Self::Number {
+            label: label.into(),
+            value: value.clamp(*range.start(), *range.end()),
+            range,
+            step,
+            precision,
+        }
+    }
+
+    /// Returns the text shown for the item.
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Action { label } | Self::Number { label, .. } => label,
+        }
+    }
+
+    /// Returns the item's value if it is a [`MenuItem::Number`].
+    pub const fn value(&self) -> Option<f64> {
+        match self {
+            Self::Action { .. } => None,
+            Self::Number { value, .. } => Some(*value),
+        }
+    }
+
+    /// Returns the text shown on the screen for this item.
+    fn text(&self) -> String {
+        match self {
+            Self::Action { label } => label.clone(),
+            Self::Number {
+                label,
+                value,
+                precision,
+                ..
+            } => format!("{label}: {value:.precision$}"),
+        }
+    }
+}
+
+/// Something that happened as a result of input to a [`Menu`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuEvent {
+    /// The [`MenuItem::Action`] at the contained index was chosen with the A button.
+    Selected(usize),
+
+    /// The [`MenuItem::Number`] at `index` was changed to `value`.
+    ValueChanged {
+        /// The index of the item.
+        index: usize,
+
+        /// The item's new value.
+        value: f64,
+    },
+
+    /// The B button was pressed.
+    Back,
+}
+
+/// A scrollable list of items shown on the controller screen.
+///
+/// The up and down buttons move the cursor, A chooses the highlighted action, the left and
+/// right buttons adjust the highlighted number, and B goes back. The menu shows
+/// [`ControllerScreen::MAX_LINES`] items at a time, scrolling to keep the cursor visible.
+///
+/// # Examples
+///
+/// Choosing an autonomous routine before connecting to field control:
+///
+/// ```no_run
+/// let mut menu = Menu::new(vec![
+///     MenuItem::action("Left side"),
+///     MenuItem::action("Right side"),
+///     MenuItem::action("Skills"),
+/// ]);
+///
+/// let routine = menu.run(&mut controller).await?;
+/// ```
+///
+/// # Competition control
+///
+/// Controller input can only be read during driver control. While the robot is connected to a
+/// competition switch or field controller and is disabled or in autonomous, [`Menu::run`]
+/// returns [`ControllerError::CompetitionControl`]. Menus should therefore be used before the
+/// robot is connected to competition control, or during driver control.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    cursor: usize,
+    scroll: usize,
+    previous_state: ControllerState,
+}
+
+impl Menu {
+    /// Creates a new menu with the cursor on the first item.
+    pub const fn new(items: Vec<MenuItem>) -> Self {
+        Self {
+            items,
+            cursor: 0,
+            scroll: 0,
+            previous_state: ControllerState::NEUTRAL,
+        }
+    }
+
+    /// Returns the items in the menu.
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Returns the item at `index`.
+    pub fn item(&self, index: usize) -> Option<&MenuItem> {
+        self.items.get(index)
+    }
+
+    /// Returns the index of the highlighted item.
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to the item at `index`, if it exists.
+    pub fn set_cursor(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.cursor = index;
+            self.scroll_to_cursor();
+        }
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + ControllerScreen::MAX_LINES {
+            self.scroll = self.cursor + 1 - ControllerScreen::MAX_LINES;
+        }
+    }
+
+    /// Handles a new controller state, returning an event if one occurred.
+    ///
+    /// Buttons only take effect when they are first pressed, so this should be called with
+    /// every state read from the controller.
+    pub fn update(&mut self, state: ControllerState) -> Option<MenuEvent> {
+        let previous = core::mem::replace(&mut self.previous_state, state);
+        let just_pressed =
+            |button: ControllerButton| state.is_pressed(button) && !previous.is_pressed(button);
+
+        if self.items.is_empty() {
+            return just_pressed(ControllerButton::B).then_some(MenuEvent::Back);
+        }
+
+        if just_pressed(ControllerButton::Up) && self.cursor > 0 {
+            self.cursor -= 1;
+            self.scroll_to_cursor();
+        } else if just_pressed(ControllerButton::Down) && self.cursor + 1 < self.items.len() {
+            self.cursor += 1;
+            self.scroll_to_cursor();
+        }
+
+        if just_pressed(ControllerButton::B) {
+            return Some(MenuEvent::Back);
+        }
+
+        let index = self.cursor;
+        match &mut self.items[index] {
+            MenuItem::Action { .. } => {
+                just_pressed(ControllerButton::A).then_some(MenuEvent::Selected(index))
+            }
+            MenuItem::Number {
+                value, range, step, ..
+            } => {
+                let direction = if just_pressed(ControllerButton::Right) {
+                    1.0
+                } else if just_pressed(ControllerButton::Left) {
+                    -1.0
+                } else {
+                    return None;
+                };
+
+                *value = (*value + direction * *step).clamp(*range.start(), *range.end());
+
+                Some(MenuEvent::ValueChanged {
+                    index,
+                    value: *value,
+                })
+            }
+        }
+    }
+
+    /// Queues the visible part of the menu to be shown on the controller screen.
+    pub fn render(&self, writer: &mut ScreenWriter<'_>) -> Result<(), ControllerError> {
+        for line in 0..ControllerScreen::MAX_LINES {
+            let index = self.scroll + line;
+            let text = match self.items.get(index) {
+                Some(item) if index == self.cursor => format!(">{}", item.text()),
+                Some(item) => format!(" {}", item.text()),
+                None => String::new(),
+            };
+
+            writer.set_line(line as u8, &text)?;
+        }
+
+        Ok(())
+    }
+
+    /// Shows the menu on a controller's screen and handles its input until an action is
+    /// chosen or B is pressed.
+    ///
+    /// Returns the index of the chosen [`MenuItem::Action`], or `None` if B was pressed.
+    /// Numbers adjusted along the way keep their new values, and can be read afterwards with
+    /// [`Menu::item`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is disconnected, or
+    /// [`ControllerError::CompetitionControl`] if the robot is disabled or in autonomous under
+    /// competition control.
+    pub async fn run(
+        &mut self,
+        controller: &mut Controller,
+    ) -> Result<Option<usize>, ControllerError> {
+        let id = controller.id;
+        let mut writer = ScreenWriter::new(&mut controller.screen);
+
+        self.run_with(&IdInput(id), &mut writer).await
+    }
+
+    /// Shows the menu through an existing [`ScreenWriter`], reading input from any
+    /// [`ControllerInput`] such as a [`Playback`](super::Playback).
+    ///
+    /// See [`Menu::run`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input can't be read, or if the controller is disconnected.
+    pub async fn run_with(
+        &mut self,
+        controller: &impl ControllerInput,
+        writer: &mut ScreenWriter<'_>,
+    ) -> Result<Option<usize>, ControllerError> {
+        // Ignore buttons that were already held when the menu was opened.
+        self.previous_state = controller.state()?;
+
+        loop {
+            self.render(writer)?;
+            writer.update()?;
+
+            match self.update(controller.state()?) {
+                Some(MenuEvent::Selected(index)) => return Ok(Some(index)),
+                Some(MenuEvent::Back) => return Ok(None),
+                _ => {}
+            }
+
+            Delay::new(Controller::UPDATE_INTERVAL).await;
+        }
+    }
+}
+
+/// Reads a controller by its ID, so that [`Menu::run`] can borrow the controller's screen while
+/// reading its input.
+struct IdInput(ControllerId);
+
+impl ControllerInput for IdInput {
+    fn state(&self) -> Result<ControllerState, ControllerError> {
+        read_state(self.0)
+    }
+}
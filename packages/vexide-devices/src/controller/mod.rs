@@ -14,6 +14,28 @@ use vexide_core::{competition, competition::CompetitionMode};
 
 use crate::adi::digital::LogicLevel;
 
+mod bindings;
+mod events;
+mod menu;
+mod recording;
+mod rumble;
+mod shaping;
+mod state;
+mod writer;
+
+pub use bindings::{
+    ActionMap, AxisBinding, BindingMode, ButtonBinding, ControllerAxis, ControllerButton,
+};
+pub use events::{ButtonEvent, ButtonEventConfig, ButtonEvents};
+pub use menu::{Menu, MenuEvent, MenuItem};
+pub use recording::{Playback, RecordedState, Recorder, Recording, RecordingError};
+pub use rumble::{HapticPriority, HapticScheduler, Rumble, RumblePattern, RumblePatternError};
+pub use shaping::{
+    Deadband, InputProfile, ProfileError, ProfileSelector, ResponseCurve, ShapedInput, StickProfile,
+};
+pub use state::{ControllerInput, ControllerState};
+pub use writer::ScreenWriter;
+
 fn validate_connection(id: ControllerId) -> Result<(), ControllerError> {
     if unsafe {
         vexControllerConnectionStatusGet(id.into()) == V5_ControllerStatus::kV5ControllerOffline
@@ -32,16 +54,21 @@ pub struct Button {
     was_pressed: bool,
 }
 
+/// Reads whether a button is currently pressed.
+fn button_pressed(id: ControllerId, channel: V5_ControllerIndex) -> Result<bool, ControllerError> {
+    if competition::mode() != CompetitionMode::Driver {
+        return Err(ControllerError::CompetitionControl);
+    }
+
+    validate_connection(id)?;
+
+    Ok(unsafe { vexControllerGet(id.into(), channel) != 0 })
+}
+
 impl Button {
     /// Gets the current logic level of a digital input pin.
     pub fn level(&self) -> Result<LogicLevel, ControllerError> {
-        if competition::mode() != CompetitionMode::Driver {
-            return Err(ControllerError::CompetitionControl);
-        }
-
-        validate_connection(self.id)?;
-
-        let value = unsafe { vexControllerGet(self.id.into(), self.channel) != 0 };
+        let value = button_pressed(self.id, self.channel)?;
 
         let level = match value {
             true => LogicLevel::High,
@@ -60,6 +87,9 @@ impl Button {
 
     /// Returns `true` if the button has been pressed again since the last time this
     /// function was called.
+    ///
+    /// This stores its state in the button itself, so only one part of a program can use it
+    /// at a time. Prefer [`Button::events`] if several tasks need to react to the same button.
     pub fn was_pressed(&mut self) -> Result<bool, ControllerError> {
         if self.is_pressed()? {
             self.was_pressed = false;
@@ -72,6 +102,16 @@ impl Button {
     }
 }
 
+/// Reads the raw position of a joystick axis.
+fn axis_position(id: ControllerId, channel: V5_ControllerIndex) -> Result<i8, ControllerError> {
+    validate_connection(id)?;
+    if competition::mode() != CompetitionMode::Driver {
+        return Err(ControllerError::CompetitionControl);
+    }
+
+    Ok(unsafe { vexControllerGet(id.into(), channel) } as _)
+}
+
 /// Stores how far the joystick is away from the center (at *(0, 0)*) from -1 to 1.
 /// On the x axis left is negative, and right is positive.
 /// On the y axis down is negative, and up is positive.
@@ -98,22 +138,12 @@ impl Joystick {
 
     /// Gets the raw value of the joystick position on its x-axis from [-128, 127].
     pub fn x_raw(&self) -> Result<i8, ControllerError> {
-        validate_connection(self.id)?;
-        if competition::mode() != CompetitionMode::Driver {
-            return Err(ControllerError::CompetitionControl);
-        }
-
-        Ok(unsafe { vexControllerGet(self.id.into(), self.x_channel) } as _)
+        axis_position(self.id, self.x_channel)
     }
 
     /// Gets the raw value of the joystick position on its x-axis from [-128, 127].
     pub fn y_raw(&self) -> Result<i8, ControllerError> {
-        validate_connection(self.id)?;
-        if competition::mode() != CompetitionMode::Driver {
-            return Err(ControllerError::CompetitionControl);
-        }
-
-        Ok(unsafe { vexControllerGet(self.id.into(), self.y_channel) } as _)
+        axis_position(self.id, self.y_channel)
     }
 }
 
@@ -351,9 +381,14 @@ impl Controller {
     ///
     /// This function takes a string consisting of the characters '.', '-', and ' ', where
     /// dots are short rumbles, dashes are long rumbles, and spaces are pauses. Maximum
-    /// supported length is 8 characters.
-    pub fn rumble(&mut self, pattern: &str) -> Result<(), ControllerError> {
-        self.screen.set_text(pattern, 3, 0)
+    /// supported length is 8 characters. A [`RumblePattern`] can be used to build a pattern
+    /// that is checked against these limits.
+    ///
+    /// To play several patterns without them interrupting each other, use a
+    /// [`HapticScheduler`]. While a [`ScreenWriter`] is in use, queue patterns with
+    /// [`ScreenWriter::rumble`] instead so that they share its rate limit.
+    pub fn rumble(&mut self, pattern: impl AsRef<str>) -> Result<(), ControllerError> {
+        self.screen.set_text(pattern.as_ref(), 3, 0)
     }
 }
 
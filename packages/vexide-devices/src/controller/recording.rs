@@ -0,0 +1,330 @@
+//! Recording and playback of controller input.
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use snafu::Snafu;
+use vexide_core::{fs, io, time::Instant};
+
+use super::{ControllerError, ControllerInput, ControllerState};
+
+/// Bytes at the start of every serialized recording.
+const MAGIC: &[u8; 4] = b"VXRC";
+
+/// The version of the serialized recording format.
+const VERSION: u8 = 1;
+
+/// The size of a serialized header in bytes.
+const HEADER_SIZE: usize = MAGIC.len() + 1;
+
+/// The size of a serialized sample in bytes.
+const SAMPLE_SIZE: usize = 10;
+
+/// A controller state captured at a point in a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedState {
+    /// The time since the recording started.
+    pub timestamp: Duration,
+
+    /// The state of the controller.
+    pub state: ControllerState,
+}
+
+/// A sequence of controller states that can be saved to the SD card and played back later.
+///
+/// Recordings are stored in a compact binary format: a 5 byte header followed by 10 bytes per
+/// sample, so a one minute recording sampled every [`Controller::UPDATE_INTERVAL`] takes up
+/// about 24 KB.
+///
+/// [`Controller::UPDATE_INTERVAL`]: super::Controller::UPDATE_INTERVAL
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    samples: Vec<RecordedState>,
+}
+
+impl Recording {
+    /// Creates an empty recording.
+    pub const fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+
+    /// Returns the samples in the recording, ordered by timestamp.
+    pub fn samples(&self) -> &[RecordedState] {
+        &self.samples
+    }
+
+    /// Returns the timestamp of the last sample in the recording.
+    pub fn duration(&self) -> Duration {
+        self.samples
+            .last()
+            .map_or(Duration::ZERO, |sample| sample.timestamp)
+    }
+
+    /// Adds a sample to the end of the recording.
+    ///
+    /// Samples must be pushed in order, so `timestamp` is clamped to be no earlier than the
+    /// previous sample.
+    pub fn push(&mut self, timestamp: Duration, state: ControllerState) {
+        let timestamp = timestamp.max(self.duration());
+        self.samples.push(RecordedState { timestamp, state });
+    }
+
+    /// Returns the controller state at a time since the start of the recording.
+    ///
+    /// This is the most recent sample at or before `elapsed`, or [`ControllerState::NEUTRAL`]
+    /// if `elapsed` is before the first sample.
+    pub fn state_at(&self, elapsed: Duration) -> ControllerState {
+        let index = self
+            .samples
+            .partition_point(|sample| sample.timestamp <= elapsed);
+
+        index
+            .checked_sub(1)
+            .map_or(ControllerState::NEUTRAL, |index| self.samples[index].state)
+    }
+
+    /// Serializes the recording to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.samples.len() * SAMPLE_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+
+        for sample in &self.samples {
+            let timestamp = u32::try_from(sample.timestamp.as_millis()).unwrap_or(u32::MAX);
+            bytes.extend_from_slice(&timestamp.to_le_bytes());
+            bytes.extend(sample.state.axes_raw().map(|axis| axis as u8));
+            bytes.extend_from_slice(&sample.state.button_bits().to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Deserializes a recording from bytes created by [`Recording::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RecordingError> {
+        let Some(samples) = bytes.strip_prefix(MAGIC) else {
+            return Err(RecordingError::InvalidHeader);
+        };
+
+        match samples.split_first() {
+            Some((&VERSION, samples)) if samples.len() % SAMPLE_SIZE == 0 => {
+                let mut recording = Self::new();
+
+                for sample in samples.chunks_exact(SAMPLE_SIZE) {
+                    let timestamp =
+                        u32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]);
+                    let axes = [sample[4], sample[5], sample[6], sample[7]].map(|axis| axis as i8);
+                    let buttons = u16::from_le_bytes([sample[8], sample[9]]);
+
+                    recording.push(
+                        Duration::from_millis(timestamp.into()),
+                        ControllerState::from_raw(axes, buttons),
+                    );
+                }
+
+                Ok(recording)
+            }
+            Some((&VERSION, _)) => Err(RecordingError::Truncated),
+            _ => Err(RecordingError::InvalidHeader),
+        }
+    }
+
+    /// Saves the recording to a file on the SD card.
+    pub fn save(&self, path: &str) -> Result<(), RecordingError> {
+        fs::write(path, self.to_bytes()).map_err(|error| RecordingError::Io { error })
+    }
+
+    /// Loads a recording from a file on the SD card.
+    pub fn load(path: &str) -> Result<Self, RecordingError> {
+        Self::from_bytes(&fs::read(path).map_err(|error| RecordingError::Io { error })?)
+    }
+}
+
+/// Records the state of a controller over time.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut recorder = Recorder::new();
+///
+/// while recorder.elapsed() < Duration::from_secs(60) {
+///     recorder.sample(&controller)?;
+///     drive(&controller.state()?);
+///
+///     sleep(Controller::UPDATE_INTERVAL).await;
+/// }
+///
+/// recorder.finish().save("skills.rec")?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorder {
+    start: Instant,
+    recording: Recording,
+}
+
+impl Recorder {
+    /// Starts a new recording.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            recording: Recording::new(),
+        }
+    }
+
+    /// Returns the time since the recording started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Records the current state of a controller.
+    ///
+    /// This should be called every [`Controller::UPDATE_INTERVAL`], since the controller's state
+    /// doesn't update any faster than that.
+    ///
+    /// [`Controller::UPDATE_INTERVAL`]: super::Controller::UPDATE_INTERVAL
+    pub fn sample(&mut self, controller: &impl ControllerInput) -> Result<(), ControllerError> {
+        let state = controller.state()?;
+        self.recording.push(self.elapsed(), state);
+
+        Ok(())
+    }
+
+    /// Returns the recording so far.
+    pub const fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Stops recording and returns the finished recording.
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A virtual controller that replays a [`Recording`].
+///
+/// Playback created with [`Playback::new`] runs in real time, starting when the `Playback` is
+/// created. Playback created with [`Playback::manual`] only moves when
+/// [`Playback::seek`] or [`Playback::advance`] is called, which is useful for stepping through
+/// a recording frame by frame or for testing. Once the end of the recording is reached,
+/// the controller is reported as [`ControllerState::NEUTRAL`] so that the robot stops instead of
+/// repeating the last input forever.
+///
+/// # Examples
+///
+/// ```no_run
+/// let playback = Playback::new(Recording::load("skills.rec")?);
+///
+/// while !playback.is_finished() {
+///     drive(&playback.state()?);
+///     sleep(Controller::UPDATE_INTERVAL).await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playback {
+    clock: PlaybackClock,
+    recording: Recording,
+}
+
+/// How a [`Playback`] keeps track of its position in the recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlaybackClock {
+    /// Playback follows the brain's clock, having started at `start`.
+    RealTime { start: Instant },
+
+    /// Playback stays at `position` until moved.
+    Manual { position: Duration },
+}
+
+impl Playback {
+    /// Starts playing back a recording in real time.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            clock: PlaybackClock::RealTime {
+                start: Instant::now(),
+            },
+            recording,
+        }
+    }
+
+    /// Creates a playback of a recording that stays at the start of the recording until it is
+    /// moved with [`Playback::seek`] or [`Playback::advance`].
+    pub const fn manual(recording: Recording) -> Self {
+        Self {
+            clock: PlaybackClock::Manual {
+                position: Duration::ZERO,
+            },
+            recording,
+        }
+    }
+
+    /// Restarts playback from the beginning of the recording.
+    pub fn restart(&mut self) {
+        self.seek(Duration::ZERO);
+    }
+
+    /// Moves playback to a time since the start of the recording.
+    pub fn seek(&mut self, position: Duration) {
+        match &mut self.clock {
+            PlaybackClock::RealTime { start } => *start = Instant::now() - position,
+            PlaybackClock::Manual { position: current } => *current = position,
+        }
+    }
+
+    /// Moves playback forward by `duration`.
+    pub fn advance(&mut self, duration: Duration) {
+        self.seek(self.elapsed() + duration);
+    }
+
+    /// Returns the time since the start of the recording that playback is at.
+    pub fn elapsed(&self) -> Duration {
+        match self.clock {
+            PlaybackClock::RealTime { start } => start.elapsed(),
+            PlaybackClock::Manual { position } => position,
+        }
+    }
+
+    /// Returns `true` if playback has reached the end of the recording.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() > self.recording.duration()
+    }
+
+    /// Returns the recording being played back.
+    pub const fn recording(&self) -> &Recording {
+        &self.recording
+    }
+}
+
+impl ControllerInput for Playback {
+    fn state(&self) -> Result<ControllerState, ControllerError> {
+        if self.is_finished() {
+            return Ok(ControllerState::NEUTRAL);
+        }
+
+        Ok(self.recording.state_at(self.elapsed()))
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when loading or saving a [`Recording`].
+pub enum RecordingError {
+    /// The data does not start with a valid recording header, or uses an unsupported version
+    /// of the format.
+    InvalidHeader,
+
+    /// The data ends partway through a sample.
+    Truncated,
+
+    /// The recording could not be read from or written to the SD card.
+    #[snafu(display("{error}"))]
+    Io {
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+}
@@ -0,0 +1,534 @@
+//! Typed rumble patterns and haptic notifications.
+
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr, time::Duration};
+
+use snafu::Snafu;
+use vexide_core::{
+    competition::{CompetitionMode, MatchClock, MatchDurations},
+    time::Instant,
+};
+
+use super::{ControllerError, ScreenWriter};
+
+/// A single element of a [`RumblePattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rumble {
+    /// A short rumble, written as `.`.
+    Short,
+
+    /// A long rumble, written as `-`.
+    Long,
+
+    /// A pause, written as a space.
+    Pause,
+}
+
+impl Rumble {
+    /// Returns the character used for this element in the controller's pattern string.
+    pub const fn as_char(self) -> char {
+        match self {
+            Self::Short => '.',
+            Self::Long => '-',
+            Self::Pause => ' ',
+        }
+    }
+
+    /// Returns the element written as `c` in a pattern string.
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Short),
+            '-' => Some(Self::Long),
+            ' ' => Some(Self::Pause),
+            _ => None,
+        }
+    }
+
+    /// Returns roughly how long the controller takes to play this element.
+    pub const fn estimated_duration(self) -> Duration {
+        match self {
+            Self::Short | Self::Pause => Duration::from_millis(150),
+            Self::Long => Duration::from_millis(450),
+        }
+    }
+}
+
+/// A sequence of rumbles that can be sent to a controller.
+///
+/// Patterns can be built up one element at a time. When built in a `const`, a pattern that is
+/// too long is a compile-time error:
+///
+/// ```
+/// const PIECE_LOADED: RumblePattern = RumblePattern::new().short().short();
+/// const OVERHEATING: RumblePattern = RumblePattern::new().long().pause().long();
+/// ```
+///
+/// Patterns can also be parsed at runtime from the same string format accepted by
+/// [`Controller::rumble`](super::Controller::rumble).
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RumblePattern {
+    elements: [u8; Self::MAX_LENGTH],
+    len: usize,
+}
+
+impl RumblePattern {
+    /// The maximum number of elements the controller supports in one pattern.
+    pub const MAX_LENGTH: usize = 8;
+
+    /// Creates an empty pattern.
+    pub const fn new() -> Self {
+        Self {
+            elements: [0; Self::MAX_LENGTH],
+            len: 0,
+        }
+    }
+
+    /// Adds an element to the end of the pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RumblePatternError::TooLong`] if the pattern already has
+    /// [`RumblePattern::MAX_LENGTH`] elements.
+    pub fn try_push(&mut self, rumble: Rumble) -> Result<(), RumblePatternError> {
+        if self.len == Self::MAX_LENGTH {
+            return Err(RumblePatternError::TooLong);
+        }
+
+        self.elements[self.len] = rumble.as_char() as u8;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Returns the pattern with an element added to the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern already has [`RumblePattern::MAX_LENGTH`] elements.
+    pub const fn then(mut self, rumble: Rumble) -> Self {
+        assert!(
+            self.len < Self::MAX_LENGTH,
+            "rumble patterns can have at most 8 elements"
+        );
+
+        self.elements[self.len] = rumble.as_char() as u8;
+        self.len += 1;
+        self
+    }
+
+    /// Returns the pattern with a short rumble added to the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern already has [`RumblePattern::MAX_LENGTH`] elements.
+    pub const fn short(self) -> Self {
+        self.then(Rumble::Short)
+    }
+
+    /// Returns the pattern with a long rumble added to the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern already has [`RumblePattern::MAX_LENGTH`] elements.
+    pub const fn long(self) -> Self {
+        self.then(Rumble::Long)
+    }
+
+    /// Returns the pattern with a pause added to the end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern already has [`RumblePattern::MAX_LENGTH`] elements.
+    pub const fn pause(self) -> Self {
+        self.then(Rumble::Pause)
+    }
+
+    /// Returns the number of elements in the pattern.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the pattern has no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the elements of the pattern.
+    pub fn iter(&self) -> impl Iterator<Item = Rumble> + '_ {
+        self.as_str().chars().filter_map(Rumble::from_char)
+    }
+
+    /// Returns the pattern in the string format accepted by
+    /// [`Controller::rumble`](super::Controller::rumble).
+    pub fn as_str(&self) -> &str {
+        // Every element is one of the ASCII characters `.`, `-` or ` `.
+        core::str::from_utf8(&self.elements[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns roughly how long the controller takes to play the whole pattern.
+    pub fn estimated_duration(&self) -> Duration {
+        self.iter().map(Rumble::estimated_duration).sum()
+    }
+}
+
+impl AsRef<str> for RumblePattern {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for RumblePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RumblePattern")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl fmt::Display for RumblePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RumblePattern {
+    type Err = RumblePatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pattern = Self::new();
+
+        for c in s.chars() {
+            let rumble = Rumble::from_char(c).ok_or(RumblePatternError::InvalidCharacter { c })?;
+            pattern.try_push(rumble)?;
+        }
+
+        Ok(pattern)
+    }
+}
+
+/// How important a haptic notification is.
+///
+/// When several notifications are waiting, the most important one is played first.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HapticPriority {
+    /// Nice-to-know information, such as a game piece being loaded.
+    Low,
+
+    /// Information the driver should act on, such as match time alerts.
+    #[default]
+    Normal,
+
+    /// Warnings that need immediate attention, such as a motor overheating.
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QueuedNotification {
+    pattern: RumblePattern,
+    priority: HapticPriority,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MatchAlert {
+    remaining: Duration,
+    notification: QueuedNotification,
+    fired: bool,
+}
+
+/// A queue of haptic notifications for a controller.
+///
+/// The controller can only play one rumble pattern at a time, and a new pattern replaces one
+/// that is still playing. `HapticScheduler` queues notifications and plays them one after
+/// another, most important first, waiting for each pattern to finish before starting the next.
+/// A notification that is already waiting is not queued again, so calling
+/// [`HapticScheduler::notify`] repeatedly won't stack up copies of the same pattern.
+///
+/// The scheduler can also play notifications automatically when a given amount of time is left
+/// in the driver control period, using [`HapticScheduler::alert_at`]. Match time is tracked
+/// with a [`MatchClock`].
+///
+/// Notifications are sent through a [`ScreenWriter`], so that they share its rate limit with
+/// screen updates. They are only sent when [`HapticScheduler::update`] is called, which should
+/// happen regularly (such as once per loop of driver control).
+///
+/// # Examples
+///
+/// ```no_run
+/// const THIRTY_SECONDS: RumblePattern = RumblePattern::new().long().long();
+/// const OVERHEATING: RumblePattern = RumblePattern::new().short().short().short();
+///
+/// let mut writer = ScreenWriter::new(&mut controller.screen);
+/// let mut haptics = HapticScheduler::new();
+/// haptics.alert_at(Duration::from_secs(30), THIRTY_SECONDS, HapticPriority::Normal);
+///
+/// loop {
+///     if intake.temperature()? > 55.0 {
+///         haptics.notify(OVERHEATING, HapticPriority::High);
+///     }
+///
+///     haptics.update(&mut writer)?;
+///     sleep(Controller::UPDATE_INTERVAL).await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HapticScheduler {
+    queue: Vec<QueuedNotification>,
+    alerts: Vec<MatchAlert>,
+    clock: MatchClock,
+
+    /// The start of the driver control period that alerts were last checked in.
+    driver_start: Option<Instant>,
+
+    /// When the controller will be ready to play another pattern.
+    busy_until: Option<Instant>,
+}
+
+impl HapticScheduler {
+    /// The pause left between two notifications, so that they don't run together.
+    pub const GAP: Duration = Duration::from_millis(250);
+
+    /// The maximum number of notifications that can be waiting at once.
+    ///
+    /// When the queue is full, the least important notification is dropped.
+    pub const MAX_QUEUED: usize = 8;
+
+    /// Creates a new scheduler with no notifications or alerts.
+    ///
+    /// Match time alerts assume a standard match by default. Use
+    /// [`HapticScheduler::set_durations`] for skills runs.
+    pub fn new() -> Self {
+        Self {
+            queue: Vec::new(),
+            alerts: Vec::new(),
+            clock: MatchClock::new(MatchDurations::MATCH),
+            driver_start: None,
+            busy_until: None,
+        }
+    }
+
+    /// Sets the period lengths used for match time alerts.
+    pub fn set_durations(&mut self, durations: MatchDurations) {
+        self.clock.set_durations(durations);
+    }
+
+    /// Queues a notification to be played as soon as possible.
+    ///
+    /// Nothing happens if the same pattern is already waiting with the same or higher priority.
+    pub fn notify(&mut self, pattern: RumblePattern, priority: HapticPriority) {
+        if pattern.is_empty() {
+            return;
+        }
+
+        if let Some(queued) = self
+            .queue
+            .iter_mut()
+            .find(|queued| queued.pattern == pattern)
+        {
+            queued.priority = queued.priority.max(priority);
+            return;
+        }
+
+        if self.queue.len() == Self::MAX_QUEUED {
+            // Drop the least important (and among those, the newest) notification.
+            let Some((index, least)) = self
+                .queue
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by_key(|(_, queued)| queued.priority)
+            else {
+                return;
+            };
+
+            if least.priority > priority {
+                return;
+            }
+            self.queue.remove(index);
+        }
+
+        self.queue.push(QueuedNotification { pattern, priority });
+    }
+
+    /// Plays a notification when `remaining` time is left in each driver control period.
+    ///
+    /// If the scheduler first sees a driver control period after `remaining` time is left,
+    /// the alert is skipped for that period.
+    pub fn alert_at(
+        &mut self,
+        remaining: Duration,
+        pattern: RumblePattern,
+        priority: HapticPriority,
+    ) {
+        self.alerts.push(MatchAlert {
+            remaining,
+            notification: QueuedNotification { pattern, priority },
+            fired: false,
+        });
+    }
+
+    /// Removes every waiting notification and match time alert.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.alerts.clear();
+    }
+
+    /// Returns the number of notifications waiting to be played.
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Queues any due match time alerts, passes the next notification to `writer` if the
+    /// controller has finished playing the previous one, and then updates `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is disconnected. The notification stays queued in
+    /// the writer and will be played once the controller reconnects.
+    pub fn update(&mut self, writer: &mut ScreenWriter<'_>) -> Result<(), ControllerError> {
+        let now = Instant::now();
+
+        self.update_alerts();
+
+        if self.busy_until.is_some_and(|busy_until| now < busy_until) {
+            return writer.update();
+        }
+
+        // The most important notification, and the oldest among equally important ones.
+        let Some((index, next)) = self
+            .queue
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, queued)| queued.priority)
+        else {
+            return writer.update();
+        };
+
+        writer.rumble(next.pattern)?;
+        self.busy_until =
+            Some(now + next.pattern.estimated_duration() + Self::GAP + ScreenWriter::MIN_INTERVAL);
+        self.queue.remove(index);
+
+        writer.update()
+    }
+
+    fn update_alerts(&mut self) {
+        let driver_start = match self.clock.mode() {
+            CompetitionMode::Driver => self.clock.period_start(),
+            _ => None,
+        };
+
+        let new_period = driver_start != self.driver_start;
+        self.driver_start = driver_start;
+
+        let remaining = self.clock.remaining().filter(|_| driver_start.is_some());
+
+        for notification in due_alerts(&mut self.alerts, new_period, remaining) {
+            self.notify(notification.pattern, notification.priority);
+        }
+    }
+}
+
+/// Marks the alerts that are due with `remaining` time left in driver control as fired, and
+/// returns their notifications.
+///
+/// When a new period starts, alerts are re-armed, except for those whose time has already
+/// passed (for example, when the program starts partway through driver control), so that they
+/// don't all fire at once.
+fn due_alerts(
+    alerts: &mut [MatchAlert],
+    new_period: bool,
+    remaining: Option<Duration>,
+) -> Vec<QueuedNotification> {
+    if new_period {
+        for alert in alerts.iter_mut() {
+            alert.fired = remaining.is_some_and(|remaining| remaining < alert.remaining);
+        }
+    }
+
+    let Some(remaining) = remaining else {
+        return Vec::new();
+    };
+
+    alerts
+        .iter_mut()
+        .filter(|alert| !alert.fired && remaining <= alert.remaining)
+        .map(|alert| {
+            alert.fired = true;
+            alert.notification
+        })
+        .collect()
+}
+
+impl Default for HapticScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when building a [`RumblePattern`].
+pub enum RumblePatternError {
+    /// The pattern has more than [`RumblePattern::MAX_LENGTH`] elements.
+    TooLong,
+
+    /// The pattern string contains a character other than `.`, `-` or a space.
+    #[snafu(display("invalid rumble pattern character {c:?}"))]
+    InvalidCharacter {
+        /// The invalid character.
+        c: char,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const fn alert(seconds: u64) -> MatchAlert {
+        MatchAlert {
+            remaining: Duration::from_secs(seconds),
+            notification: QueuedNotification {
+                pattern: RumblePattern::new().short(),
+                priority: HapticPriority::Normal,
+            },
+            fired: false,
+        }
+    }
+
+    fn fire(alerts: &mut [MatchAlert], new_period: bool, remaining: Option<u64>) -> usize {
+        due_alerts(alerts, new_period, remaining.map(Duration::from_secs)).len()
+    }
+
+    #[test]
+    fn alerts_fire_once_per_period() {
+        let mut alerts = [alert(30), alert(10)];
+
+        assert_eq!(fire(&mut alerts, true, Some(105)), 0);
+        assert_eq!(fire(&mut alerts, false, Some(30)), 1);
+        assert_eq!(fire(&mut alerts, false, Some(20)), 0);
+        assert_eq!(fire(&mut alerts, false, Some(5)), 1);
+        assert_eq!(fire(&mut alerts, false, Some(0)), 0);
+
+        // Leaving driver control and starting a new period re-arms every alert.
+        assert_eq!(fire(&mut alerts, true, None), 0);
+        assert_eq!(fire(&mut alerts, true, Some(105)), 0);
+        assert_eq!(fire(&mut alerts, false, Some(25)), 1);
+    }
+
+    #[test]
+    fn passed_alerts_are_skipped_when_starting_mid_period() {
+        let mut alerts = [alert(60), alert(30), alert(10)];
+
+        assert_eq!(fire(&mut alerts, true, Some(20)), 0);
+        assert_eq!(fire(&mut alerts, false, Some(15)), 0);
+        assert_eq!(fire(&mut alerts, false, Some(10)), 1);
+    }
+
+    #[test]
+    fn alert_due_at_period_start_fires() {
+        let mut alerts = [alert(30)];
+
+        assert_eq!(fire(&mut alerts, true, Some(30)), 1);
+        assert_eq!(fire(&mut alerts, false, Some(29)), 0);
+    }
+}
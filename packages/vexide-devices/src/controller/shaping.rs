@@ -0,0 +1,515 @@
+//! Joystick input shaping and driver profiles.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use snafu::Snafu;
+use vexide_core::{float::Float, fs, io};
+
+use super::{ControllerAxis, ControllerButton, ControllerError, ControllerInput, ControllerScreen};
+
+/// A region around the center of a joystick where input is ignored.
+///
+/// Joysticks rarely return exactly to zero when released, so a small deadband prevents the robot
+/// from creeping. Input outside of the deadband is rescaled so that output still starts at zero
+/// at the edge of the deadband and reaches 1.0 at full deflection.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Deadband {
+    /// No deadband.
+    #[default]
+    None,
+
+    /// Each axis is zeroed independently when within the given distance of the center.
+    ///
+    /// This makes it easy to drive in a perfectly straight line, since small sideways movements
+    /// are ignored.
+    Square(f32),
+
+    /// Both axes are zeroed when the stick is within the given distance of the center.
+    ///
+    /// This preserves the direction the stick is pushed in, which is better for holonomic
+    /// drivetrains.
+    Circular(f32),
+}
+
+impl Deadband {
+    /// Applies the deadband to a joystick position.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        match *self {
+            Self::None => (x, y),
+            Self::Square(width) => (rescale(x, width), rescale(y, width)),
+            Self::Circular(radius) => {
+                let magnitude = x.hypot(y);
+
+                if magnitude <= radius || magnitude == 0.0 {
+                    (0.0, 0.0)
+                } else {
+                    let scale = rescale(magnitude, radius).min(1.0) / magnitude;
+                    (x * scale, y * scale)
+                }
+            }
+        }
+    }
+}
+
+/// Zeroes a value within `width` of zero, and rescales the rest to start at zero.
+fn rescale(value: f32, width: f32) -> f32 {
+    let width = width.clamp(0.0, 0.99);
+
+    if value.abs() <= width {
+        0.0
+    } else {
+        value.signum() * (value.abs() - width) / (1.0 - width)
+    }
+}
+
+/// A curve that maps joystick input to output.
+///
+/// Curves make small movements of the joystick more precise while still allowing full speed at
+/// full deflection. Every curve maps 0.0 to 0.0 and ±1.0 to ±1.0.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum ResponseCurve {
+    /// Output is equal to input.
+    #[default]
+    Linear,
+
+    /// An exponential curve, `(e^(k|x|) - 1) / (e^k - 1)`, with the given steepness `k`.
+    ///
+    /// Larger values of `k` make small inputs produce smaller outputs. A `k` of around 2 to 5 is
+    /// typical.
+    Exponential(f32),
+
+    /// A blend between a linear and cubic response, `(1 - w)x + wx³`, with the given weight `w`
+    /// from 0.0 (linear) to 1.0 (fully cubic).
+    Cubic(f32),
+}
+
+impl ResponseCurve {
+    /// Applies the curve to a single axis value from -1.0 to 1.0.
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(-1.0, 1.0);
+
+        match *self {
+            Self::Linear => value,
+            Self::Exponential(steepness) if steepness.abs() < f32::EPSILON => value,
+            Self::Exponential(steepness) => {
+                value.signum() * ((steepness * value.abs()).exp() - 1.0) / (steepness.exp() - 1.0)
+            }
+            Self::Cubic(weight) => {
+                let weight = weight.clamp(0.0, 1.0);
+                (1.0 - weight) * value + weight * value * value * value
+            }
+        }
+    }
+}
+
+/// Shaping settings for a single joystick.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct StickProfile {
+    /// The deadband applied to the stick.
+    pub deadband: Deadband,
+
+    /// The response curve applied to each axis after the deadband.
+    pub curve: ResponseCurve,
+
+    /// Whether to invert the x axis.
+    pub invert_x: bool,
+
+    /// Whether to invert the y axis.
+    pub invert_y: bool,
+}
+
+impl StickProfile {
+    /// Shapes a raw joystick position.
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = self.deadband.apply(x, y);
+        let (x, y) = (self.curve.apply(x), self.curve.apply(y));
+
+        (
+            if self.invert_x { -x } else { x },
+            if self.invert_y { -y } else { y },
+        )
+    }
+}
+
+/// Controller input after being shaped by an [`InputProfile`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ShapedInput {
+    /// The shaped x position of the (possibly swapped) left joystick.
+    pub left_x: f32,
+
+    /// The shaped y position of the (possibly swapped) left joystick.
+    pub left_y: f32,
+
+    /// The shaped x position of the (possibly swapped) right joystick.
+    pub right_x: f32,
+
+    /// The shaped y position of the (possibly swapped) right joystick.
+    pub right_y: f32,
+
+    /// Whether the (possibly swapped) top left trigger is pressed.
+    pub left_trigger_1: bool,
+
+    /// Whether the (possibly swapped) bottom left trigger is pressed.
+    pub left_trigger_2: bool,
+
+    /// Whether the (possibly swapped) top right trigger is pressed.
+    pub right_trigger_1: bool,
+
+    /// Whether the (possibly swapped) bottom right trigger is pressed.
+    pub right_trigger_2: bool,
+}
+
+/// A driver's preferred controller settings.
+///
+/// Profiles can be converted to and from a simple text format using [`Display`] and
+/// [`FromStr`], and saved to or loaded from the SD card with [`InputProfile::save`] and
+/// [`InputProfile::load`]. The format has one `key = value` setting per line:
+///
+/// ```text
+/// name = Alice
+/// swap_sticks = false
+/// swap_triggers = false
+/// left.deadband = circular 0.05
+/// left.curve = cubic 0.4
+/// left.invert_x = false
+/// left.invert_y = false
+/// right.deadband = square 0.05
+/// right.curve = exponential 3
+/// right.invert_x = false
+/// right.invert_y = false
+/// ```
+///
+/// Settings that are left out keep their default values. Blank lines and lines starting with
+/// `#` are ignored.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct InputProfile {
+    /// The name of the profile, usually the driver's name.
+    pub name: String,
+
+    /// Shaping settings for the left joystick.
+    pub left_stick: StickProfile,
+
+    /// Shaping settings for the right joystick.
+    pub right_stick: StickProfile,
+
+    /// Whether to swap the left and right joysticks.
+    pub swap_sticks: bool,
+
+    /// Whether to swap the left and right triggers.
+    pub swap_triggers: bool,
+}
+
+impl InputProfile {
+    /// Creates a new profile with no shaping or remapping.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Reads the controller's joysticks and triggers, applying this profile's shaping and
+    /// remapping.
+    ///
+    /// This works with any [`ControllerInput`], including recordings played back with
+    /// [`Playback`](super::Playback).
+    pub fn read(&self, controller: &impl ControllerInput) -> Result<ShapedInput, ControllerError> {
+        let state = controller.state()?;
+
+        let (mut left_stick, mut right_stick) = (
+            (
+                state.axis(ControllerAxis::LeftX),
+                state.axis(ControllerAxis::LeftY),
+            ),
+            (
+                state.axis(ControllerAxis::RightX),
+                state.axis(ControllerAxis::RightY),
+            ),
+        );
+        if self.swap_sticks {
+            core::mem::swap(&mut left_stick, &mut right_stick);
+        }
+
+        let (left_x, left_y) = self.left_stick.apply(left_stick.0, left_stick.1);
+        let (right_x, right_y) = self.right_stick.apply(right_stick.0, right_stick.1);
+
+        let mut input = ShapedInput {
+            left_x,
+            left_y,
+            right_x,
+            right_y,
+            left_trigger_1: state.is_pressed(ControllerButton::L1),
+            left_trigger_2: state.is_pressed(ControllerButton::L2),
+            right_trigger_1: state.is_pressed(ControllerButton::R1),
+            right_trigger_2: state.is_pressed(ControllerButton::R2),
+        };
+
+        if self.swap_triggers {
+            core::mem::swap(&mut input.left_trigger_1, &mut input.right_trigger_1);
+            core::mem::swap(&mut input.left_trigger_2, &mut input.right_trigger_2);
+        }
+
+        Ok(input)
+    }
+
+    /// Saves the profile to a file on the SD card.
+    pub fn save(&self, path: &str) -> Result<(), ProfileError> {
+        fs::write(path, self.to_string()).map_err(|error| ProfileError::Io { error })
+    }
+
+    /// Loads a profile from a file on the SD card.
+    pub fn load(path: &str) -> Result<Self, ProfileError> {
+        fs::read_to_string(path)
+            .map_err(|error| ProfileError::Io { error })?
+            .parse()
+    }
+}
+
+impl Display for Deadband {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Square(width) => write!(f, "square {width}"),
+            Self::Circular(radius) => write!(f, "circular {radius}"),
+        }
+    }
+}
+
+impl Display for ResponseCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => write!(f, "linear"),
+            Self::Exponential(steepness) => write!(f, "exponential {steepness}"),
+            Self::Cubic(weight) => write!(f, "cubic {weight}"),
+        }
+    }
+}
+
+impl Display for InputProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "name = {}", self.name)?;
+        writeln!(f, "swap_sticks = {}", self.swap_sticks)?;
+        writeln!(f, "swap_triggers = {}", self.swap_triggers)?;
+
+        for (prefix, stick) in [("left", &self.left_stick), ("right", &self.right_stick)] {
+            writeln!(f, "{prefix}.deadband = {}", stick.deadband)?;
+            writeln!(f, "{prefix}.curve = {}", stick.curve)?;
+            writeln!(f, "{prefix}.invert_x = {}", stick.invert_x)?;
+            writeln!(f, "{prefix}.invert_y = {}", stick.invert_y)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a setting with a kind and an optional numeric parameter, like `cubic 0.4`.
+fn parse_parameterized(value: &str) -> Option<(&str, Option<f32>)> {
+    let mut parts = value.split_whitespace();
+    let kind = parts.next()?;
+    let parameter = match parts.next() {
+        Some(parameter) => Some(parameter.parse().ok()?),
+        None => None,
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((kind, parameter))
+}
+
+impl FromStr for Deadband {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_parameterized(s).ok_or(())? {
+            ("none", None) => Ok(Self::None),
+            ("square", Some(width)) => Ok(Self::Square(width)),
+            ("circular", Some(radius)) => Ok(Self::Circular(radius)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromStr for ResponseCurve {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_parameterized(s).ok_or(())? {
+            ("linear", None) => Ok(Self::Linear),
+            ("exponential", Some(steepness)) => Ok(Self::Exponential(steepness)),
+            ("cubic", Some(weight)) => Ok(Self::Cubic(weight)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromStr for InputProfile {
+    type Err = ProfileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut profile = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || ProfileError::Parse { line: index + 1 };
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let parse_bool = |value: &str| value.parse::<bool>().map_err(|_| error());
+
+            match key.split_once('.') {
+                None => match key {
+                    "name" => profile.name = value.into(),
+                    "swap_sticks" => profile.swap_sticks = parse_bool(value)?,
+                    "swap_triggers" => profile.swap_triggers = parse_bool(value)?,
+                    _ => return Err(error()),
+                },
+                Some((stick, setting)) => {
+                    let stick = match stick {
+                        "left" => &mut profile.left_stick,
+                        "right" => &mut profile.right_stick,
+                        _ => return Err(error()),
+                    };
+
+                    match setting {
+                        "deadband" => stick.deadband = value.parse().map_err(|()| error())?,
+                        "curve" => stick.curve = value.parse().map_err(|()| error())?,
+                        "invert_x" => stick.invert_x = parse_bool(value)?,
+                        "invert_y" => stick.invert_y = parse_bool(value)?,
+                        _ => return Err(error()),
+                    }
+                }
+            }
+        }
+
+        Ok(profile)
+    }
+}
+
+/// A set of driver profiles that can be switched between at runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut profiles = ProfileSelector::new(vec![
+///     InputProfile::load("alice.txt")?,
+///     InputProfile::load("bob.txt")?,
+/// ]);
+///
+/// let mut next_profile = controller.button_right.events();
+///
+/// loop {
+///     if let Some(ButtonEvent::Pressed) = next_profile.next().now_or_never().flatten() {
+///         profiles.select_next();
+///         profiles.show(&mut controller.screen)?;
+///     }
+///
+///     let input = profiles.active().read(&controller)?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileSelector {
+    profiles: Vec<InputProfile>,
+    active: usize,
+}
+
+impl ProfileSelector {
+    /// Creates a new selector with the first profile active.
+    ///
+    /// If `profiles` is empty, a single default profile is used.
+    pub fn new(mut profiles: Vec<InputProfile>) -> Self {
+        if profiles.is_empty() {
+            profiles.push(InputProfile::default());
+        }
+
+        Self {
+            profiles,
+            active: 0,
+        }
+    }
+
+    /// Returns the active profile.
+    pub fn active(&self) -> &InputProfile {
+        &self.profiles[self.active]
+    }
+
+    /// Returns a mutable reference to the active profile.
+    pub fn active_mut(&mut self) -> &mut InputProfile {
+        &mut self.profiles[self.active]
+    }
+
+    /// Returns every profile in the selector.
+    pub fn profiles(&self) -> &[InputProfile] {
+        &self.profiles
+    }
+
+    /// Switches to the next profile, wrapping around after the last one.
+    pub fn select_next(&mut self) -> &InputProfile {
+        self.active = (self.active + 1) % self.profiles.len();
+        self.active()
+    }
+
+    /// Switches to the previous profile, wrapping around before the first one.
+    pub fn select_previous(&mut self) -> &InputProfile {
+        self.active = (self.active + self.profiles.len() - 1) % self.profiles.len();
+        self.active()
+    }
+
+    /// Switches to the profile with the given name.
+    ///
+    /// Returns `None` and leaves the active profile unchanged if no profile has that name.
+    pub fn select(&mut self, name: &str) -> Option<&InputProfile> {
+        self.active = self
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)?;
+
+        Some(self.active())
+    }
+
+    /// Writes the name of the active profile to the first line of a controller's screen.
+    pub fn show(&self, screen: &mut ControllerScreen) -> Result<(), ControllerError> {
+        // The controller drops writes that arrive too close together, so the name is padded
+        // to overwrite the whole line in a single write rather than clearing it first.
+        let line: String = self
+            .active()
+            .name
+            .chars()
+            .chain(core::iter::repeat(' '))
+            .take(ControllerScreen::MAX_LINE_LENGTH)
+            .collect();
+
+        screen.set_text(&line, 0, 0)
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when loading or saving an [`InputProfile`].
+pub enum ProfileError {
+    /// A line of the profile could not be parsed.
+    #[snafu(display("invalid setting on line {line}"))]
+    Parse {
+        /// The line (starting at 1) that could not be parsed.
+        line: usize,
+    },
+
+    /// The profile could not be read from or written to the SD card.
+    #[snafu(display("{error}"))]
+    Io {
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+}
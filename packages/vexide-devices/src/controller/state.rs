@@ -0,0 +1,114 @@
+//! Snapshots of controller input.
+
+use super::{
+    axis_position, button_pressed, Controller, ControllerAxis, ControllerButton, ControllerError,
+    ControllerId,
+};
+
+/// The state of every joystick and button on a controller at a single point in time.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ControllerState {
+    /// The raw position of each joystick axis from -127 to 127, indexed by [`ControllerAxis`].
+    axes: [i8; 4],
+
+    /// A bitmask of the pressed buttons, indexed by [`ControllerButton`].
+    buttons: u16,
+}
+
+impl ControllerState {
+    /// A state with every joystick centered and every button released.
+    pub const NEUTRAL: Self = Self {
+        axes: [0; 4],
+        buttons: 0,
+    };
+
+    /// Returns the raw position of a joystick axis from -127 to 127.
+    pub const fn axis_raw(&self, axis: ControllerAxis) -> i8 {
+        self.axes[axis as usize]
+    }
+
+    /// Returns the position of a joystick axis from -1.0 to 1.0.
+    pub fn axis(&self, axis: ControllerAxis) -> f32 {
+        self.axis_raw(axis) as f32 / 127.0
+    }
+
+    /// Sets the raw position of a joystick axis.
+    pub fn set_axis_raw(&mut self, axis: ControllerAxis, position: i8) {
+        self.axes[axis as usize] = position;
+    }
+
+    /// Returns `true` if the button is pressed.
+    pub const fn is_pressed(&self, button: ControllerButton) -> bool {
+        self.buttons & (1 << button as u16) != 0
+    }
+
+    /// Sets whether a button is pressed.
+    pub fn set_pressed(&mut self, button: ControllerButton, pressed: bool) {
+        if pressed {
+            self.buttons |= 1 << button as u16;
+        } else {
+            self.buttons &= !(1 << button as u16);
+        }
+    }
+
+    /// Returns the pressed buttons as a bitmask, where bit `n` is set if the button with
+    /// discriminant `n` in [`ControllerButton`] is pressed.
+    pub const fn button_bits(&self) -> u16 {
+        self.buttons
+    }
+
+    /// Creates a state from raw axis positions (in [`ControllerAxis`] order) and a bitmask of
+    /// pressed buttons as returned by [`ControllerState::button_bits`].
+    pub const fn from_raw(axes: [i8; 4], buttons: u16) -> Self {
+        Self { axes, buttons }
+    }
+
+    /// Returns the raw axis positions in [`ControllerAxis`] order.
+    pub const fn axes_raw(&self) -> [i8; 4] {
+        self.axes
+    }
+}
+
+/// A source of controller input.
+///
+/// This is implemented by [`Controller`], which reads from a physical controller, and by
+/// [`Playback`](super::Playback), which replays a recording. Drive code written against this
+/// trait works with either. A [`ControllerState`] can also be used directly as a controller
+/// that never changes, such as [`ControllerState::NEUTRAL`] for a missing partner controller.
+pub trait ControllerInput {
+    /// Returns the current state of the controller's joysticks and buttons.
+    fn state(&self) -> Result<ControllerState, ControllerError>;
+}
+
+impl ControllerInput for Controller {
+    fn state(&self) -> Result<ControllerState, ControllerError> {
+        read_state(self.id)
+    }
+}
+
+/// Reads the state of every joystick and button on a controller.
+pub(super) fn read_state(id: ControllerId) -> Result<ControllerState, ControllerError> {
+    let mut state = ControllerState::NEUTRAL;
+
+    for axis in ControllerAxis::ALL {
+        state.set_axis_raw(axis, axis_position(id, axis.into())?);
+    }
+
+    for button in ControllerButton::ALL {
+        state.set_pressed(button, button_pressed(id, button.into())?);
+    }
+
+    Ok(state)
+}
+
+impl ControllerInput for ControllerState {
+    fn state(&self) -> Result<ControllerState, ControllerError> {
+        Ok(*self)
+    }
+}
+
+impl<T: ControllerInput + ?Sized> ControllerInput for &T {
+    fn state(&self) -> Result<ControllerState, ControllerError> {
+        (**self).state()
+    }
+}
@@ -0,0 +1,255 @@
+//! Rate-limited controller screen updates.
+
+use alloc::string::String;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use vexide_core::time::Instant;
+
+use super::{ControllerError, ControllerScreen};
+
+/// A future that completes once a deadline has passed.
+pub(super) struct Delay {
+    deadline: Instant,
+}
+
+impl Delay {
+    /// Creates a future that completes after `duration`.
+    pub(super) fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+        }
+    }
+
+    /// Creates a future that completes at `deadline`.
+    pub(super) const fn until(deadline: Instant) -> Self {
+        Self { deadline }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        // TODO: This should probably be done on a timer in the reactor.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// A queued, rate-limited writer for a controller's screen.
+///
+/// The controller can only receive screen updates every so often over its radio link, and
+/// silently drops any that arrive too quickly. `ScreenWriter` keeps track of what each line
+/// *should* show, and sends at most one line every [`ScreenWriter::MIN_INTERVAL`]. Lines that
+/// haven't changed since they were last sent are skipped, so it's fine to call
+/// [`ScreenWriter::set_line`] every loop with the same text.
+///
+/// Text is sent either by calling [`ScreenWriter::update`] regularly (such as once per loop of
+/// driver control), or by awaiting [`ScreenWriter::flush`].
+///
+/// Rumble patterns are sent over the same link, so they should be queued with
+/// [`ScreenWriter::rumble`] rather than [`Controller::rumble`](super::Controller::rumble) while
+/// a writer is in use. A queued pattern is sent before any lines.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut writer = ScreenWriter::new(&mut controller.screen);
+///
+/// writer.set_line(0, "Auton: Left")?;
+/// writer.set_line(1, &format!("Battery: {}%", battery::capacity()))?;
+/// writer.flush().await?;
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScreenWriter<'a> {
+    screen: &'a mut ControllerScreen,
+
+    /// The text each line should show, padded to [`ControllerScreen::MAX_LINE_LENGTH`].
+    desired: [String; ControllerScreen::MAX_LINES],
+
+    /// The text last sent to each line, or `None` if it is unknown.
+    sent: [Option<String>; ControllerScreen::MAX_LINES],
+
+    /// The line to check first on the next update, so that no line is starved.
+    next_line: usize,
+
+    /// A rumble pattern waiting to be sent.
+    rumble: Option<String>,
+
+    /// When the most recent line or rumble pattern was sent.
+    last_write: Option<Instant>,
+}
+
+impl<'a> ScreenWriter<'a> {
+    /// The minimum time between two screen updates.
+    pub const MIN_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Creates a new writer that takes over a controller's screen for as long as it is
+    /// borrowed.
+    ///
+    /// The screen is cleared on the first update.
+    pub fn new(screen: &'a mut ControllerScreen) -> Self {
+        let mut writer = Self {
+            screen,
+            desired: Default::default(),
+            sent: Default::default(),
+            next_line: 0,
+            rumble: None,
+            last_write: None,
+        };
+        writer.clear();
+
+        writer
+    }
+
+    /// Queues text to be shown on a line of the screen.
+    ///
+    /// Text longer than [`ControllerScreen::MAX_LINE_LENGTH`] characters is truncated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ControllerError::InvalidLine`] if `line` is not less than
+    /// [`ControllerScreen::MAX_LINES`], or [`ControllerError::NonTerminatingNul`] if `text`
+    /// contains a nul byte.
+    pub fn set_line(&mut self, line: u8, text: &str) -> Result<(), ControllerError> {
+        let desired = self
+            .desired
+            .get_mut(line as usize)
+            .ok_or(ControllerError::InvalidLine)?;
+
+        if text.contains('\0') {
+            return Err(ControllerError::NonTerminatingNul);
+        }
+
+        // Pad with spaces so that shorter text fully overwrites what was there before.
+        desired.clear();
+        desired.extend(
+            text.chars()
+                .chain(core::iter::repeat(' '))
+                .take(ControllerScreen::MAX_LINE_LENGTH),
+        );
+
+        Ok(())
+    }
+
+    /// Queues every line of the screen to be cleared.
+    pub fn clear(&mut self) {
+        for line in 0..ControllerScreen::MAX_LINES as u8 {
+            _ = self.set_line(line, "");
+        }
+    }
+
+    /// Queues a rumble pattern to be played by the controller.
+    ///
+    /// The pattern uses the format accepted by
+    /// [`Controller::rumble`](super::Controller::rumble), and replaces any pattern that hasn't
+    /// been sent yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ControllerError::NonTerminatingNul`] if `pattern` contains a nul byte.
+    pub fn rumble(&mut self, pattern: impl AsRef<str>) -> Result<(), ControllerError> {
+        let pattern = pattern.as_ref();
+        if pattern.contains('\0') {
+            return Err(ControllerError::NonTerminatingNul);
+        }
+
+        self.rumble = Some(pattern.into());
+
+        Ok(())
+    }
+
+    /// Returns the text queued for a line, including any trailing padding.
+    pub fn line(&self, line: u8) -> Option<&str> {
+        self.desired.get(line as usize).map(String::as_str)
+    }
+
+    /// Returns `true` if every queued line and rumble pattern has been sent to the controller.
+    pub fn is_flushed(&self) -> bool {
+        self.rumble.is_none()
+            && self
+                .desired
+                .iter()
+                .zip(&self.sent)
+                .all(|(desired, sent)| sent.as_ref() == Some(desired))
+    }
+
+    /// Sends the queued rumble pattern or the next changed line to the controller if enough
+    /// time has passed since the previous update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is disconnected. The pattern or line stays queued and
+    /// will be sent once the controller reconnects.
+    pub fn update(&mut self) -> Result<(), ControllerError> {
+        if self
+            .last_write
+            .is_some_and(|last_write| last_write.elapsed() < Self::MIN_INTERVAL)
+        {
+            return Ok(());
+        }
+
+        if let Some(pattern) = &self.rumble {
+            self.last_write = Some(Instant::now());
+            // Line 3 isn't shown on the screen, and plays rumble patterns instead.
+            self.screen.set_text(pattern, 3, 0)?;
+            self.rumble = None;
+
+            return Ok(());
+        }
+
+        for offset in 0..ControllerScreen::MAX_LINES {
+            let line = (self.next_line + offset) % ControllerScreen::MAX_LINES;
+            if self.sent[line].as_ref() == Some(&self.desired[line]) {
+                continue;
+            }
+
+            self.last_write = Some(Instant::now());
+            self.screen.set_text(&self.desired[line], line as u8, 0)?;
+            self.sent[line] = Some(self.desired[line].clone());
+            self.next_line = (line + 1) % ControllerScreen::MAX_LINES;
+
+            break;
+        }
+
+        Ok(())
+    }
+
+    /// Waits until every queued line and rumble pattern has been sent to the controller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is disconnected.
+    pub async fn flush(&mut self) -> Result<(), ControllerError> {
+        while !self.is_flushed() {
+            if let Some(last_write) = self.last_write {
+                Delay::until(last_write + Self::MIN_INTERVAL).await;
+            }
+
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets what is currently shown on the screen, so that every line is sent again.
+    ///
+    /// This is useful if something else has written to the screen.
+    pub fn invalidate(&mut self) {
+        self.sent = Default::default();
+    }
+
+    /// Returns the underlying controller screen.
+    pub fn into_inner(self) -> &'a mut ControllerScreen {
+        self.screen
+    }
+}
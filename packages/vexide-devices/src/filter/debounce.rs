@@ -0,0 +1,99 @@
+use core::time::Duration;
+
+use vexide_core::time::Instant;
+
+use super::Filter;
+
+/// A filter that ignores changes in a boolean signal until they have persisted for some time.
+///
+/// This is useful for limit switches and bumpers that chatter when pressed, or for
+/// thresholded sensor readings that flicker around their threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct Debouncer {
+    delay: Duration,
+    output: bool,
+    initial: bool,
+    pending_since: Option<Instant>,
+}
+
+impl Debouncer {
+    /// Creates a new debouncer that only changes its output after the input has held a new
+    /// value for `delay`.
+    pub const fn new(delay: Duration, initial: bool) -> Self {
+        Self {
+            delay,
+            output: initial,
+            initial,
+            pending_since: None,
+        }
+    }
+
+    /// Returns the current debounced value.
+    pub const fn value(&self) -> bool {
+        self.output
+    }
+
+    /// Switches the output to `input` if it has been held for at least the delay.
+    fn settle(&mut self, input: bool, held_for: Duration) {
+        if held_for >= self.delay {
+            self.output = input;
+            self.pending_since = None;
+        }
+    }
+}
+
+impl Filter for Debouncer {
+    type Input = bool;
+    type Output = bool;
+
+    fn update(&mut self, input: bool) -> bool {
+        if input == self.output {
+            self.pending_since = None;
+        } else {
+            let now = Instant::now();
+            let since = *self.pending_since.get_or_insert(now);
+
+            self.settle(input, now.duration_since(since));
+        }
+
+        self.output
+    }
+
+    fn reset(&mut self) {
+        self.output = self.initial;
+        self.pending_since = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_switches_after_delay() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(10), false);
+
+        debouncer.settle(true, Duration::from_millis(9));
+        assert!(!debouncer.value());
+
+        debouncer.settle(true, Duration::from_millis(10));
+        assert!(debouncer.value());
+    }
+
+    #[test]
+    fn unchanged_input_keeps_output() {
+        let mut debouncer = Debouncer::new(Duration::from_millis(10), true);
+
+        assert!(debouncer.update(true));
+        assert!(debouncer.update(true));
+    }
+
+    #[test]
+    fn reset_restores_initial_value() {
+        let mut debouncer = Debouncer::new(Duration::ZERO, false);
+
+        debouncer.settle(true, Duration::ZERO);
+        debouncer.reset();
+        assert!(!debouncer.value());
+    }
+}
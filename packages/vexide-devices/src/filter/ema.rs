@@ -0,0 +1,55 @@
+use super::Filter;
+
+/// An exponential moving average (first-order low-pass) filter.
+///
+/// Each output is a weighted average of the newest reading and the previous output:
+/// `output = alpha * input + (1 - alpha) * previous`. Smaller values of `alpha` smooth more
+/// heavily, at the cost of responding more slowly to real changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialMovingAverage {
+    alpha: f64,
+    state: Option<f64>,
+}
+
+impl ExponentialMovingAverage {
+    /// Creates a new filter with a smoothing factor between 0.0 and 1.0.
+    ///
+    /// `alpha` is clamped to that range. An `alpha` of 1.0 disables filtering entirely.
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            state: None,
+        }
+    }
+
+    /// Returns the smoothing factor of the filter.
+    pub const fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the most recent output of the filter, or `None` if it has not received any
+    /// readings.
+    pub const fn value(&self) -> Option<f64> {
+        self.state
+    }
+}
+
+impl Filter for ExponentialMovingAverage {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let output = match self.state {
+            // The first reading is used as-is, rather than being averaged with zero.
+            None => input,
+            Some(previous) => self.alpha * input + (1.0 - self.alpha) * previous,
+        };
+
+        self.state = Some(output);
+        output
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
@@ -0,0 +1,87 @@
+use super::Filter;
+
+/// A one-dimensional Kalman filter.
+///
+/// This models the measured value as roughly constant between updates, with some random drift
+/// (the process noise). Each reading is weighted by how much the filter trusts it compared to its
+/// current estimate, so the filter smooths heavily when its estimate is confident and responds
+/// quickly when it isn't.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KalmanFilter {
+    process_noise: f64,
+    measurement_noise: f64,
+    initial_variance: f64,
+    state: Option<(f64, f64)>,
+}
+
+impl KalmanFilter {
+    /// Creates a new Kalman filter.
+    ///
+    /// - `process_noise` is the variance of how much the true value is expected to change
+    ///   between readings.
+    /// - `measurement_noise` is the variance of the sensor's readings.
+    ///
+    /// If both the estimate and the readings are certain (their variances are zero), each
+    /// reading replaces the estimate.
+    pub const fn new(process_noise: f64, measurement_noise: f64) -> Self {
+        Self::with_initial_variance(process_noise, measurement_noise, measurement_noise)
+    }
+
+    /// Creates a new Kalman filter with a specific variance for the first estimate.
+    ///
+    /// By default, the first reading is trusted as much as any other reading.
+    pub const fn with_initial_variance(
+        process_noise: f64,
+        measurement_noise: f64,
+        initial_variance: f64,
+    ) -> Self {
+        Self {
+            process_noise,
+            measurement_noise,
+            initial_variance,
+            state: None,
+        }
+    }
+
+    /// Returns the current estimate, or `None` if the filter has not received any readings.
+    pub fn estimate(&self) -> Option<f64> {
+        self.state.map(|(estimate, _)| estimate)
+    }
+
+    /// Returns the variance of the current estimate, or `None` if the filter has not received
+    /// any readings.
+    pub fn variance(&self) -> Option<f64> {
+        self.state.map(|(_, variance)| variance)
+    }
+}
+
+impl Filter for KalmanFilter {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let (estimate, variance) = match self.state {
+            None => (input, self.initial_variance),
+            Some((estimate, variance)) => {
+                // Predict: the value may have drifted since the last reading.
+                let variance = variance + self.process_noise;
+
+                // Update: blend the reading into the estimate based on relative confidence.
+                // With no uncertainty on either side the gain is 0/0, so trust the reading.
+                let total = variance + self.measurement_noise;
+                let gain = if total == 0.0 { 1.0 } else { variance / total };
+                (
+                    estimate + gain * (input - estimate),
+                    (1.0 - gain) * variance,
+                )
+            }
+        };
+
+        self.state = Some((estimate, variance));
+        estimate
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
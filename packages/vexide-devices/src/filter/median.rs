@@ -0,0 +1,67 @@
+use super::Filter;
+
+/// A filter that returns the median of the last `N` readings.
+///
+/// Median filters are good at rejecting occasional outliers (such as a distance sensor briefly
+/// seeing past an object) without smoothing out real changes the way an average does. Odd
+/// window sizes are recommended; with an even number of readings, the mean of the two middle
+/// readings is returned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MedianFilter<const N: usize> {
+    buffer: [f64; N],
+    len: usize,
+    index: usize,
+}
+
+impl<const N: usize> MedianFilter<N> {
+    /// Creates a new median filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub const fn new() -> Self {
+        assert!(
+            N > 0,
+            "MedianFilter window must contain at least one reading."
+        );
+
+        Self {
+            buffer: [0.0; N],
+            len: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<const N: usize> Default for MedianFilter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MedianFilter<N> {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.buffer[self.index] = input;
+        self.index = (self.index + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        let mut sorted = self.buffer;
+        let sorted = &mut sorted[..self.len];
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let middle = self.len / 2;
+        if self.len % 2 == 0 {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        } else {
+            sorted[middle]
+        }
+    }
+
+    fn reset(&mut self) {
+        self.len = 0;
+        self.index = 0;
+    }
+}
@@ -0,0 +1,283 @@
+//! Signal filters for smoothing noisy sensor readings.
+//!
+//! All filters in this module implement the [`Filter`] trait, which takes a single reading
+//! at a time and returns the filtered output. Filters never allocate, making them safe to use in
+//! tight control loops.
+//!
+//! - [`ExponentialMovingAverage`] is a cheap low-pass filter for general smoothing.
+//! - [`MovingAverage`] averages the last `N` readings.
+//! - [`MedianFilter`] takes the median of the last `N` readings, rejecting occasional outliers.
+//! - [`SlewRateLimiter`] limits how quickly a value may change over time.
+//! - [`Debouncer`] ignores brief changes in a boolean signal.
+//! - [`KalmanFilter`] is a scalar Kalman filter for tracking a slowly changing value.
+//!
+//! # Sensor adapters
+//!
+//! Most device readings return a [`Result`]. [`Filter::update_reading`] applies a filter to
+//! successful readings while passing errors through unchanged, and [`Filtered`] bundles a
+//! filter together with the function used to read from a sensor.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vexide_devices::filter::{ExponentialMovingAverage, Filtered};
+//!
+//! let rotation = RotationSensor::new(peripherals.port_1, Direction::Forward);
+//! let mut velocity = Filtered::new(|| rotation.velocity(), ExponentialMovingAverage::new(0.2));
+//!
+//! loop {
+//!     println!("Smoothed velocity: {}", velocity.read()?);
+//!     sleep(Duration::from_millis(10)).await;
+//! }
+//! ```
+
+mod debounce;
+mod ema;
+mod kalman;
+mod median;
+mod moving_average;
+mod slew;
+
+pub use debounce::Debouncer;
+pub use ema::ExponentialMovingAverage;
+pub use kalman::KalmanFilter;
+pub use median::MedianFilter;
+pub use moving_average::MovingAverage;
+pub use slew::SlewRateLimiter;
+
+/// A filter that transforms a stream of readings one at a time.
+pub trait Filter {
+    /// The type of reading accepted by the filter.
+    type Input;
+
+    /// The type of value produced by the filter.
+    type Output;
+
+    /// Feeds a new reading into the filter, returning the filtered output.
+    fn update(&mut self, input: Self::Input) -> Self::Output;
+
+    /// Clears the filter's history, returning it to the state it was in when created.
+    fn reset(&mut self);
+
+    /// Feeds a fallible sensor reading into the filter.
+    ///
+    /// Errors are returned unchanged and are not fed into the filter, so a disconnected sensor
+    /// does not corrupt the filter's history.
+    fn update_reading<E>(&mut self, reading: Result<Self::Input, E>) -> Result<Self::Output, E> {
+        reading.map(|input| self.update(input))
+    }
+}
+
+/// A sensor reading paired with a filter.
+///
+/// This calls a reading function (usually a closure borrowing a device) and feeds the result
+/// through a filter every time [`Filtered::read`] is called.
+#[derive(Debug)]
+pub struct Filtered<S, F> {
+    source: S,
+    filter: F,
+}
+
+impl<S, F, E> Filtered<S, F>
+where
+    S: FnMut() -> Result<F::Input, E>,
+    F: Filter,
+{
+    /// Creates a new filtered reading from a reading function and a filter.
+    pub const fn new(source: S, filter: F) -> Self {
+        Self { source, filter }
+    }
+
+    /// Takes a new reading from the source and returns the filtered value.
+    ///
+    /// # Errors
+    ///
+    /// Errors returned by the source are passed through without updating the filter.
+    pub fn read(&mut self) -> Result<F::Output, E> {
+        self.filter.update_reading((self.source)())
+    }
+
+    /// Returns a reference to the underlying filter.
+    pub const fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns a mutable reference to the underlying filter.
+    pub fn filter_mut(&mut self) -> &mut F {
+        &mut self.filter
+    }
+
+    /// Consumes the adapter, returning the reading function and the filter.
+    pub fn into_parts(self) -> (S, F) {
+        (self.source, self.filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// Generates a deterministic sequence of readings in `-1000.0..1000.0`.
+    fn readings(seed: u64, count: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..count)
+            .map(|_| {
+                // xorshift64
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % 2_000_000) as f64 / 1000.0 - 1000.0
+            })
+            .collect()
+    }
+
+    fn bounds(readings: &[f64]) -> (f64, f64) {
+        readings
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+                (min.min(x), max.max(x))
+            })
+    }
+
+    #[test]
+    fn averaging_filters_stay_within_input_range() {
+        for seed in 1..50 {
+            let readings = readings(seed, 64);
+            let mut ema = ExponentialMovingAverage::new(0.3);
+            let mut average = MovingAverage::<5>::new();
+            let mut median = MedianFilter::<5>::new();
+            let mut kalman = KalmanFilter::new(0.1, 4.0);
+
+            for (index, &reading) in readings.iter().enumerate() {
+                let (min, max) = bounds(&readings[..=index]);
+
+                for output in [
+                    ema.update(reading),
+                    average.update(reading),
+                    median.update(reading),
+                    kalman.update(reading),
+                ] {
+                    assert!(
+                        (min - 1e-9..=max + 1e-9).contains(&output),
+                        "{output} outside {min}..={max}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn moving_average_matches_window_mean() {
+        for seed in 1..50 {
+            let readings = readings(seed, 32);
+            let mut filter = MovingAverage::<4>::new();
+
+            for (index, &reading) in readings.iter().enumerate() {
+                let window = &readings[index.saturating_sub(3)..=index];
+                let mean = window.iter().sum::<f64>() / window.len() as f64;
+
+                assert!((filter.update(reading) - mean).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn median_returns_a_window_reading() {
+        for seed in 1..50 {
+            let readings = readings(seed, 32);
+            let mut filter = MedianFilter::<3>::new();
+
+            for (index, &reading) in readings.iter().enumerate() {
+                let output = filter.update(reading);
+
+                if index >= 2 {
+                    assert!(readings[index - 2..=index].contains(&output));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn median_rejects_single_outlier() {
+        let mut filter = MedianFilter::<3>::new();
+        filter.update(1.0);
+        filter.update(1.0);
+
+        assert_eq!(filter.update(500.0), 1.0);
+    }
+
+    #[test]
+    fn ema_with_alpha_one_passes_through() {
+        let mut filter = ExponentialMovingAverage::new(1.0);
+
+        for reading in readings(7, 16) {
+            assert_eq!(filter.update(reading), reading);
+        }
+    }
+
+    #[test]
+    fn kalman_variance_stays_positive_and_bounded() {
+        let mut filter = KalmanFilter::new(0.1, 4.0);
+
+        for reading in readings(3, 128) {
+            filter.update(reading);
+            let variance = filter.variance().unwrap();
+            assert!(variance > 0.0 && variance <= 4.0);
+        }
+    }
+
+    #[test]
+    fn kalman_without_noise_follows_readings() {
+        let mut filter = KalmanFilter::new(0.0, 0.0);
+
+        for reading in readings(5, 16) {
+            assert!((filter.update(reading) - reading).abs() < 1e-9);
+            assert_eq!(filter.variance(), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn reset_forgets_history() {
+        let mut ema = ExponentialMovingAverage::new(0.5);
+        let mut average = MovingAverage::<3>::new();
+        let mut kalman = KalmanFilter::new(0.1, 1.0);
+
+        for reading in readings(11, 8) {
+            ema.update(reading);
+            average.update(reading);
+            kalman.update(reading);
+        }
+        ema.reset();
+        average.reset();
+        kalman.reset();
+
+        assert_eq!(ema.update(42.0), 42.0);
+        assert_eq!(average.update(42.0), 42.0);
+        assert_eq!(kalman.update(42.0), 42.0);
+    }
+
+    #[test]
+    fn update_reading_passes_errors_through() {
+        let mut filter = ExponentialMovingAverage::new(0.5);
+
+        assert_eq!(filter.update_reading(Ok::<_, ()>(2.0)), Ok(2.0));
+        assert_eq!(filter.update_reading(Err::<f64, _>(())), Err(()));
+        assert_eq!(filter.value(), Some(2.0));
+    }
+
+    #[test]
+    fn filtered_reads_through_filter() {
+        let mut values = [1.0, 3.0].into_iter();
+        let mut filtered = Filtered::new(
+            || values.next().ok_or(()),
+            ExponentialMovingAverage::new(0.5),
+        );
+
+        assert_eq!(filtered.read(), Ok(1.0));
+        assert_eq!(filtered.read(), Ok(2.0));
+        assert_eq!(filtered.read(), Err(()));
+        assert_eq!(filtered.filter().value(), Some(2.0));
+    }
+}
@@ -0,0 +1,56 @@
+use super::Filter;
+
+/// A filter that averages the last `N` readings.
+///
+/// Until `N` readings have been received, the average of every reading so far is returned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingAverage<const N: usize> {
+    buffer: [f64; N],
+    len: usize,
+    index: usize,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Creates a new moving average filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub const fn new() -> Self {
+        assert!(
+            N > 0,
+            "MovingAverage window must contain at least one reading."
+        );
+
+        Self {
+            buffer: [0.0; N],
+            len: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MovingAverage<N> {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.buffer[self.index] = input;
+        self.index = (self.index + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        // Summing the whole window each time avoids the floating point drift of a running sum.
+        self.buffer[..self.len].iter().sum::<f64>() / self.len as f64
+    }
+
+    fn reset(&mut self) {
+        self.len = 0;
+        self.index = 0;
+    }
+}
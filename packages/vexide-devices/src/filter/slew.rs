@@ -0,0 +1,122 @@
+use vexide_core::{float::Float, time::Instant};
+
+use super::Filter;
+
+/// A filter that limits how quickly a value can change.
+///
+/// This is commonly used on drivetrain outputs to prevent tipping or wheel slip from sudden
+/// changes in speed. Rates are measured in units per second using the time between calls to
+/// [`Filter::update`].
+#[derive(Debug, Clone, Copy)]
+pub struct SlewRateLimiter {
+    rising_rate: f64,
+    falling_rate: f64,
+    state: Option<(f64, Instant)>,
+}
+
+impl SlewRateLimiter {
+    /// Creates a new slew rate limiter that allows the value to change by at most `rate` units
+    /// per second in either direction.
+    pub fn new(rate: f64) -> Self {
+        Self::with_rates(rate, rate)
+    }
+
+    /// Creates a new slew rate limiter with separate limits for increasing and decreasing values,
+    /// both in units per second.
+    pub fn with_rates(rising_rate: f64, falling_rate: f64) -> Self {
+        Self {
+            rising_rate: rising_rate.abs(),
+            falling_rate: falling_rate.abs(),
+            state: None,
+        }
+    }
+
+    /// Returns the most recent output of the limiter, or `None` if it has not received any
+    /// readings.
+    pub fn value(&self) -> Option<f64> {
+        self.state.map(|(value, _)| value)
+    }
+
+    /// Limits how far `input` may move from `previous` over `elapsed` seconds.
+    fn limit(&self, previous: f64, input: f64, elapsed: f64) -> f64 {
+        // `clamp` panics if either bound is NaN, which happens with an infinite rate and no
+        // elapsed time, or after a NaN reading. `min` and `max` ignore a NaN bound instead.
+        input
+            .min(previous + self.rising_rate * elapsed)
+            .max(previous - self.falling_rate * elapsed)
+    }
+}
+
+impl Filter for SlewRateLimiter {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        let now = Instant::now();
+
+        let output = match self.state {
+            None => input,
+            Some((previous, timestamp)) => {
+                self.limit(previous, input, now.duration_since(timestamp).as_secs_f64())
+            }
+        };
+
+        self.state = Some((output, now));
+        output
+    }
+
+    fn reset(&mut self) {
+        self.state = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn limits_rising_and_falling_separately() {
+        let limiter = SlewRateLimiter::with_rates(2.0, 1.0);
+
+        assert_eq!(limiter.limit(0.0, 10.0, 0.5), 1.0);
+        assert_eq!(limiter.limit(0.0, -10.0, 0.5), -0.5);
+        assert_eq!(limiter.limit(0.0, 0.25, 0.5), 0.25);
+    }
+
+    #[test]
+    fn never_moves_faster_than_rate() {
+        let limiter = SlewRateLimiter::with_rates(3.0, 5.0);
+
+        for step in 0..200 {
+            let previous = (step as f64 * 0.37).sin() * 10.0;
+            let input = (step as f64 * 1.91).cos() * 100.0;
+            let elapsed = (step % 7) as f64 * 0.01;
+
+            let output = limiter.limit(previous, input, elapsed);
+            assert!(output - previous <= 3.0 * elapsed + 1e-12);
+            assert!(previous - output <= 5.0 * elapsed + 1e-12);
+            assert!((output - input).abs() <= (previous - input).abs() + 1e-12);
+        }
+    }
+
+    #[test]
+    fn infinite_rate_without_elapsed_time_passes_input_through() {
+        let limiter = SlewRateLimiter::new(f64::INFINITY);
+
+        assert_eq!(limiter.limit(1.0, 5.0, 0.0), 5.0);
+    }
+
+    #[test]
+    fn recovers_from_nan_previous_output() {
+        let limiter = SlewRateLimiter::new(1.0);
+
+        assert_eq!(limiter.limit(f64::NAN, 3.0, 0.01), 3.0);
+    }
+
+    #[test]
+    fn nan_input_is_bounded() {
+        let limiter = SlewRateLimiter::new(1.0);
+
+        assert_eq!(limiter.limit(0.0, f64::NAN, 1.0), 1.0);
+    }
+}
@@ -9,6 +9,9 @@ pub use mint::{EulerAngles, Quaternion, Vector3};
 
 #[cfg(feature = "nalgebra")]
 mod nalgebra;
+mod pose;
+
+pub use pose::{Pose2d, Rotation2d, Transform2d, Twist2d};
 
 /// A point in 2D cartesian space.
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
@@ -1,4 +1,4 @@
-use super::Point2;
+use super::{Point2, Pose2d, Rotation2d, Transform2d};
 
 impl<T: nalgebra::Scalar> From<nalgebra::Point2<T>> for Point2<T> {
     fn from(na_point: nalgebra::Point2<T>) -> Self {
@@ -16,3 +16,58 @@ impl<T: nalgebra::Scalar> From<Point2<T>> for nalgebra::Point2<T> {
         Self::new(point.x, point.y)
     }
 }
+
+impl From<nalgebra::UnitComplex<f64>> for Rotation2d {
+    fn from(rotation: nalgebra::UnitComplex<f64>) -> Self {
+        let complex = rotation.into_inner();
+        Self::from_components(complex.re, complex.im)
+    }
+}
+
+impl From<Rotation2d> for nalgebra::UnitComplex<f64> {
+    fn from(rotation: Rotation2d) -> Self {
+        Self::new_unchecked(nalgebra::Complex::new(rotation.cos(), rotation.sin()))
+    }
+}
+
+impl From<nalgebra::Isometry2<f64>> for Transform2d {
+    fn from(isometry: nalgebra::Isometry2<f64>) -> Self {
+        Self {
+            translation: Point2::new(isometry.translation.x, isometry.translation.y),
+            rotation: isometry.rotation.into(),
+        }
+    }
+}
+
+impl From<Transform2d> for nalgebra::Isometry2<f64> {
+    fn from(transform: Transform2d) -> Self {
+        Self {
+            translation: nalgebra::Translation2::new(
+                transform.translation.x,
+                transform.translation.y,
+            ),
+            rotation: transform.rotation.into(),
+        }
+    }
+}
+
+impl From<nalgebra::Isometry2<f64>> for Pose2d {
+    fn from(isometry: nalgebra::Isometry2<f64>) -> Self {
+        let transform = Transform2d::from(isometry);
+
+        Self {
+            position: transform.translation,
+            rotation: transform.rotation,
+        }
+    }
+}
+
+impl From<Pose2d> for nalgebra::Isometry2<f64> {
+    fn from(pose: Pose2d) -> Self {
+        Transform2d {
+            translation: pose.position,
+            rotation: pose.rotation,
+        }
+        .into()
+    }
+}
@@ -0,0 +1,523 @@
+use core::{
+    f64::consts::TAU,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use vexide_core::float::Float;
+
+use super::Point2;
+
+/// Angles smaller than this are treated as zero when integrating twists, to avoid dividing by
+/// (nearly) zero.
+const EPSILON: f64 = 1e-9;
+
+/// A rotation in 2D space.
+///
+/// Rotations are stored as a point on the unit circle, so composing and inverting them never
+/// accumulates wraparound error. Angles follow the standard mathematical convention, increasing
+/// counterclockwise from the positive x-axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation2d {
+    cos: f64,
+    sin: f64,
+}
+
+impl Rotation2d {
+    /// A rotation of zero radians.
+    pub const IDENTITY: Self = Self { cos: 1.0, sin: 0.0 };
+
+    /// Creates a new rotation from an angle in radians.
+    pub fn from_radians(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self { cos, sin }
+    }
+
+    /// Creates a new rotation from an angle in degrees.
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self::from_radians(degrees.to_radians())
+    }
+
+    /// Creates a new rotation pointing in the direction of the vector `(x, y)`.
+    ///
+    /// If both components are zero, this returns [`Rotation2d::IDENTITY`].
+    pub fn from_components(x: f64, y: f64) -> Self {
+        let magnitude = x.hypot(y);
+
+        if magnitude > EPSILON {
+            Self {
+                cos: x / magnitude,
+                sin: y / magnitude,
+            }
+        } else {
+            Self::IDENTITY
+        }
+    }
+
+    /// Returns the angle of this rotation in radians, in the range `(-π, π]`.
+    pub fn radians(&self) -> f64 {
+        self.sin.atan2(self.cos)
+    }
+
+    /// Returns the angle of this rotation in degrees, in the range `(-180, 180]`.
+    pub fn degrees(&self) -> f64 {
+        self.radians().to_degrees()
+    }
+
+    /// Returns the cosine of this rotation's angle.
+    pub const fn cos(&self) -> f64 {
+        self.cos
+    }
+
+    /// Returns the sine of this rotation's angle.
+    pub const fn sin(&self) -> f64 {
+        self.sin
+    }
+
+    /// Returns the tangent of this rotation's angle.
+    pub fn tan(&self) -> f64 {
+        self.sin / self.cos
+    }
+
+    /// Returns the rotation that undoes this one.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        Self {
+            cos: self.cos,
+            sin: -self.sin,
+        }
+    }
+
+    /// Rotates a point about the origin by this rotation.
+    pub fn rotate(&self, point: Point2<f64>) -> Point2<f64> {
+        Point2 {
+            x: point.x * self.cos - point.y * self.sin,
+            y: point.x * self.sin + point.y * self.cos,
+        }
+    }
+
+    /// Linearly interpolates between this rotation and `end` along the shortest arc.
+    ///
+    /// `t` is clamped to the range `[0, 1]`.
+    #[must_use]
+    pub fn interpolate(&self, end: Self, t: f64) -> Self {
+        *self + (end - *self) * t.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Rotation2d {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Display for Rotation2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°", self.degrees())
+    }
+}
+
+impl Add for Rotation2d {
+    type Output = Self;
+
+    /// Rotation composition
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            cos: self.cos * rhs.cos - self.sin * rhs.sin,
+            sin: self.cos * rhs.sin + self.sin * rhs.cos,
+        }
+    }
+}
+
+impl Sub for Rotation2d {
+    type Output = Self;
+
+    /// Rotation difference
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            cos: self.cos * rhs.cos + self.sin * rhs.sin,
+            sin: self.sin * rhs.cos - self.cos * rhs.sin,
+        }
+    }
+}
+
+impl Neg for Rotation2d {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.inverse()
+    }
+}
+
+impl Mul<f64> for Rotation2d {
+    type Output = Self;
+
+    /// Angle scaling
+    fn mul(self, rhs: f64) -> Self {
+        Self::from_radians(self.radians() * rhs)
+    }
+}
+
+impl AddAssign for Rotation2d {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Rotation2d {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// A rigid transformation (translation followed by rotation) in 2D space.
+///
+/// Transforms describe the offset between two [`Pose2d`]s, expressed in the frame of the first
+/// pose. They can be applied to a pose using the `+` operator, and composed using `*`.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Transform2d {
+    /// The translational part of the transform.
+    pub translation: Point2<f64>,
+
+    /// The rotational part of the transform.
+    pub rotation: Rotation2d,
+}
+
+impl Transform2d {
+    /// A transform that does nothing.
+    pub const IDENTITY: Self = Self {
+        translation: Point2 { x: 0.0, y: 0.0 },
+        rotation: Rotation2d::IDENTITY,
+    };
+
+    /// Creates a new transform.
+    pub fn new(translation: impl Into<Point2<f64>>, rotation: Rotation2d) -> Self {
+        Self {
+            translation: translation.into(),
+            rotation,
+        }
+    }
+
+    /// Returns the transform that undoes this one.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverse();
+
+        Self {
+            translation: rotation.rotate(-self.translation),
+            rotation,
+        }
+    }
+}
+
+impl Mul for Transform2d {
+    type Output = Self;
+
+    /// Transform composition, applying `self` and then `rhs`.
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            translation: self.translation + self.rotation.rotate(rhs.translation),
+            rotation: self.rotation + rhs.rotation,
+        }
+    }
+}
+
+/// A position and heading in 2D space.
+///
+/// # Coordinate frames
+///
+/// Poses use the same field coordinate frame as [`GpsSensor::pose`](crate::smart::GpsSensor::pose):
+/// the origin is at the center of the field, positions are in meters, and headings increase
+/// **counterclockwise** from the positive x-axis. Note that this is the opposite direction of
+/// [`InertialSensor`](crate::smart::InertialSensor) headings.
+///
+/// Poses can also be used in a robot-relative frame by using [`Pose2d::relative_to`] and the
+/// [`Transform2d`] operations.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Pose2d {
+    /// The position of the pose.
+    pub position: Point2<f64>,
+
+    /// The heading of the pose.
+    pub rotation: Rotation2d,
+}
+
+impl Pose2d {
+    /// A pose at the origin, facing along the positive x-axis.
+    pub const ORIGIN: Self = Self {
+        position: Point2 { x: 0.0, y: 0.0 },
+        rotation: Rotation2d::IDENTITY,
+    };
+
+    /// Creates a new pose.
+    pub fn new(position: impl Into<Point2<f64>>, rotation: Rotation2d) -> Self {
+        Self {
+            position: position.into(),
+            rotation,
+        }
+    }
+
+    /// Returns this pose expressed in the coordinate frame of `origin`.
+    ///
+    /// The returned transform maps `origin` onto `self`, so `origin + self.relative_to(origin)`
+    /// is equal to `self`.
+    pub fn relative_to(&self, origin: Self) -> Transform2d {
+        Transform2d {
+            translation: origin
+                .rotation
+                .inverse()
+                .rotate(self.position - origin.position),
+            rotation: self.rotation - origin.rotation,
+        }
+    }
+
+    /// Integrates a twist starting at this pose, returning the pose that the robot ends up at.
+    ///
+    /// This assumes the robot moves along a constant-curvature arc, which is much more accurate
+    /// than applying the twist's velocity in a straight line for odometry.
+    #[must_use]
+    pub fn exp(&self, twist: Twist2d) -> Self {
+        let (sin, cos) = twist.dtheta.sin_cos();
+
+        let (s, c) = if twist.dtheta.abs() < EPSILON {
+            (1.0 - twist.dtheta * twist.dtheta / 6.0, 0.5 * twist.dtheta)
+        } else {
+            (sin / twist.dtheta, (1.0 - cos) / twist.dtheta)
+        };
+
+        *self
+            + Transform2d {
+                translation: Point2 {
+                    x: twist.dx * s - twist.dy * c,
+                    y: twist.dx * c + twist.dy * s,
+                },
+                rotation: Rotation2d { cos, sin },
+            }
+    }
+
+    /// Returns the twist that moves the robot from this pose to `end` along a
+    /// constant-curvature arc.
+    ///
+    /// This is the inverse of [`Pose2d::exp`].
+    pub fn log(&self, end: Self) -> Twist2d {
+        let transform = end.relative_to(*self);
+        let dtheta = transform.rotation.radians();
+        let half_dtheta = dtheta / 2.0;
+
+        let cos_minus_one = transform.rotation.cos - 1.0;
+        let half_theta_by_tan_of_half_dtheta = if cos_minus_one.abs() < EPSILON {
+            1.0 - dtheta * dtheta / 12.0
+        } else {
+            -(half_dtheta * transform.rotation.sin) / cos_minus_one
+        };
+
+        // Multiply the translation by the complex number (a - bi).
+        let (a, b) = (half_theta_by_tan_of_half_dtheta, -half_dtheta);
+        let translation = transform.translation;
+
+        Twist2d {
+            dx: translation.x * a - translation.y * b,
+            dy: translation.x * b + translation.y * a,
+            dtheta,
+        }
+    }
+
+    /// Interpolates between this pose and `end` along a constant-curvature arc.
+    ///
+    /// `t` is clamped to the range `[0, 1]`.
+    #[must_use]
+    pub fn interpolate(&self, end: Self, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+
+        if t == 0.0 {
+            *self
+        } else if t == 1.0 {
+            end
+        } else {
+            self.exp(self.log(end) * t)
+        }
+    }
+
+    /// Returns the straight-line distance between the positions of two poses.
+    pub fn distance(&self, other: Self) -> f64 {
+        let delta = other.position - self.position;
+        delta.x.hypot(delta.y)
+    }
+}
+
+impl Display for Pose2d {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}, {})",
+            self.position.x, self.position.y, self.rotation
+        )
+    }
+}
+
+impl Add<Transform2d> for Pose2d {
+    type Output = Self;
+
+    /// Applies a transform relative to this pose.
+    fn add(self, rhs: Transform2d) -> Self {
+        Self {
+            position: self.position + self.rotation.rotate(rhs.translation),
+            rotation: self.rotation + rhs.rotation,
+        }
+    }
+}
+
+impl AddAssign<Transform2d> for Pose2d {
+    fn add_assign(&mut self, rhs: Transform2d) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pose2d {
+    type Output = Transform2d;
+
+    /// Returns the transform from `rhs` to `self`. See [`Pose2d::relative_to`].
+    fn sub(self, rhs: Self) -> Transform2d {
+        self.relative_to(rhs)
+    }
+}
+
+/// Converts the `(position, heading)` tuple returned by
+/// [`GpsSensor::pose`](crate::smart::GpsSensor::pose), where the heading is in degrees.
+impl From<(Point2<f64>, f64)> for Pose2d {
+    fn from((position, heading): (Point2<f64>, f64)) -> Self {
+        Self {
+            position,
+            rotation: Rotation2d::from_degrees(heading),
+        }
+    }
+}
+
+/// Converts into a `(position, heading)` tuple in the same format as
+/// [`GpsSensor::pose`](crate::smart::GpsSensor::pose), with the heading in degrees in the
+/// range `[0, 360)`.
+impl From<Pose2d> for (Point2<f64>, f64) {
+    fn from(pose: Pose2d) -> Self {
+        (
+            pose.position,
+            pose.rotation.radians().rem_euclid(TAU).to_degrees(),
+        )
+    }
+}
+
+/// A change in pose along a constant-curvature arc, expressed in the robot's frame.
+///
+/// Twists are most commonly produced by odometry (the distance each side of a drivetrain
+/// travelled in one update) and integrated using [`Pose2d::exp`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Twist2d {
+    /// The distance travelled forwards.
+    pub dx: f64,
+
+    /// The distance travelled to the left.
+    pub dy: f64,
+
+    /// The change in heading in radians, counterclockwise.
+    pub dtheta: f64,
+}
+
+impl Twist2d {
+    /// Creates a new twist.
+    pub const fn new(dx: f64, dy: f64, dtheta: f64) -> Self {
+        Self { dx, dy, dtheta }
+    }
+}
+
+impl Mul<f64> for Twist2d {
+    type Output = Self;
+
+    /// Scalar multiplication
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            dx: self.dx * rhs,
+            dy: self.dy * rhs,
+            dtheta: self.dtheta * rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn assert_twist_eq(actual: Twist2d, expected: Twist2d) {
+        assert!(
+            (actual.dx - expected.dx).abs() < TOLERANCE
+                && (actual.dy - expected.dy).abs() < TOLERANCE
+                && (actual.dtheta - expected.dtheta).abs() < TOLERANCE,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_pose_eq(actual: Pose2d, expected: Pose2d) {
+        assert!(
+            actual.distance(expected) < TOLERANCE
+                && (actual.rotation - expected.rotation).radians().abs() < TOLERANCE,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn log_inverts_exp() {
+        let start = Pose2d::new((1.5, -0.5), Rotation2d::from_degrees(30.0));
+
+        for twist in [
+            Twist2d::new(1.0, 0.0, 0.0),
+            Twist2d::new(0.5, -0.25, 1e-12),
+            Twist2d::new(-2.0, 0.0, 1e-6),
+            Twist2d::new(1.0, 0.5, FRAC_PI_2),
+            Twist2d::new(0.3, 0.0, -2.5),
+            Twist2d::new(0.0, 0.0, 3.0),
+        ] {
+            assert_twist_eq(start.log(start.exp(twist)), twist);
+        }
+    }
+
+    #[test]
+    fn zero_angle_twist_moves_in_a_straight_line() {
+        let start = Pose2d::new((1.0, 2.0), Rotation2d::from_degrees(90.0));
+        let twist = Twist2d::new(3.0, 1.0, 0.0);
+
+        let end = start.exp(twist);
+
+        // Forwards is +y and left is -x when facing 90 degrees.
+        assert_pose_eq(end, Pose2d::new((0.0, 5.0), start.rotation));
+        assert_twist_eq(start.log(end), twist);
+    }
+
+    #[test]
+    fn quarter_turn_follows_an_arc() {
+        let end = Pose2d::ORIGIN.exp(Twist2d::new(FRAC_PI_2, 0.0, FRAC_PI_2));
+
+        assert_pose_eq(
+            end,
+            Pose2d::new((1.0, 1.0), Rotation2d::from_radians(FRAC_PI_2)),
+        );
+    }
+
+    #[test]
+    fn composing_with_inverse_is_identity() {
+        let pose = Pose2d::new((-3.0, 4.0), Rotation2d::from_degrees(-120.0));
+        let transform = Transform2d::new((2.0, -1.0), Rotation2d::from_radians(PI / 3.0));
+
+        assert_pose_eq(pose + transform + transform.inverse(), pose);
+
+        let identity = transform * transform.inverse();
+        assert_pose_eq(Pose2d::ORIGIN + identity, Pose2d::ORIGIN);
+
+        let identity = pose.relative_to(pose);
+        assert_pose_eq(Pose2d::ORIGIN + identity, Pose2d::ORIGIN);
+
+        let origin = Pose2d::new((0.5, 0.5), Rotation2d::from_degrees(45.0));
+        assert_pose_eq(origin + pose.relative_to(origin), pose);
+    }
+}
@@ -13,6 +13,19 @@
 //! - [`battery`] provides functions for getting information about the currently connected
 //!   battery.
 //! - [`controller`] provides types for interacting with the V5 controller.
+#![cfg_attr(
+    feature = "units",
+    doc = "- [`units`] provides strongly-typed physical quantities."
+)]
+#![cfg_attr(
+    not(feature = "units"),
+    doc = "- `units` (with the `units` feature) provides strongly-typed physical quantities."
+)]
+//! - [`filter`] provides signal filters for smoothing noisy sensor readings.
+//! - [`localization`] provides pose estimators that fuse odometry with sensor measurements.
+//! - [`path`] provides parsers for importing waypoint paths from CSV and JSON files.
+//! - [`wiring`] provides port maps for checking robot wiring at startup.
+//! - [`sysid`] provides feedforward characterization for motors and drivetrains.
 
 #![no_std]
 
@@ -24,11 +37,18 @@ pub mod smart;
 pub mod battery;
 pub mod color;
 pub mod controller;
+pub mod filter;
 pub mod geometry;
+pub mod localization;
+pub mod path;
 pub mod peripherals;
 pub mod position;
 pub mod screen;
+pub mod sysid;
+#[cfg(feature = "units")]
+pub mod units;
 pub mod usd;
+pub mod wiring;
 
 use snafu::Snafu;
 
@@ -0,0 +1,420 @@
+use vexide_core::float::Float;
+
+use crate::{
+    geometry::{Point2, Pose2d, Rotation2d, Twist2d},
+    smart::GpsSensor,
+    PortError,
+};
+
+/// A 3x3 covariance matrix over the `(x, y, heading)` state of a pose.
+///
+/// Position variances are in square meters and heading variance is in square radians.
+pub type Covariance = [[f64; 3]; 3];
+
+const IDENTITY: Covariance = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+fn multiply(a: &Covariance, b: &Covariance) -> Covariance {
+    let mut result = [[0.0; 3]; 3];
+
+    for (row, result_row) in result.iter_mut().enumerate() {
+        for (column, value) in result_row.iter_mut().enumerate() {
+            *value = (0..3).map(|i| a[row][i] * b[i][column]).sum();
+        }
+    }
+
+    result
+}
+
+fn transpose(matrix: &Covariance) -> Covariance {
+    let mut result = [[0.0; 3]; 3];
+
+    for (row, result_row) in result.iter_mut().enumerate() {
+        for (column, value) in result_row.iter_mut().enumerate() {
+            *value = matrix[column][row];
+        }
+    }
+
+    result
+}
+
+fn add(a: &Covariance, b: &Covariance) -> Covariance {
+    let mut result = *a;
+
+    for (result_row, b_row) in result.iter_mut().zip(b) {
+        for (value, b_value) in result_row.iter_mut().zip(b_row) {
+            *value += b_value;
+        }
+    }
+
+    result
+}
+
+const fn diagonal(values: [f64; 3]) -> Covariance {
+    [
+        [values[0], 0.0, 0.0],
+        [0.0, values[1], 0.0],
+        [0.0, 0.0, values[2]],
+    ]
+}
+
+/// Inverts a 3x3 matrix, returning `None` if it is singular.
+fn invert(m: &Covariance) -> Option<Covariance> {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+
+    let determinant = m[0][0] * cofactor(1, 2, 1, 2) - m[0][1] * cofactor(1, 2, 0, 2)
+        + m[0][2] * cofactor(1, 2, 0, 1);
+
+    if determinant == 0.0 || !determinant.is_finite() {
+        return None;
+    }
+
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+
+    Some(adjugate.map(|row| row.map(|value| value / determinant)))
+}
+
+/// Tuning parameters for a [`Localizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalizerConfig {
+    /// Standard deviation of odometry position error, in meters per meter travelled.
+    pub odometry_translation_noise: f64,
+
+    /// Standard deviation of odometry heading error, in radians per radian turned.
+    pub odometry_rotation_noise: f64,
+
+    /// Standard deviation of GPS heading measurements in radians.
+    ///
+    /// The GPS sensor only reports an error estimate for its position, so heading uncertainty
+    /// must be configured separately.
+    pub gps_heading_noise: f64,
+
+    /// GPS readings with an RMS error (as reported by [`GpsSensor::error`]) greater than this
+    /// many meters are ignored.
+    pub max_gps_error: f64,
+}
+
+impl Default for LocalizerConfig {
+    fn default() -> Self {
+        Self {
+            odometry_translation_noise: 0.05,
+            odometry_rotation_noise: 0.05,
+            gps_heading_noise: 0.05,
+            max_gps_error: 0.1,
+        }
+    }
+}
+
+/// The result of feeding a GPS reading into a [`Localizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpsUpdate {
+    /// The reading was fused into the pose estimate.
+    Applied,
+
+    /// The reading was ignored because its reported error was above
+    /// [`LocalizerConfig::max_gps_error`].
+    Rejected {
+        /// The RMS error reported by the sensor in meters.
+        error: f64,
+    },
+
+    /// The sensor is disconnected, so no reading was taken. The pose estimate continues using
+    /// odometry alone.
+    Disconnected,
+}
+
+/// A pose estimator that fuses odometry with absolute position measurements.
+///
+/// The localizer tracks a pose along with its covariance using an extended Kalman filter.
+/// Odometry increments are fed in through [`Localizer::predict`], which moves the estimate and
+/// grows its uncertainty. Absolute measurements are fed in through [`Localizer::correct`] (or
+/// [`Localizer::update_gps`] for a [`GpsSensor`]), which pull the estimate towards the
+/// measurement in proportion to how much each is trusted.
+///
+/// When measurements are unavailable (for example when the GPS sensor's view of the field strip
+/// is blocked, or it is unplugged), the localizer simply continues on odometry until the next
+/// good measurement arrives. It never resets the estimate on its own.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut localizer = Localizer::new(Pose2d::ORIGIN, LocalizerConfig::default());
+///
+/// loop {
+///     localizer.predict(odometry.twist());
+///     localizer.update_gps(&gps)?;
+///
+///     println!("{}", localizer.pose());
+///     sleep(Duration::from_millis(10)).await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Localizer {
+    pose: Pose2d,
+    covariance: Covariance,
+    config: LocalizerConfig,
+}
+
+impl Localizer {
+    /// Creates a new localizer that is certain the robot starts at `initial_pose`.
+    pub const fn new(initial_pose: Pose2d, config: LocalizerConfig) -> Self {
+        Self::with_covariance(initial_pose, [[0.0; 3]; 3], config)
+    }
+
+    /// Creates a new localizer with some initial uncertainty about the robot's pose.
+    pub const fn with_covariance(
+        initial_pose: Pose2d,
+        covariance: Covariance,
+        config: LocalizerConfig,
+    ) -> Self {
+        Self {
+            pose: initial_pose,
+            covariance,
+            config,
+        }
+    }
+
+    /// Returns the current pose estimate.
+    pub const fn pose(&self) -> Pose2d {
+        self.pose
+    }
+
+    /// Returns the covariance of the current pose estimate.
+    pub const fn covariance(&self) -> Covariance {
+        self.covariance
+    }
+
+    /// Returns the localizer's tuning parameters.
+    pub const fn config(&self) -> &LocalizerConfig {
+        &self.config
+    }
+
+    /// Overwrites the pose estimate and its covariance.
+    pub fn reset(&mut self, pose: Pose2d, covariance: Covariance) {
+        self.pose = pose;
+        self.covariance = covariance;
+    }
+
+    /// Advances the estimate by an odometry increment, expressed in the robot's frame.
+    pub fn predict(&mut self, twist: Twist2d) {
+        let heading = self.pose.rotation;
+
+        // Jacobian of the motion model with respect to the state.
+        let dx = heading.cos() * twist.dx - heading.sin() * twist.dy;
+        let dy = heading.sin() * twist.dx + heading.cos() * twist.dy;
+        let jacobian = [[1.0, 0.0, -dy], [0.0, 1.0, dx], [0.0, 0.0, 1.0]];
+
+        // Odometry error grows with the distance travelled.
+        let translation_std_dev = self.config.odometry_translation_noise * twist.dx.hypot(twist.dy);
+        let rotation_std_dev = self.config.odometry_rotation_noise * twist.dtheta;
+        let translation_variance = translation_std_dev * translation_std_dev;
+        let rotation_variance = rotation_std_dev * rotation_std_dev;
+        let process_noise = diagonal([
+            translation_variance,
+            translation_variance,
+            rotation_variance,
+        ]);
+
+        self.pose = self.pose.exp(twist);
+        self.covariance = add(
+            &multiply(
+                &multiply(&jacobian, &self.covariance),
+                &transpose(&jacobian),
+            ),
+            &process_noise,
+        );
+    }
+
+    /// Fuses an absolute pose measurement into the estimate.
+    ///
+    /// `measurement_covariance` describes the uncertainty of the measurement. To measure only
+    /// part of the pose, use a very large variance for the components that weren't measured.
+    pub fn correct(&mut self, measurement: Pose2d, measurement_covariance: Covariance) {
+        let Some(innovation_inverse) = invert(&add(&self.covariance, &measurement_covariance))
+        else {
+            return;
+        };
+
+        let gain = multiply(&self.covariance, &innovation_inverse);
+
+        let residual = [
+            measurement.position.x - self.pose.position.x,
+            measurement.position.y - self.pose.position.y,
+            (measurement.rotation - self.pose.rotation).radians(),
+        ];
+        let correction: [f64; 3] =
+            core::array::from_fn(|row| (0..3).map(|i| gain[row][i] * residual[i]).sum());
+
+        self.pose = Pose2d {
+            position: self.pose.position + Point2::new(correction[0], correction[1]),
+            rotation: self.pose.rotation + Rotation2d::from_radians(correction[2]),
+        };
+
+        let mut identity_minus_gain = IDENTITY;
+        for (row, gain_row) in identity_minus_gain.iter_mut().zip(gain) {
+            for (value, gain_value) in row.iter_mut().zip(gain_row) {
+                *value -= gain_value;
+            }
+        }
+
+        self.covariance = multiply(&identity_minus_gain, &self.covariance);
+    }
+
+    /// Takes a reading from a GPS sensor and fuses it into the estimate.
+    ///
+    /// The reading is weighted using the RMS error reported by [`GpsSensor::error`], and is
+    /// ignored if that error is above [`LocalizerConfig::max_gps_error`].
+    ///
+    /// # Errors
+    ///
+    /// A disconnected sensor is reported as [`GpsUpdate::Disconnected`] rather than an error,
+    /// so that brief dropouts can be handled by simply continuing. Any other port error is
+    /// returned.
+    pub fn update_gps(&mut self, gps: &GpsSensor) -> Result<GpsUpdate, PortError> {
+        let reading = gps.error().and_then(|error| Ok((error, gps.pose()?)));
+
+        let (error, pose) = match reading {
+            Ok(reading) => reading,
+            Err(PortError::Disconnected) => return Ok(GpsUpdate::Disconnected),
+            Err(err) => return Err(err),
+        };
+
+        if error > self.config.max_gps_error {
+            return Ok(GpsUpdate::Rejected { error });
+        }
+
+        let position_variance = error * error;
+        let heading_variance = self.config.gps_heading_noise * self.config.gps_heading_noise;
+
+        self.correct(
+            pose.into(),
+            diagonal([position_variance, position_variance, heading_variance]),
+        );
+
+        Ok(GpsUpdate::Applied)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn inverts_matrix() {
+        let matrix = [[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]];
+        let product = multiply(&matrix, &invert(&matrix).unwrap());
+
+        for (row, identity_row) in product.iter().zip(IDENTITY) {
+            for (&value, expected) in row.iter().zip(identity_row) {
+                assert_close(value, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert_eq!(invert(&[[0.0; 3]; 3]), None);
+    }
+
+    #[test]
+    fn predict_moves_pose_and_grows_translation_variance() {
+        let mut localizer = Localizer::new(Pose2d::ORIGIN, LocalizerConfig::default());
+        localizer.predict(Twist2d::new(1.0, 0.0, 0.0));
+
+        let pose = localizer.pose();
+        assert_close(pose.position.x, 1.0);
+        assert_close(pose.position.y, 0.0);
+
+        let covariance = localizer.covariance();
+        assert_close(covariance[0][0], 0.05 * 0.05);
+        assert_close(covariance[1][1], 0.05 * 0.05);
+        assert_close(covariance[2][2], 0.0);
+    }
+
+    #[test]
+    fn predict_spreads_heading_uncertainty_sideways() {
+        let mut localizer = Localizer::with_covariance(
+            Pose2d::ORIGIN,
+            diagonal([0.0, 0.0, 0.01]),
+            LocalizerConfig::default(),
+        );
+        localizer.predict(Twist2d::new(1.0, 0.0, 0.0));
+
+        // A heading error of θ moves a robot driving 1m forward sideways by about θ meters.
+        let covariance = localizer.covariance();
+        assert_close(covariance[0][0], 0.05 * 0.05);
+        assert_close(covariance[1][1], 0.01 + 0.05 * 0.05);
+        assert_close(covariance[1][2], 0.01);
+        assert_close(covariance[2][1], 0.01);
+    }
+
+    #[test]
+    fn correct_weights_equally_trusted_measurement_halfway() {
+        let mut localizer = Localizer::with_covariance(
+            Pose2d::ORIGIN,
+            diagonal([1.0, 1.0, 1.0]),
+            LocalizerConfig::default(),
+        );
+        localizer.correct(
+            Pose2d::new((2.0, 4.0), Rotation2d::from_radians(0.5)),
+            diagonal([1.0, 1.0, 1.0]),
+        );
+
+        let pose = localizer.pose();
+        assert_close(pose.position.x, 1.0);
+        assert_close(pose.position.y, 2.0);
+        assert_close(pose.rotation.radians(), 0.25);
+
+        for index in 0..3 {
+            assert_close(localizer.covariance()[index][index], 0.5);
+        }
+    }
+
+    #[test]
+    fn correct_ignores_untrusted_components() {
+        let mut localizer = Localizer::with_covariance(
+            Pose2d::ORIGIN,
+            diagonal([1.0, 1.0, 1.0]),
+            LocalizerConfig::default(),
+        );
+        localizer.correct(
+            Pose2d::new((2.0, 2.0), Rotation2d::IDENTITY),
+            diagonal([0.0, 1e12, 1e12]),
+        );
+
+        let pose = localizer.pose();
+        assert_close(pose.position.x, 2.0);
+        assert!(pose.position.y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn correct_with_singular_innovation_does_nothing() {
+        let mut localizer = Localizer::new(Pose2d::ORIGIN, LocalizerConfig::default());
+        localizer.correct(Pose2d::new((1.0, 1.0), Rotation2d::IDENTITY), [[0.0; 3]; 3]);
+
+        assert_eq!(localizer.pose(), Pose2d::ORIGIN);
+    }
+}
@@ -0,0 +1,22 @@
+//! Robot localization.
+//!
+//! This module provides tools for estimating the robot's [`Pose2d`](crate::geometry::Pose2d)
+//! on the field by combining several imperfect sources of information.
+//!
+//! - [`Localizer`] fuses odometry with absolute position measurements (such as those from a
+//!   [`GpsSensor`](crate::smart::GpsSensor)) using an extended Kalman filter.
+//! - [`WallRelocalizer`] corrects odometry drift using
+//!   [`DistanceSensor`](crate::smart::DistanceSensor) readings of known field walls.
+//! - [`ParticleFilter`] performs Monte Carlo localization using distance sensors and GPS
+//!   readings, and can recover from large disturbances such as collisions.
+//!
+//! All poses in this module use the field coordinate frame described on
+//! [`Pose2d`](crate::geometry::Pose2d).
+
+mod ekf;
+mod particle;
+mod wall;
+
+pub use ekf::{Covariance, GpsUpdate, Localizer, LocalizerConfig};
+pub use particle::{Particle, ParticleFilter, ParticleFilterConfig};
+pub use wall::{DistanceReading, RelocalizerConfig, WallCorrection, WallRelocalizer, WallSegment};
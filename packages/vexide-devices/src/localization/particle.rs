@@ -0,0 +1,602 @@
+use core::f64::consts::TAU;
+
+use vexide_core::float::Float;
+
+use super::{wall::raycast, Covariance, DistanceReading, GpsUpdate, WallSegment};
+use crate::{
+    geometry::{Point2, Pose2d, Rotation2d, Twist2d},
+    smart::GpsSensor,
+    PortError,
+};
+
+/// A small, fast pseudorandom number generator (xorshift64*).
+///
+/// Particle filters only need statistically reasonable noise rather than cryptographic quality,
+/// and this avoids depending on an external RNG crate.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // A zero state would only ever produce zeroes.
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a uniformly distributed number in `[0, 1)`.
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a normally distributed number with a mean of zero and a standard deviation of
+    /// one, using the Box-Muller transform.
+    fn gaussian(&mut self) -> f64 {
+        let magnitude = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+        magnitude * (TAU * self.uniform()).cos()
+    }
+}
+
+/// Evaluates the probability density of a normal distribution.
+fn gaussian_density(residual: f64, std_dev: f64) -> f64 {
+    (-0.5 * (residual / std_dev) * (residual / std_dev)).exp() / (std_dev * TAU.sqrt())
+}
+
+/// A single hypothesis of the robot's pose.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// The pose this particle represents.
+    pub pose: Pose2d,
+
+    /// The relative likelihood of this particle. Weights are normalized to sum to 1.
+    pub weight: f64,
+}
+
+/// Tuning parameters for a [`ParticleFilter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleFilterConfig {
+    /// Standard deviation of odometry position error, in meters per meter travelled.
+    pub odometry_translation_noise: f64,
+
+    /// Standard deviation of odometry heading error, in radians per radian turned.
+    pub odometry_rotation_noise: f64,
+
+    /// Standard deviation of distance sensor readings in meters.
+    pub distance_noise: f64,
+
+    /// The maximum range of the distance sensors in meters.
+    ///
+    /// If this is not positive, readings are never treated as unexpected, regardless of
+    /// [`ParticleFilterConfig::unexpected_reading_probability`].
+    pub max_distance: f64,
+
+    /// The probability (from 0.0 to 1.0) that a distance reading is caused by something other
+    /// than a wall, such as a game object or another robot.
+    ///
+    /// Higher values make the filter more robust to unexpected obstacles, at the cost of
+    /// trusting every reading less.
+    pub unexpected_reading_probability: f64,
+
+    /// Distance readings with a confidence below this value (from 0.0 to 1.0) are ignored.
+    pub min_distance_confidence: f64,
+
+    /// Standard deviation of GPS heading measurements in radians.
+    pub gps_heading_noise: f64,
+
+    /// GPS readings with an RMS error (as reported by [`GpsSensor::error`]) greater than this
+    /// many meters are ignored.
+    pub max_gps_error: f64,
+
+    /// Standard deviation in meters of noise added to particle positions when resampling.
+    ///
+    /// Resampling duplicates likely particles, so without this noise the particles can
+    /// collapse onto a handful of identical poses while the robot is moving slowly.
+    pub resample_position_noise: f64,
+
+    /// Standard deviation in radians of noise added to particle headings when resampling.
+    pub resample_heading_noise: f64,
+
+    /// The fraction of particles (from 0.0 to 1.0) replaced with uniformly random poses each
+    /// time the filter resamples.
+    ///
+    /// This allows the filter to recover if the robot is knocked somewhere unexpected, such as
+    /// after a collision.
+    pub random_particle_fraction: f64,
+}
+
+impl Default for ParticleFilterConfig {
+    fn default() -> Self {
+        Self {
+            odometry_translation_noise: 0.1,
+            odometry_rotation_noise: 0.1,
+            distance_noise: 0.03,
+            max_distance: 2.0,
+            unexpected_reading_probability: 0.2,
+            min_distance_confidence: 0.5,
+            gps_heading_noise: 0.05,
+            max_gps_error: 0.1,
+            resample_position_noise: 0.01,
+            resample_heading_noise: 0.01,
+            random_particle_fraction: 0.01,
+        }
+    }
+}
+
+/// A Monte Carlo (particle filter) localizer.
+///
+/// Unlike [`Localizer`](super::Localizer), which tracks a single estimate, a particle filter
+/// tracks `N` hypotheses of where the robot might be. Each odometry update moves every particle
+/// with some added noise, and each sensor reading reweights the particles by how well they
+/// explain it. Unlikely particles are periodically replaced with copies of likely ones.
+///
+/// This makes particle filters able to localize globally (without knowing the starting pose)
+/// and recover from large disturbances like collisions, at the cost of more computation.
+///
+/// Particles are stored inline, so the filter never allocates. Larger values of `N` are more
+/// robust but slower. A few hundred particles is usually a good starting point.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut filter = ParticleFilter::<300>::new(&WallSegment::FIELD_PERIMETER, Default::default(), 1234);
+///
+/// loop {
+///     filter.predict(odometry.twist());
+///     filter.update_distance(&readings);
+///     filter.update_gps(&gps)?;
+///
+///     println!("{}", filter.estimate());
+///     sleep(Duration::from_millis(10)).await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParticleFilter<'a, const N: usize> {
+    particles: [Particle; N],
+    walls: &'a [WallSegment],
+    config: ParticleFilterConfig,
+    rng: Rng,
+}
+
+impl<'a, const N: usize> ParticleFilter<'a, N> {
+    /// Creates a new particle filter with particles spread uniformly across the field.
+    ///
+    /// The bounds of the field are determined from the walls in the field map. `seed` is used
+    /// to initialize the filter's random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    pub fn new(walls: &'a [WallSegment], config: ParticleFilterConfig, seed: u64) -> Self {
+        assert!(N > 0, "ParticleFilter must contain at least one particle.");
+
+        let mut filter = Self {
+            particles: [Particle::default(); N],
+            walls,
+            config,
+            rng: Rng::new(seed),
+        };
+        filter.reset_uniform();
+
+        filter
+    }
+
+    /// Returns the particles currently tracked by the filter.
+    pub const fn particles(&self) -> &[Particle; N] {
+        &self.particles
+    }
+
+    /// Returns the filter's tuning parameters.
+    pub const fn config(&self) -> &ParticleFilterConfig {
+        &self.config
+    }
+
+    /// Returns the wall segments in the field map.
+    pub const fn walls(&self) -> &'a [WallSegment] {
+        self.walls
+    }
+
+    /// Returns the bounding box of the field map as `(min, max)` corners.
+    fn bounds(&self) -> (Point2<f64>, Point2<f64>) {
+        let mut min = Point2::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+        for point in self.walls.iter().flat_map(|wall| [wall.start, wall.end]) {
+            min = Point2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Point2::new(max.x.max(point.x), max.y.max(point.y));
+        }
+
+        if self.walls.is_empty() {
+            (Point2::default(), Point2::default())
+        } else {
+            (min, max)
+        }
+    }
+
+    fn random_pose(&mut self) -> Pose2d {
+        let (min, max) = self.bounds();
+
+        Pose2d {
+            position: Point2::new(
+                min.x + self.rng.uniform() * (max.x - min.x),
+                min.y + self.rng.uniform() * (max.y - min.y),
+            ),
+            rotation: Rotation2d::from_radians(self.rng.uniform() * TAU),
+        }
+    }
+
+    /// Spreads the particles uniformly across the field, forgetting the current estimate.
+    pub fn reset_uniform(&mut self) {
+        for i in 0..N {
+            self.particles[i] = Particle {
+                pose: self.random_pose(),
+                weight: 1.0 / N as f64,
+            };
+        }
+    }
+
+    /// Spreads the particles around a known pose.
+    ///
+    /// `position_std_dev` is in meters and `heading_std_dev` is in radians.
+    pub fn reset_around(&mut self, pose: Pose2d, position_std_dev: f64, heading_std_dev: f64) {
+        for particle in &mut self.particles {
+            *particle = Particle {
+                pose: Pose2d {
+                    position: pose.position
+                        + Point2::new(
+                            self.rng.gaussian() * position_std_dev,
+                            self.rng.gaussian() * position_std_dev,
+                        ),
+                    rotation: pose.rotation
+                        + Rotation2d::from_radians(self.rng.gaussian() * heading_std_dev),
+                },
+                weight: 1.0 / N as f64,
+            };
+        }
+    }
+
+    /// Moves every particle by an odometry increment, expressed in the robot's frame.
+    pub fn predict(&mut self, twist: Twist2d) {
+        let translation_std_dev = self.config.odometry_translation_noise * twist.dx.hypot(twist.dy);
+        let rotation_std_dev = self.config.odometry_rotation_noise * twist.dtheta.abs();
+
+        for particle in &mut self.particles {
+            let noisy_twist = Twist2d {
+                dx: twist.dx + self.rng.gaussian() * translation_std_dev,
+                dy: twist.dy + self.rng.gaussian() * translation_std_dev,
+                dtheta: twist.dtheta + self.rng.gaussian() * rotation_std_dev,
+            };
+
+            particle.pose = particle.pose.exp(noisy_twist);
+        }
+    }
+
+    /// Reweights the particles using distance sensor readings of the field walls.
+    ///
+    /// Readings below [`ParticleFilterConfig::min_distance_confidence`] are ignored.
+    pub fn update_distance(&mut self, readings: &[DistanceReading]) {
+        let config = self.config;
+
+        // Unexpected readings are spread evenly over the sensor's range. A range of zero would
+        // make this density infinite and every weight NaN, so it disables the model instead.
+        let unexpected_density = if config.max_distance > 0.0 {
+            config.unexpected_reading_probability / config.max_distance
+        } else {
+            0.0
+        };
+
+        let readings = readings
+            .iter()
+            .filter(|reading| reading.confidence >= config.min_distance_confidence);
+
+        for reading in readings {
+            for particle in &mut self.particles {
+                let hit_density = raycast(self.walls, particle.pose + reading.mount).map_or(
+                    0.0,
+                    |(expected, _)| {
+                        gaussian_density(reading.distance - expected, config.distance_noise)
+                    },
+                );
+
+                particle.weight *= (1.0 - config.unexpected_reading_probability) * hit_density
+                    + unexpected_density;
+            }
+        }
+
+        self.normalize_and_resample();
+    }
+
+    /// Reweights the particles using an absolute pose measurement.
+    ///
+    /// `position_std_dev` is in meters and `heading_std_dev` is in radians.
+    pub fn update_pose(
+        &mut self,
+        measurement: Pose2d,
+        position_std_dev: f64,
+        heading_std_dev: f64,
+    ) {
+        for particle in &mut self.particles {
+            let offset = measurement.position - particle.pose.position;
+            let heading_error = (measurement.rotation - particle.pose.rotation).radians();
+
+            particle.weight *= gaussian_density(offset.x, position_std_dev)
+                * gaussian_density(offset.y, position_std_dev)
+                * gaussian_density(heading_error, heading_std_dev);
+        }
+
+        self.normalize_and_resample();
+    }
+
+    /// Takes a reading from a GPS sensor and uses it to reweight the particles.
+    ///
+    /// The reading is weighted using the RMS error reported by [`GpsSensor::error`], and is
+    /// ignored if that error is above [`ParticleFilterConfig::max_gps_error`].
+    ///
+    /// # Errors
+    ///
+    /// A disconnected sensor is reported as [`GpsUpdate::Disconnected`] rather than an error.
+    /// Any other port error is returned.
+    pub fn update_gps(&mut self, gps: &GpsSensor) -> Result<GpsUpdate, PortError> {
+        let reading = gps.error().and_then(|error| Ok((error, gps.pose()?)));
+
+        let (error, pose) = match reading {
+            Ok(reading) => reading,
+            Err(PortError::Disconnected) => return Ok(GpsUpdate::Disconnected),
+            Err(err) => return Err(err),
+        };
+
+        if error > self.config.max_gps_error {
+            return Ok(GpsUpdate::Rejected { error });
+        }
+
+        // Guard against a reported error of exactly zero, which would make every particle
+        // infinitely unlikely.
+        self.update_pose(pose.into(), error.max(0.001), self.config.gps_heading_noise);
+
+        Ok(GpsUpdate::Applied)
+    }
+
+    /// Normalizes particle weights, and resamples the particles if too few of them are likely.
+    fn normalize_and_resample(&mut self) {
+        let total: f64 = self.particles.iter().map(|particle| particle.weight).sum();
+
+        // Every particle has become (numerically) impossible, so the estimate is lost entirely.
+        if total <= 0.0 || !total.is_finite() {
+            self.reset_uniform();
+            return;
+        }
+
+        let mut sum_of_squares = 0.0;
+        for particle in &mut self.particles {
+            particle.weight /= total;
+            sum_of_squares += particle.weight * particle.weight;
+        }
+
+        // Resample once the effective number of particles drops below half.
+        if 1.0 / sum_of_squares < N as f64 / 2.0 {
+            self.resample();
+        }
+    }
+
+    /// Replaces the particles with a new set drawn in proportion to their weights, using
+    /// low-variance (systematic) resampling.
+    fn resample(&mut self) {
+        let previous = self.particles;
+        let random_count =
+            (N as f64 * self.config.random_particle_fraction.clamp(0.0, 1.0)) as usize;
+        let resampled_count = N - random_count;
+
+        let step = 1.0 / resampled_count as f64;
+        let mut target = self.rng.uniform() * step;
+        let mut cumulative = previous[0].weight;
+        let mut source = 0;
+
+        for i in 0..resampled_count {
+            while target > cumulative && source < N - 1 {
+                source += 1;
+                cumulative += previous[source].weight;
+            }
+
+            let pose = previous[source].pose;
+            self.particles[i] = Particle {
+                pose: Pose2d {
+                    position: pose.position
+                        + Point2::new(
+                            self.rng.gaussian() * self.config.resample_position_noise,
+                            self.rng.gaussian() * self.config.resample_position_noise,
+                        ),
+                    rotation: pose.rotation
+                        + Rotation2d::from_radians(
+                            self.rng.gaussian() * self.config.resample_heading_noise,
+                        ),
+                },
+                weight: 1.0 / N as f64,
+            };
+            target += step;
+        }
+
+        for i in resampled_count..N {
+            self.particles[i] = Particle {
+                pose: self.random_pose(),
+                weight: 1.0 / N as f64,
+            };
+        }
+    }
+
+    /// Returns the weighted mean of the particles' poses.
+    pub fn estimate(&self) -> Pose2d {
+        let mut position = Point2::default();
+        let (mut cos, mut sin) = (0.0, 0.0);
+
+        for particle in &self.particles {
+            position += particle.pose.position * particle.weight;
+            cos += particle.pose.rotation.cos() * particle.weight;
+            sin += particle.pose.rotation.sin() * particle.weight;
+        }
+
+        Pose2d {
+            position,
+            rotation: Rotation2d::from_components(cos, sin),
+        }
+    }
+
+    /// Returns the weighted covariance of the particles' poses around [`Self::estimate`].
+    ///
+    /// This is a useful measure of how confident the filter is. A large covariance means the
+    /// particles are spread out, and the estimate should not be trusted yet.
+    pub fn covariance(&self) -> Covariance {
+        let mean = self.estimate();
+        let mut covariance = [[0.0; 3]; 3];
+
+        for particle in &self.particles {
+            let offset = particle.pose.position - mean.position;
+            let deviation = [
+                offset.x,
+                offset.y,
+                (particle.pose.rotation - mean.rotation).radians(),
+            ];
+
+            for (row, covariance_row) in covariance.iter_mut().enumerate() {
+                for (column, value) in covariance_row.iter_mut().enumerate() {
+                    *value += particle.weight * deviation[row] * deviation[column];
+                }
+            }
+        }
+
+        covariance
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::Transform2d;
+
+    /// Distance sensors facing forwards, left, backwards and right.
+    fn mounts() -> [Transform2d; 4] {
+        [0.0, 90.0, 180.0, 270.0]
+            .map(|degrees| Transform2d::new((0.0, 0.0), Rotation2d::from_degrees(degrees)))
+    }
+
+    /// Returns the readings the sensors would take with the robot at `pose`.
+    fn synthetic_readings(pose: Pose2d) -> [DistanceReading; 4] {
+        mounts().map(|mount| DistanceReading {
+            mount,
+            distance: raycast(&WallSegment::FIELD_PERIMETER, pose + mount)
+                .unwrap()
+                .0,
+            confidence: 1.0,
+        })
+    }
+
+    #[test]
+    fn converges_on_pose_from_wall_readings() {
+        let actual = Pose2d::new((0.6, -0.4), Rotation2d::from_degrees(20.0));
+        let mut filter = ParticleFilter::<500>::new(
+            &WallSegment::FIELD_PERIMETER,
+            ParticleFilterConfig {
+                max_distance: 4.0,
+                ..Default::default()
+            },
+            1234,
+        );
+
+        // Start from a guess that is off by a few centimeters, as after some odometry drift.
+        filter.reset_around(
+            Pose2d::new((0.5, -0.3), Rotation2d::from_degrees(20.0)),
+            0.15,
+            0.05,
+        );
+
+        for _ in 0..20 {
+            filter.predict(Twist2d::default());
+            filter.update_distance(&synthetic_readings(actual));
+        }
+
+        let estimate = filter.estimate();
+        assert!(
+            estimate.distance(actual) < 0.03,
+            "estimate {estimate} is too far from {actual}"
+        );
+        assert!((estimate.rotation - actual.rotation).radians().abs() < 0.05);
+    }
+
+    #[test]
+    fn tracks_pose_while_moving() {
+        let mut actual = Pose2d::new((-0.5, 0.0), Rotation2d::IDENTITY);
+        let mut filter = ParticleFilter::<500>::new(
+            &WallSegment::FIELD_PERIMETER,
+            ParticleFilterConfig {
+                max_distance: 4.0,
+                ..Default::default()
+            },
+            42,
+        );
+        filter.reset_around(actual, 0.02, 0.01);
+
+        let twist = Twist2d::new(0.02, 0.0, 0.01);
+        for _ in 0..50 {
+            actual = actual.exp(twist);
+            filter.predict(twist);
+            filter.update_distance(&synthetic_readings(actual));
+        }
+
+        assert!(filter.estimate().distance(actual) < 0.03);
+    }
+
+    #[test]
+    fn update_pose_pulls_particles_towards_measurement() {
+        let mut filter =
+            ParticleFilter::<300>::new(&WallSegment::FIELD_PERIMETER, Default::default(), 7);
+        let measurement = Pose2d::new((1.0, 1.0), Rotation2d::from_degrees(45.0));
+        filter.reset_around(
+            Pose2d::new((0.8, 1.1), Rotation2d::from_degrees(40.0)),
+            0.2,
+            0.1,
+        );
+
+        for _ in 0..10 {
+            filter.update_pose(measurement, 0.05, 0.05);
+        }
+
+        assert!(filter.estimate().distance(measurement) < 0.05);
+    }
+
+    #[test]
+    fn zero_max_distance_keeps_weights_finite() {
+        let mut filter = ParticleFilter::<100>::new(
+            &WallSegment::FIELD_PERIMETER,
+            ParticleFilterConfig {
+                max_distance: 0.0,
+                ..Default::default()
+            },
+            99,
+        );
+
+        filter.update_distance(&synthetic_readings(Pose2d::ORIGIN));
+
+        assert!(filter
+            .particles()
+            .iter()
+            .all(|particle| particle.weight.is_finite()));
+        assert!(filter.estimate().position.x.is_finite());
+    }
+
+    #[test]
+    fn weights_stay_normalized() {
+        let mut filter =
+            ParticleFilter::<200>::new(&WallSegment::FIELD_PERIMETER, Default::default(), 3);
+        filter.update_distance(&synthetic_readings(Pose2d::ORIGIN));
+
+        let total: f64 = filter
+            .particles()
+            .iter()
+            .map(|particle| particle.weight)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+}
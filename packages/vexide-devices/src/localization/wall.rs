@@ -0,0 +1,472 @@
+use vexide_core::float::Float;
+
+use super::{Covariance, Localizer};
+use crate::{
+    geometry::{Point2, Pose2d, Transform2d},
+    smart::{distance::DistanceError, DistanceSensor},
+};
+
+/// Regularization added to the correction's normal equations, so that readings which all hit
+/// parallel walls still produce a (one-dimensional) correction.
+const REGULARIZATION: f64 = 1e-9;
+
+/// Variance used for components of the pose that wall readings cannot observe.
+const UNOBSERVED_VARIANCE: f64 = 1e9;
+
+fn dot(a: Point2<f64>, b: Point2<f64>) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Point2<f64>, b: Point2<f64>) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+/// A straight wall on the field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallSegment {
+    /// One end of the wall.
+    pub start: Point2<f64>,
+
+    /// The other end of the wall.
+    pub end: Point2<f64>,
+}
+
+impl WallSegment {
+    /// Half the width of a standard 12' x 12' field, in meters.
+    const HALF_FIELD: f64 = 1.8288;
+
+    /// The four perimeter walls of a standard 12' x 12' field, in the field frame used by
+    /// [`GpsSensor::pose`](crate::smart::GpsSensor::pose).
+    pub const FIELD_PERIMETER: [Self; 4] = [
+        Self::new(
+            Point2::new(-Self::HALF_FIELD, -Self::HALF_FIELD),
+            Point2::new(Self::HALF_FIELD, -Self::HALF_FIELD),
+        ),
+        Self::new(
+            Point2::new(Self::HALF_FIELD, -Self::HALF_FIELD),
+            Point2::new(Self::HALF_FIELD, Self::HALF_FIELD),
+        ),
+        Self::new(
+            Point2::new(Self::HALF_FIELD, Self::HALF_FIELD),
+            Point2::new(-Self::HALF_FIELD, Self::HALF_FIELD),
+        ),
+        Self::new(
+            Point2::new(-Self::HALF_FIELD, Self::HALF_FIELD),
+            Point2::new(-Self::HALF_FIELD, -Self::HALF_FIELD),
+        ),
+    ];
+
+    /// Creates a new wall between two points.
+    pub const fn new(start: Point2<f64>, end: Point2<f64>) -> Self {
+        Self { start, end }
+    }
+
+    /// Finds where a ray hits this wall.
+    ///
+    /// Returns the distance along the ray and the wall's unit normal facing the ray's origin, or
+    /// `None` if the ray misses.
+    fn intersect(&self, origin: Point2<f64>, direction: Point2<f64>) -> Option<(f64, Point2<f64>)> {
+        let edge = self.end - self.start;
+        let denominator = cross(direction, edge);
+
+        // The ray is parallel to the wall.
+        if denominator.abs() < f64::EPSILON {
+            return None;
+        }
+
+        let to_start = self.start - origin;
+        let distance = cross(to_start, edge) / denominator;
+        let along_wall = cross(to_start, direction) / denominator;
+
+        if distance <= 0.0 || !(0.0..=1.0).contains(&along_wall) {
+            return None;
+        }
+
+        let length = edge.x.hypot(edge.y);
+        let mut normal = Point2::new(-edge.y / length, edge.x / length);
+        if dot(normal, origin - self.start) < 0.0 {
+            normal = -normal;
+        }
+
+        Some((distance, normal))
+    }
+}
+
+/// Computes the distance a sensor at `sensor_pose` would read to the nearest wall, along with
+/// that wall's unit normal.
+pub(crate) fn raycast(walls: &[WallSegment], sensor_pose: Pose2d) -> Option<(f64, Point2<f64>)> {
+    let direction = Point2::new(sensor_pose.rotation.cos(), sensor_pose.rotation.sin());
+
+    walls
+        .iter()
+        .filter_map(|wall| wall.intersect(sensor_pose.position, direction))
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+}
+
+/// A single reading from a distance sensor mounted on the robot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceReading {
+    /// The pose of the sensor relative to the robot's center, facing the direction the
+    /// sensor points.
+    pub mount: Transform2d,
+
+    /// The measured distance in meters.
+    pub distance: f64,
+
+    /// The sensor's confidence in the reading, from 0.0 to 1.0.
+    pub confidence: f64,
+}
+
+impl DistanceReading {
+    /// Takes a reading from a distance sensor mounted at `mount` on the robot.
+    ///
+    /// Returns `None` if the sensor does not detect anything within its range.
+    pub fn from_sensor(
+        sensor: &DistanceSensor,
+        mount: Transform2d,
+    ) -> Result<Option<Self>, DistanceError> {
+        let Some(distance) = sensor.distance()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            mount,
+            distance: distance as f64 / 1000.0,
+            confidence: sensor.distance_confidence()?,
+        }))
+    }
+}
+
+/// Tuning parameters for a [`WallRelocalizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelocalizerConfig {
+    /// Readings with a confidence below this value (from 0.0 to 1.0) are ignored.
+    pub min_confidence: f64,
+
+    /// Readings that differ from the expected distance to a wall by more than this many meters
+    /// are ignored.
+    ///
+    /// This is what rejects readings that hit game objects or other robots instead of a wall,
+    /// so it should be larger than the expected odometry drift but smaller than the game
+    /// objects on the field.
+    pub max_residual: f64,
+
+    /// Readings that hit a wall at more than this angle (in radians) from head-on are
+    /// ignored, since distance sensors become unreliable at shallow angles.
+    pub max_incidence_angle: f64,
+
+    /// Standard deviation of distance sensor readings in meters, used when correcting a
+    /// [`Localizer`].
+    pub measurement_noise: f64,
+}
+
+impl Default for RelocalizerConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.5,
+            max_residual: 0.1,
+            max_incidence_angle: 0.5,
+            measurement_noise: 0.015,
+        }
+    }
+}
+
+/// A position correction computed from distance sensor readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallCorrection {
+    /// The offset that should be added to the robot's position, in meters.
+    pub offset: Point2<f64>,
+
+    /// The covariance of the corrected pose.
+    ///
+    /// Directions that none of the readings constrain (such as along a wall, or the robot's
+    /// heading) have a very large variance.
+    pub covariance: Covariance,
+
+    /// The number of readings that were used to compute the correction.
+    pub readings_used: usize,
+}
+
+impl WallCorrection {
+    /// Applies the correction to a pose.
+    pub fn apply(&self, pose: Pose2d) -> Pose2d {
+        Pose2d {
+            position: pose.position + self.offset,
+            rotation: pose.rotation,
+        }
+    }
+}
+
+/// Corrects odometry drift by comparing distance sensor readings against known walls.
+///
+/// Each distance sensor reading is compared against the distance the sensor *should* read if
+/// the robot were exactly at its estimated pose. The difference tells the robot how far it has
+/// drifted towards or away from that wall. Readings are combined in a least-squares sense, so a
+/// single sensor corrects one axis and sensors facing perpendicular walls correct both.
+///
+/// Only the robot's position is corrected. Heading is left unchanged.
+///
+/// # Examples
+///
+/// ```no_run
+/// let relocalizer = WallRelocalizer::new(&WallSegment::FIELD_PERIMETER, Default::default());
+/// let mount = Transform2d::new((0.0, -0.15), Rotation2d::from_degrees(-90.0));
+///
+/// if let Some(reading) = DistanceReading::from_sensor(&right_sensor, mount)? {
+///     relocalizer.correct_localizer(&mut localizer, &[reading]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallRelocalizer<'a> {
+    walls: &'a [WallSegment],
+    config: RelocalizerConfig,
+}
+
+impl<'a> WallRelocalizer<'a> {
+    /// Creates a new relocalizer for a field map made of wall segments.
+    pub const fn new(walls: &'a [WallSegment], config: RelocalizerConfig) -> Self {
+        Self { walls, config }
+    }
+
+    /// Returns the wall segments in the field map.
+    pub const fn walls(&self) -> &'a [WallSegment] {
+        self.walls
+    }
+
+    /// Returns the relocalizer's tuning parameters.
+    pub const fn config(&self) -> &RelocalizerConfig {
+        &self.config
+    }
+
+    /// Returns the distance a sensor mounted at `mount` should read if the robot is at `pose`,
+    /// or `None` if the sensor isn't facing any wall.
+    pub fn expected_distance(&self, pose: Pose2d, mount: Transform2d) -> Option<f64> {
+        raycast(self.walls, pose + mount).map(|(distance, _)| distance)
+    }
+
+    /// Computes a position correction for a robot estimated to be at `pose`.
+    ///
+    /// Returns `None` if none of the readings could be matched to a wall.
+    pub fn correction(&self, pose: Pose2d, readings: &[DistanceReading]) -> Option<WallCorrection> {
+        let min_incidence_cos = self.config.max_incidence_angle.cos();
+
+        // Normal equations of the least-squares problem `normal · offset = error`.
+        let mut information = [[REGULARIZATION, 0.0], [0.0, REGULARIZATION]];
+        let mut target = [0.0; 2];
+        let mut readings_used = 0;
+
+        for reading in readings {
+            if reading.confidence < self.config.min_confidence {
+                continue;
+            }
+
+            let sensor_pose = pose + reading.mount;
+            let Some((expected, normal)) = raycast(self.walls, sensor_pose) else {
+                continue;
+            };
+
+            let residual = reading.distance - expected;
+            if residual.abs() > self.config.max_residual {
+                continue;
+            }
+
+            let direction = Point2::new(sensor_pose.rotation.cos(), sensor_pose.rotation.sin());
+            let incidence_cos = -dot(direction, normal);
+            if incidence_cos < min_incidence_cos {
+                continue;
+            }
+
+            // Reading further than expected means the robot is further from the wall, so it
+            // should move along the wall's normal by the perpendicular part of the residual.
+            let error = residual * incidence_cos;
+            information[0][0] += normal.x * normal.x;
+            information[0][1] += normal.x * normal.y;
+            information[1][0] += normal.y * normal.x;
+            information[1][1] += normal.y * normal.y;
+            target[0] += normal.x * error;
+            target[1] += normal.y * error;
+            readings_used += 1;
+        }
+
+        if readings_used == 0 {
+            return None;
+        }
+
+        let determinant =
+            information[0][0] * information[1][1] - information[0][1] * information[1][0];
+        let inverse = [
+            [
+                information[1][1] / determinant,
+                -information[0][1] / determinant,
+            ],
+            [
+                -information[1][0] / determinant,
+                information[0][0] / determinant,
+            ],
+        ];
+
+        let variance = self.config.measurement_noise * self.config.measurement_noise;
+        let covariance_of = |row: usize, column: usize| {
+            (variance * inverse[row][column]).clamp(-UNOBSERVED_VARIANCE, UNOBSERVED_VARIANCE)
+        };
+
+        Some(WallCorrection {
+            offset: Point2::new(
+                inverse[0][0] * target[0] + inverse[0][1] * target[1],
+                inverse[1][0] * target[0] + inverse[1][1] * target[1],
+            ),
+            covariance: [
+                [covariance_of(0, 0), covariance_of(0, 1), 0.0],
+                [covariance_of(1, 0), covariance_of(1, 1), 0.0],
+                [0.0, 0.0, UNOBSERVED_VARIANCE],
+            ],
+            readings_used,
+        })
+    }
+
+    /// Computes a correction from the localizer's current pose and fuses it into the
+    /// localizer's estimate.
+    ///
+    /// Returns the number of readings that were used.
+    pub fn correct_localizer(
+        &self,
+        localizer: &mut Localizer,
+        readings: &[DistanceReading],
+    ) -> usize {
+        let pose = localizer.pose();
+
+        match self.correction(pose, readings) {
+            Some(correction) => {
+                localizer.correct(correction.apply(pose), correction.covariance);
+                correction.readings_used
+            }
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::Rotation2d;
+
+    const WALL: WallSegment = WallSegment::new(Point2::new(1.0, -1.0), Point2::new(1.0, 1.0));
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn raycast_hits_wall_in_front() {
+        let (distance, normal) = raycast(&[WALL], Pose2d::ORIGIN).unwrap();
+
+        assert_close(distance, 1.0);
+        assert_close(normal.x, -1.0);
+        assert_close(normal.y, 0.0);
+    }
+
+    #[test]
+    fn raycast_hits_wall_at_an_angle() {
+        let pose = Pose2d::new((0.0, 0.0), Rotation2d::from_degrees(45.0));
+        let (distance, _) = raycast(&[WALL], pose).unwrap();
+
+        assert_close(distance, 2.0f64.sqrt());
+    }
+
+    #[test]
+    fn raycast_misses_past_end_of_wall() {
+        let pose = Pose2d::new((0.0, 0.0), Rotation2d::from_degrees(80.0));
+
+        assert_eq!(raycast(&[WALL], pose), None);
+    }
+
+    #[test]
+    fn raycast_misses_wall_behind() {
+        let pose = Pose2d::new((0.0, 0.0), Rotation2d::from_degrees(180.0));
+
+        assert_eq!(raycast(&[WALL], pose), None);
+    }
+
+    #[test]
+    fn raycast_ignores_parallel_wall() {
+        let pose = Pose2d::new((0.0, 0.0), Rotation2d::from_degrees(90.0));
+
+        assert_eq!(raycast(&[WALL], pose), None);
+    }
+
+    #[test]
+    fn raycast_returns_nearest_wall() {
+        let (distance, _) = raycast(
+            &WallSegment::FIELD_PERIMETER,
+            Pose2d::new((1.0, 0.0), Rotation2d::IDENTITY),
+        )
+        .unwrap();
+
+        assert_close(distance, WallSegment::HALF_FIELD - 1.0);
+    }
+
+    #[test]
+    fn perpendicular_readings_correct_both_axes() {
+        let relocalizer =
+            WallRelocalizer::new(&WallSegment::FIELD_PERIMETER, RelocalizerConfig::default());
+        let forward = Transform2d::new((0.0, 0.0), Rotation2d::IDENTITY);
+        let left = Transform2d::new((0.0, 0.0), Rotation2d::from_degrees(90.0));
+
+        // The robot is really at the origin, but odometry thinks it is at (0.03, -0.02).
+        let estimate = Pose2d::new((0.03, -0.02), Rotation2d::IDENTITY);
+        let readings = [forward, left].map(|mount| DistanceReading {
+            mount,
+            distance: relocalizer
+                .expected_distance(Pose2d::ORIGIN, mount)
+                .unwrap(),
+            confidence: 1.0,
+        });
+
+        let correction = relocalizer.correction(estimate, &readings).unwrap();
+        let corrected = correction.apply(estimate);
+
+        assert_eq!(correction.readings_used, 2);
+        assert!(corrected.position.x.abs() < 1e-6);
+        assert!(corrected.position.y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn single_reading_leaves_other_axis_unobserved() {
+        let relocalizer =
+            WallRelocalizer::new(&WallSegment::FIELD_PERIMETER, RelocalizerConfig::default());
+        let reading = DistanceReading {
+            mount: Transform2d::IDENTITY,
+            distance: WallSegment::HALF_FIELD - 0.05,
+            confidence: 1.0,
+        };
+
+        let correction = relocalizer.correction(Pose2d::ORIGIN, &[reading]).unwrap();
+
+        assert!((correction.offset.x - 0.05).abs() < 1e-6);
+        assert!(correction.offset.y.abs() < 1e-6);
+        assert!(correction.covariance[1][1] > 1.0);
+    }
+
+    #[test]
+    fn rejects_low_confidence_and_outlying_readings() {
+        let relocalizer =
+            WallRelocalizer::new(&WallSegment::FIELD_PERIMETER, RelocalizerConfig::default());
+        let readings = [
+            DistanceReading {
+                mount: Transform2d::IDENTITY,
+                distance: WallSegment::HALF_FIELD,
+                confidence: 0.1,
+            },
+            DistanceReading {
+                mount: Transform2d::IDENTITY,
+                distance: 0.5,
+                confidence: 1.0,
+            },
+        ];
+
+        assert_eq!(relocalizer.correction(Pose2d::ORIGIN, &readings), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! CSV path parser.
+
+use alloc::vec::Vec;
+
+use super::{SyntaxError, SyntaxErrorKind, Waypoint};
+
+/// A waypoint field that a CSV column can map to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    X,
+    Y,
+    Heading,
+    Velocity,
+    Ignored,
+}
+
+impl Column {
+    fn from_name(name: &[u8]) -> Self {
+        let name = trim(name);
+
+        if name.eq_ignore_ascii_case(b"x") {
+            Self::X
+        } else if name.eq_ignore_ascii_case(b"y") {
+            Self::Y
+        } else if name.eq_ignore_ascii_case(b"heading") {
+            Self::Heading
+        } else if name.eq_ignore_ascii_case(b"velocity") {
+            Self::Velocity
+        } else {
+            Self::Ignored
+        }
+    }
+}
+
+/// The column layout used when a file has no header.
+const POSITIONAL_COLUMNS: [Column; 4] = [Column::X, Column::Y, Column::Heading, Column::Velocity];
+
+fn trim(field: &[u8]) -> &[u8] {
+    let start = field
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(field.len());
+    let end = field
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+
+    &field[start..end]
+}
+
+/// Splits a line into its fields, along with the byte offset of each field into the file.
+fn fields(line: &[u8], line_offset: usize) -> impl Iterator<Item = (&[u8], usize)> {
+    let mut field_offset = line_offset;
+
+    line.split(|&byte| byte == b',').map(move |field| {
+        // Point errors at the start of the field's contents rather than any leading whitespace.
+        let leading = field
+            .iter()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        let offset = field_offset + leading;
+
+        field_offset += field.len() + 1;
+
+        (trim(field), offset)
+    })
+}
+
+fn parse_number(field: &[u8], offset: usize) -> Result<f64, SyntaxError> {
+    core::str::from_utf8(field)
+        .ok()
+        .and_then(|field| field.parse().ok())
+        .ok_or(SyntaxError::new(SyntaxErrorKind::InvalidNumber, offset))
+}
+
+pub(crate) fn parse(source: &[u8]) -> Result<Vec<Waypoint>, SyntaxError> {
+    let mut waypoints = Vec::new();
+    let mut columns: Option<Vec<Column>> = None;
+
+    let mut line_offset = 0;
+
+    for line in source.split(|&byte| byte == b'\n') {
+        let offset = line_offset;
+        line_offset += line.len() + 1;
+
+        // Handle CRLF line endings.
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if trim(line).is_empty() || trim(line).starts_with(b"#") {
+            continue;
+        }
+
+        let columns = match &columns {
+            Some(columns) => columns,
+            None => {
+                // The first row is a header if its first field isn't a number.
+                let (first_field, _) = fields(line, offset).next().unwrap_or_default();
+                let is_header = parse_number(first_field, offset).is_err();
+
+                let layout = if is_header {
+                    let layout: Vec<Column> = fields(line, offset)
+                        .map(|(name, _)| Column::from_name(name))
+                        .collect();
+
+                    if !(layout.contains(&Column::X) && layout.contains(&Column::Y)) {
+                        return Err(SyntaxError::new(SyntaxErrorKind::MissingColumn, offset));
+                    }
+
+                    layout
+                } else {
+                    POSITIONAL_COLUMNS.to_vec()
+                };
+
+                let layout = columns.insert(layout);
+
+                if is_header {
+                    continue;
+                }
+
+                layout
+            }
+        };
+
+        let mut x = None;
+        let mut y = None;
+        let mut waypoint = Waypoint::default();
+
+        for (index, (field, field_offset)) in fields(line, offset).enumerate() {
+            let Some(column) = columns.get(index) else {
+                return Err(SyntaxError::new(
+                    SyntaxErrorKind::TooManyFields,
+                    field_offset,
+                ));
+            };
+
+            match column {
+                Column::X => x = Some(parse_number(field, field_offset)?),
+                Column::Y => y = Some(parse_number(field, field_offset)?),
+                Column::Heading if !field.is_empty() => {
+                    waypoint.heading = Some(parse_number(field, field_offset)?);
+                }
+                Column::Velocity if !field.is_empty() => {
+                    waypoint.velocity = Some(parse_number(field, field_offset)?);
+                }
+                _ => {}
+            }
+        }
+
+        let (Some(x), Some(y)) = (x, y) else {
+            // Point at the end of the line, since that's where the missing fields should be.
+            return Err(SyntaxError::new(
+                SyntaxErrorKind::MissingCoordinate,
+                offset + line.len(),
+            ));
+        };
+
+        waypoint.position.x = x;
+        waypoint.position.y = y;
+        waypoints.push(waypoint);
+    }
+
+    Ok(waypoints)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse, PathError, PathFormat, SyntaxErrorKind, Waypoint};
+
+    fn error_at(source: &str) -> (SyntaxErrorKind, usize, usize) {
+        match parse(source.as_bytes(), PathFormat::Csv) {
+            Err(PathError::Syntax { kind, line, column }) => (kind, line, column),
+            other => panic!("expected a syntax error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_rows_with_header() {
+        let source = "# Exported from a path planner\n\
+                      x,y,heading,velocity\n\
+                      0.0,0.0,90,0.0\n\
+                      \n\
+                      24.0,12.5,,48.0\n";
+
+        let waypoints = parse(source.as_bytes(), PathFormat::Csv).unwrap();
+
+        assert_eq!(
+            waypoints,
+            [
+                Waypoint {
+                    heading: Some(90.0),
+                    velocity: Some(0.0),
+                    ..Waypoint::new((0.0, 0.0))
+                },
+                Waypoint {
+                    velocity: Some(48.0),
+                    ..Waypoint::new((24.0, 12.5))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn header_columns_may_be_reordered_and_ignored() {
+        let source = "time, Y ,X\n0.5,1,2\n";
+
+        let waypoints = parse(source.as_bytes(), PathFormat::Csv).unwrap();
+
+        assert_eq!(waypoints, [Waypoint::new((2.0, 1.0))]);
+    }
+
+    #[test]
+    fn parses_positional_rows_without_header() {
+        let source = "1,2\r\n3, 4, 45\r\n";
+
+        let waypoints = parse(source.as_bytes(), PathFormat::Csv).unwrap();
+
+        assert_eq!(
+            waypoints,
+            [
+                Waypoint::new((1.0, 2.0)),
+                Waypoint {
+                    heading: Some(45.0),
+                    ..Waypoint::new((3.0, 4.0))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_number() {
+        assert_eq!(
+            error_at("x,y\n1.0,2.0\n3.0, abc\n"),
+            (SyntaxErrorKind::InvalidNumber, 3, 6)
+        );
+    }
+
+    #[test]
+    fn reports_missing_column() {
+        assert_eq!(
+            error_at("# path\nx,heading\n1,2\n"),
+            (SyntaxErrorKind::MissingColumn, 2, 1)
+        );
+    }
+
+    #[test]
+    fn reports_missing_coordinate() {
+        assert_eq!(
+            error_at("1,2\n3\n"),
+            (SyntaxErrorKind::MissingCoordinate, 2, 2)
+        );
+    }
+
+    #[test]
+    fn reports_extra_fields() {
+        assert_eq!(
+            error_at("x,y\n1,2,3\n"),
+            (SyntaxErrorKind::TooManyFields, 2, 5)
+        );
+        assert_eq!(
+            error_at("1,2,3,4,5\n"),
+            (SyntaxErrorKind::TooManyFields, 1, 9)
+        );
+    }
+}
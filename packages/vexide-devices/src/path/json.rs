@@ -0,0 +1,360 @@
+//! JSON path parser.
+//!
+//! This is a minimal JSON parser that only understands enough of the format to extract
+//! waypoints. Values that aren't needed (such as unrecognized keys) are validated and skipped.
+
+use alloc::vec::Vec;
+
+use super::{SyntaxError, SyntaxErrorKind, Waypoint};
+
+struct Parser<'a> {
+    source: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    const fn new(source: &'a [u8]) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    const fn error(&self, kind: SyntaxErrorKind) -> SyntaxError {
+        SyntaxError::new(kind, self.offset)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.source.get(self.offset) {
+            self.offset += 1;
+        }
+    }
+
+    /// Returns the next non-whitespace byte without consuming it.
+    fn peek(&mut self) -> Result<u8, SyntaxError> {
+        self.skip_whitespace();
+        self.source
+            .get(self.offset)
+            .copied()
+            .ok_or(self.error(SyntaxErrorKind::UnexpectedEof))
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), SyntaxError> {
+        if self.peek()? == byte {
+            self.offset += 1;
+            Ok(())
+        } else {
+            Err(self.error(SyntaxErrorKind::UnexpectedCharacter))
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &[u8]) -> Result<(), SyntaxError> {
+        for &byte in literal {
+            match self.source.get(self.offset) {
+                Some(&next) if next == byte => self.offset += 1,
+                Some(_) => return Err(self.error(SyntaxErrorKind::UnexpectedCharacter)),
+                None => return Err(self.error(SyntaxErrorKind::UnexpectedEof)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses a comma-separated sequence of items until `end` is reached.
+    fn sequence(
+        &mut self,
+        end: u8,
+        mut item: impl FnMut(&mut Self) -> Result<(), SyntaxError>,
+    ) -> Result<(), SyntaxError> {
+        if self.peek()? == end {
+            self.offset += 1;
+            return Ok(());
+        }
+
+        loop {
+            item(self)?;
+
+            match self.peek()? {
+                b',' => self.offset += 1,
+                byte if byte == end => {
+                    self.offset += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error(SyntaxErrorKind::UnexpectedCharacter)),
+            }
+        }
+    }
+
+    /// Parses a string, returning its raw contents.
+    ///
+    /// Escape sequences are validated but not decoded, since keys are only ever compared
+    /// against plain ASCII names.
+    fn string(&mut self) -> Result<&'a [u8], SyntaxError> {
+        self.expect(b'"')?;
+        let start = self.offset;
+
+        loop {
+            match self.source.get(self.offset) {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.offset += 1;
+                    match self.source.get(self.offset) {
+                        Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {}
+                        Some(b'u') => {
+                            for _ in 0..4 {
+                                self.offset += 1;
+                                match self.source.get(self.offset) {
+                                    Some(byte) if byte.is_ascii_hexdigit() => {}
+                                    Some(_) => {
+                                        return Err(self.error(SyntaxErrorKind::InvalidString))
+                                    }
+                                    None => return Err(self.error(SyntaxErrorKind::UnexpectedEof)),
+                                }
+                            }
+                        }
+                        Some(_) => return Err(self.error(SyntaxErrorKind::InvalidString)),
+                        None => return Err(self.error(SyntaxErrorKind::UnexpectedEof)),
+                    }
+                }
+                Some(byte) if *byte < 0x20 => {
+                    return Err(self.error(SyntaxErrorKind::InvalidString));
+                }
+                Some(_) => {}
+                None => return Err(self.error(SyntaxErrorKind::UnexpectedEof)),
+            }
+
+            self.offset += 1;
+        }
+
+        let contents = &self.source[start..self.offset];
+        if core::str::from_utf8(contents).is_err() {
+            return Err(SyntaxError::new(SyntaxErrorKind::InvalidString, start));
+        }
+
+        self.offset += 1;
+        Ok(contents)
+    }
+
+    fn number(&mut self) -> Result<f64, SyntaxError> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') =
+            self.source.get(self.offset)
+        {
+            self.offset += 1;
+        }
+
+        core::str::from_utf8(&self.source[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(SyntaxError::new(SyntaxErrorKind::InvalidNumber, start))
+    }
+
+    /// Parses a number or `null`.
+    fn optional_number(&mut self) -> Result<Option<f64>, SyntaxError> {
+        if self.peek()? == b'n' {
+            self.expect_literal(b"null")?;
+            Ok(None)
+        } else {
+            self.number().map(Some)
+        }
+    }
+
+    /// Parses and discards any value.
+    fn skip_value(&mut self) -> Result<(), SyntaxError> {
+        match self.peek()? {
+            b'{' => {
+                self.offset += 1;
+                self.sequence(b'}', |parser| {
+                    parser.string()?;
+                    parser.expect(b':')?;
+                    parser.skip_value()
+                })
+            }
+            b'[' => {
+                self.offset += 1;
+                self.sequence(b']', Self::skip_value)
+            }
+            b'"' => self.string().map(|_| ()),
+            b't' => self.expect_literal(b"true"),
+            b'f' => self.expect_literal(b"false"),
+            b'n' => self.expect_literal(b"null"),
+            b'-' | b'0'..=b'9' => self.number().map(|_| ()),
+            _ => Err(self.error(SyntaxErrorKind::UnexpectedCharacter)),
+        }
+    }
+
+    fn waypoint(&mut self) -> Result<Waypoint, SyntaxError> {
+        self.expect(b'{')?;
+
+        let mut x = None;
+        let mut y = None;
+        let mut waypoint = Waypoint::default();
+
+        self.sequence(b'}', |parser| {
+            let key = parser.string()?;
+            parser.expect(b':')?;
+
+            match key {
+                b"x" => x = Some(parser.number()?),
+                b"y" => y = Some(parser.number()?),
+                b"heading" => waypoint.heading = parser.optional_number()?,
+                b"velocity" => waypoint.velocity = parser.optional_number()?,
+                _ => parser.skip_value()?,
+            }
+
+            Ok(())
+        })?;
+
+        let (Some(x), Some(y)) = (x, y) else {
+            // Point at the closing brace of the object.
+            return Err(SyntaxError::new(
+                SyntaxErrorKind::MissingCoordinate,
+                self.offset - 1,
+            ));
+        };
+
+        waypoint.position.x = x;
+        waypoint.position.y = y;
+        Ok(waypoint)
+    }
+
+    fn waypoints(&mut self) -> Result<Vec<Waypoint>, SyntaxError> {
+        let mut waypoints = Vec::new();
+
+        self.expect(b'[')?;
+        self.sequence(b']', |parser| {
+            waypoints.push(parser.waypoint()?);
+            Ok(())
+        })?;
+
+        Ok(waypoints)
+    }
+
+    /// Parses a document that is either a list of waypoints, or an object with a `waypoints` key.
+    fn document(&mut self) -> Result<Vec<Waypoint>, SyntaxError> {
+        if self.peek()? == b'[' {
+            return self.waypoints();
+        }
+
+        let start = self.offset;
+        let mut waypoints = None;
+
+        self.expect(b'{')?;
+        self.sequence(b'}', |parser| {
+            let key = parser.string()?;
+            parser.expect(b':')?;
+
+            if key == b"waypoints" {
+                waypoints = Some(parser.waypoints()?);
+                Ok(())
+            } else {
+                parser.skip_value()
+            }
+        })?;
+
+        waypoints.ok_or(SyntaxError::new(SyntaxErrorKind::MissingWaypoints, start))
+    }
+}
+
+pub(crate) fn parse(source: &[u8]) -> Result<Vec<Waypoint>, SyntaxError> {
+    let mut parser = Parser::new(source);
+    let waypoints = parser.document()?;
+
+    parser.skip_whitespace();
+    if parser.offset != source.len() {
+        return Err(parser.error(SyntaxErrorKind::TrailingData));
+    }
+
+    Ok(waypoints)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{parse, PathError, PathFormat, SyntaxErrorKind, Waypoint};
+
+    fn error_at(source: &str) -> (SyntaxErrorKind, usize, usize) {
+        match parse(source.as_bytes(), PathFormat::Json) {
+            Err(PathError::Syntax { kind, line, column }) => (kind, line, column),
+            other => panic!("expected a syntax error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_waypoints_object() {
+        let source = r#"{
+            "name": "Skills \"run\"",
+            "tags": [true, false, null, {"nested": [1, 2.5e1]}],
+            "waypoints": [
+                { "x": 0.0, "y": 0.0, "heading": 90.0, "velocity": 0.0 },
+                { "x": 24.0, "y": 12.5, "velocity": 48.0, "note": "°" },
+                { "x": -48.0, "y": 24.0, "heading": null }
+            ]
+        }"#;
+
+        let waypoints = parse(source.as_bytes(), PathFormat::Json).unwrap();
+
+        assert_eq!(
+            waypoints,
+            [
+                Waypoint {
+                    heading: Some(90.0),
+                    velocity: Some(0.0),
+                    ..Waypoint::new((0.0, 0.0))
+                },
+                Waypoint {
+                    velocity: Some(48.0),
+                    ..Waypoint::new((24.0, 12.5))
+                },
+                Waypoint::new((-48.0, 24.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_root_list() {
+        let source = br#"[{"y": 2, "x": 1}, {"x": 3, "y": 4}]"#;
+
+        let waypoints = parse(source, PathFormat::Json).unwrap();
+
+        assert_eq!(
+            waypoints,
+            [Waypoint::new((1.0, 2.0)), Waypoint::new((3.0, 4.0))]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_number() {
+        assert_eq!(
+            error_at("[\n{\"x\": 1.0, \"y\": -}]"),
+            (SyntaxErrorKind::InvalidNumber, 2, 17)
+        );
+    }
+
+    #[test]
+    fn reports_missing_coordinate() {
+        assert_eq!(
+            error_at("{\"waypoints\": [\n  {\"x\": 1.0}\n]}"),
+            (SyntaxErrorKind::MissingCoordinate, 2, 12)
+        );
+    }
+
+    #[test]
+    fn reports_truncated_document() {
+        assert_eq!(
+            error_at("[\n  {\"x\": 1.0, \"y\": 2.0},\n  {\"x\": 3.0,"),
+            (SyntaxErrorKind::UnexpectedEof, 3, 13)
+        );
+    }
+
+    #[test]
+    fn reports_missing_waypoints() {
+        assert_eq!(
+            error_at("\n  {\"name\": \"Skills\"}"),
+            (SyntaxErrorKind::MissingWaypoints, 2, 3)
+        );
+    }
+
+    #[test]
+    fn reports_trailing_data() {
+        assert_eq!(error_at("[] x"), (SyntaxErrorKind::TrailingData, 1, 4));
+    }
+}
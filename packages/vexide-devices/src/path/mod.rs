@@ -0,0 +1,267 @@
+//! Path file import.
+//!
+//! This module parses waypoint lists exported by external path planning tools. Paths can be
+//! loaded from bytes embedded in the program (for example using [`include_bytes!`]) or from a
+//! file stored on the brain's SD card.
+//!
+//! Two formats are supported, [`PathFormat::Csv`] and [`PathFormat::Json`]. Both formats produce
+//! a list of [`Waypoint`]s. The exact schema for each format is documented on its variant.
+//!
+//! Errors in malformed files are reported with the line and column where parsing failed, so that
+//! problems can be tracked down in the exporting tool.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use vexide_devices::path::{self, PathFormat};
+//!
+//! // Embed a path into the program binary.
+//! let waypoints = path::parse(include_bytes!("skills.csv"), PathFormat::Csv)?;
+//!
+//! // Or load it from the SD card at runtime.
+//! let waypoints = path::load("skills.json")?;
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use snafu::Snafu;
+use vexide_core::{fs, io};
+
+use crate::geometry::Point2;
+
+mod csv;
+mod json;
+
+/// A single point along a path.
+///
+/// Waypoints are stored in whatever units the path was exported in. vexide does not
+/// convert between units when parsing path files.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Waypoint {
+    /// The position of the waypoint on the field.
+    pub position: Point2<f64>,
+
+    /// The desired heading of the robot at this waypoint in degrees, or `None` if the
+    /// path does not specify one.
+    ///
+    /// Headings follow the same convention as [`GpsSensor::pose`](crate::smart::GpsSensor::pose),
+    /// increasing as the robot turns counterclockwise.
+    pub heading: Option<f64>,
+
+    /// The desired velocity of the robot at this waypoint, or `None` if the path does not
+    /// specify one.
+    pub velocity: Option<f64>,
+}
+
+impl Waypoint {
+    /// Creates a new waypoint at a position with no heading or velocity constraints.
+    pub fn new(position: impl Into<Point2<f64>>) -> Self {
+        Self {
+            position: position.into(),
+            heading: None,
+            velocity: None,
+        }
+    }
+}
+
+/// A file format that a path can be stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathFormat {
+    /// Comma-separated values with one waypoint per line.
+    ///
+    /// The file may optionally start with a header line naming its columns. Recognized column
+    /// names are `x`, `y`, `heading` and `velocity` (case-insensitive), and they may appear in
+    /// any order. Columns with any other name are ignored. The `x` and `y` columns are required.
+    ///
+    /// Without a header, columns are read positionally as `x,y[,heading[,velocity]]`. A row
+    /// with more fields than there are columns is an error.
+    ///
+    /// Blank lines and lines starting with `#` are skipped. Empty `heading` and `velocity`
+    /// fields are treated as unspecified.
+    ///
+    /// ```text
+    /// # Exported from a path planner
+    /// x,y,heading,velocity
+    /// 0.0,0.0,90,0.0
+    /// 24.0,12.5,,48.0
+    /// 48.0,24.0,45,0.0
+    /// ```
+    Csv,
+
+    /// A JSON document containing a list of waypoint objects.
+    ///
+    /// The list may either be the root of the document, or stored under a `"waypoints"` key of
+    /// the root object. Each waypoint object must have numeric `"x"` and `"y"` keys, and may
+    /// optionally have numeric `"heading"` and `"velocity"` keys (which may also be `null`).
+    /// Any other keys are ignored.
+    ///
+    /// ```json
+    /// {
+    ///     "name": "Skills",
+    ///     "waypoints": [
+    ///         { "x": 0.0, "y": 0.0, "heading": 90.0, "velocity": 0.0 },
+    ///         { "x": 24.0, "y": 12.5, "velocity": 48.0 },
+    ///         { "x": 48.0, "y": 24.0, "heading": 45.0, "velocity": null }
+    ///     ]
+    /// }
+    /// ```
+    Json,
+}
+
+impl PathFormat {
+    /// Guesses the format of a file based on its extension (`.csv` or `.json`).
+    ///
+    /// Returns `None` if the extension is not recognized.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, extension) = path.rsplit_once('.')?;
+
+        if extension.eq_ignore_ascii_case("csv") {
+            Some(Self::Csv)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses a list of waypoints from the contents of a path file.
+pub fn parse(source: &[u8], format: PathFormat) -> Result<Vec<Waypoint>, PathError> {
+    let result = match format {
+        PathFormat::Csv => csv::parse(source),
+        PathFormat::Json => json::parse(source),
+    };
+
+    result.map_err(|error| {
+        let (line, column) = error.location(source);
+
+        PathError::Syntax {
+            kind: error.kind,
+            line,
+            column,
+        }
+    })
+}
+
+/// Loads a list of waypoints from a file on the SD card.
+///
+/// The format of the file is determined by its extension using [`PathFormat::from_path`].
+pub fn load(path: &str) -> Result<Vec<Waypoint>, PathError> {
+    let format = PathFormat::from_path(path).ok_or(PathError::UnknownFormat)?;
+
+    load_with_format(path, format)
+}
+
+/// Loads a list of waypoints in a specific format from a file on the SD card.
+pub fn load_with_format(path: &str, format: PathFormat) -> Result<Vec<Waypoint>, PathError> {
+    let source = fs::read(path).map_err(|error| PathError::Io { error })?;
+
+    parse(&source, format)
+}
+
+/// A syntax error found at a specific byte offset into a path file.
+pub(crate) struct SyntaxError {
+    kind: SyntaxErrorKind,
+    offset: usize,
+}
+
+impl SyntaxError {
+    pub(crate) const fn new(kind: SyntaxErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+
+    /// Computes the 1-indexed line and column that this error occurred at.
+    fn location(&self, source: &[u8]) -> (usize, usize) {
+        let preceding = &source[..self.offset.min(source.len())];
+        let line_start = preceding
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        let line = preceding.iter().filter(|&&byte| byte == b'\n').count() + 1;
+
+        // Columns are counted in characters rather than bytes, so skip UTF-8 continuation bytes.
+        let column = preceding[line_start..]
+            .iter()
+            .filter(|&&byte| byte & 0xC0 != 0x80)
+            .count()
+            + 1;
+
+        (line, column)
+    }
+}
+
+/// The reason that a path file failed to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxErrorKind {
+    /// The file ended before a value was complete.
+    UnexpectedEof,
+
+    /// An unexpected character was encountered.
+    UnexpectedCharacter,
+
+    /// A value that should have been a number could not be parsed as one.
+    InvalidNumber,
+
+    /// A string contained an invalid escape sequence or was not valid UTF-8.
+    InvalidString,
+
+    /// A waypoint was missing its `x` or `y` coordinate.
+    MissingCoordinate,
+
+    /// A CSV header did not contain both an `x` and a `y` column.
+    MissingColumn,
+
+    /// A CSV row had more fields than there are columns.
+    TooManyFields,
+
+    /// A JSON document did not contain a list of waypoints.
+    MissingWaypoints,
+
+    /// Additional data was found after the end of a JSON document.
+    TrailingData,
+}
+
+impl fmt::Display for SyntaxErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnexpectedEof => "unexpected end of file",
+            Self::UnexpectedCharacter => "unexpected character",
+            Self::InvalidNumber => "invalid number",
+            Self::InvalidString => "invalid string",
+            Self::MissingCoordinate => "waypoint is missing an x or y coordinate",
+            Self::MissingColumn => "header is missing an x or y column",
+            Self::TooManyFields => "row has more fields than there are columns",
+            Self::MissingWaypoints => "document does not contain a list of waypoints",
+            Self::TrailingData => "trailing data after end of document",
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when loading a path file.
+pub enum PathError {
+    /// The path file is malformed.
+    #[snafu(display("{kind} at line {line}, column {column}"))]
+    Syntax {
+        /// The reason that parsing failed.
+        kind: SyntaxErrorKind,
+
+        /// The line (starting at 1) where parsing failed.
+        line: usize,
+
+        /// The column (starting at 1) where parsing failed.
+        column: usize,
+    },
+
+    /// The format of the file could not be determined from its extension.
+    UnknownFormat,
+
+    /// The file could not be read from the SD card.
+    #[snafu(display("{error}"))]
+    Io {
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+}
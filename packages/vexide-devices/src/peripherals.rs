@@ -21,14 +21,30 @@
 //! let motor = peripherals.take_smart_port(1).unwrap();
 //! let adi_digital_in = peripherals.take_adi_port(4).unwrap();
 //! ```
+//! ### Discovering devices with [`DynamicPeripherals`]
+//! ```rust
+//! # use vexide::prelude::*;
+//! let mut peripherals = DynamicPeripherals::new(Peripherals::take().unwrap());
+//! let drive_motors = peripherals.take_all::<Motor>();
+//! let imu = peripherals.find_first::<InertialSensor>().unwrap();
+//! ```
 
-use core::sync::atomic::AtomicBool;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
 
 use crate::{
     adi::AdiPort,
     controller::{Controller, ControllerId},
     screen::Screen,
-    smart::SmartPort,
+    smart::{
+        motor::{Direction, Gearset},
+        AdiExpander, AiVisionSensor, Arm, DistanceSensor, Electromagnet, InertialSensor,
+        LightTower, Motor, OpticalSensor, RotationSensor, SmartDevice, SmartDeviceType, SmartPort,
+        VisionSensor,
+    },
 };
 
 static PERIPHERALS_TAKEN: AtomicBool = AtomicBool::new(false);
@@ -185,10 +201,18 @@ impl Peripherals {
 /// Guarentees that ports are only used once **at runtime**
 /// This is useful for when you want to store a peripherals struct for use in multiple functions.
 /// When possible, use [`Peripherals`] instead.
+///
+/// # Discovery
+///
+/// Dynamic peripherals can also find devices by what is plugged in, rather than by port
+/// number. [`DynamicPeripherals::smart_ports`] lists the type of device detected on every
+/// smart port, and [`DynamicPeripherals::take_all`] and [`DynamicPeripherals::find_first`]
+/// create [`Discoverable`] devices on every matching port. Devices created this way are
+/// wrapped in a [`PooledDevice`], which returns the port to the pool when it is dropped.
 #[derive(Debug)]
 pub struct DynamicPeripherals {
     screen: bool,
-    smart_ports: [bool; 21],
+    smart_ports: Arc<AtomicU32>,
     adi_slots: [bool; 8],
 }
 impl DynamicPeripherals {
@@ -198,11 +222,10 @@ impl DynamicPeripherals {
     /// This guarentees safety because [`Peripherals`] cannot be passed by value
     /// after they have been used to create devices.
     pub fn new(_peripherals: Peripherals) -> Self {
-        let smart_ports = [false; 21];
         let adi_slots = [false; 8];
         Self {
             screen: false,
-            smart_ports,
+            smart_ports: Arc::new(AtomicU32::new(0)),
             adi_slots,
         }
     }
@@ -214,12 +237,80 @@ impl DynamicPeripherals {
     /// This function panics if the provided port is outside the range 1-21.
     /// Ports outside of this range are invalid and cannot be created.
     pub fn take_smart_port(&mut self, port_index: u8) -> Option<SmartPort> {
-        let port_index = port_index as usize - 1;
-        if self.smart_ports[port_index] {
+        let bit = smart_port_bit(port_index);
+        if self.smart_ports.fetch_or(bit, Ordering::AcqRel) & bit != 0 {
             return None;
         };
-        self.smart_ports[port_index] = true;
-        Some(unsafe { SmartPort::new(port_index as u8 + 1) })
+        Some(unsafe { SmartPort::new(port_index) })
+    }
+
+    /// Returns a [`SmartPort`] to the pool, allowing it to be taken again.
+    ///
+    /// Any smart device can be converted back into its port with [`Into`], so this can be used
+    /// to free the port of a device that is no longer needed.
+    pub fn return_smart_port(&mut self, port: SmartPort) {
+        self.smart_ports
+            .fetch_and(!smart_port_bit(port.index()), Ordering::AcqRel);
+    }
+
+    /// Returns `true` if the given smart port has already been taken.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided port is outside the range 1-21.
+    pub fn is_smart_port_taken(&self, port_index: u8) -> bool {
+        self.smart_ports.load(Ordering::Acquire) & smart_port_bit(port_index) != 0
+    }
+
+    /// Lists every smart port along with the type of device currently plugged into it.
+    pub fn smart_ports(&self) -> impl Iterator<Item = PortInfo> + '_ {
+        (1..=21).map(|port| PortInfo {
+            port,
+            // SAFETY: The port is only used to read the type of the connected device.
+            device_type: unsafe { SmartPort::new(port) }
+                .device_type()
+                .unwrap_or(SmartDeviceType::None),
+            taken: self.is_smart_port_taken(port),
+        })
+    }
+
+    /// Creates a device on every available smart port that has a device of type `T` plugged
+    /// in, in order of port number.
+    ///
+    /// Ports that have already been taken are skipped.
+    pub fn take_all<T: Discoverable>(&mut self) -> Vec<PooledDevice<T>> {
+        let ports: Vec<u8> = self
+            .smart_ports()
+            .filter(|info| !info.taken && info.device_type == T::DEVICE_TYPE)
+            .map(|info| info.port)
+            .collect();
+
+        ports
+            .into_iter()
+            .filter_map(|port| self.take_pooled(port))
+            .collect()
+    }
+
+    /// Creates a device on the lowest-numbered available smart port that has a device of type
+    /// `T` plugged in.
+    ///
+    /// Ports that have already been taken are skipped.
+    pub fn find_first<T: Discoverable>(&mut self) -> Option<PooledDevice<T>> {
+        let port = self
+            .smart_ports()
+            .find(|info| !info.taken && info.device_type == T::DEVICE_TYPE)?
+            .port;
+
+        self.take_pooled(port)
+    }
+
+    fn take_pooled<T: Discoverable>(&mut self, port: u8) -> Option<PooledDevice<T>> {
+        let device = T::from_port(self.take_smart_port(port)?);
+
+        Some(PooledDevice {
+            device: Box::new(device),
+            pool: self.smart_ports.clone(),
+        })
     }
 
     /// Creates an [`AdiPort`] only if one has not been created on the given slot before.
@@ -233,7 +324,7 @@ impl DynamicPeripherals {
         if self.adi_slots[port_index] {
             return None;
         }
-        self.smart_ports[port_index] = true;
+        self.adi_slots[port_index] = true;
         Some(unsafe { AdiPort::new(port_index as u8 + 1, None) })
     }
 
@@ -246,8 +337,230 @@ impl DynamicPeripherals {
         Some(unsafe { Screen::new() })
     }
 }
+
+/// Returns the bit representing a smart port in [`DynamicPeripherals`]'s pool.
+fn smart_port_bit(port_index: u8) -> u32 {
+    assert!(
+        (1..=21).contains(&port_index),
+        "smart port {port_index} is outside the range 1-21"
+    );
+
+    1 << (port_index - 1)
+}
+
+/// A smart port listed by [`DynamicPeripherals::smart_ports`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortInfo {
+    /// The index of the port.
+    pub port: u8,
+
+    /// The type of device plugged into the port, or [`SmartDeviceType::None`] if the port is
+    /// empty.
+    pub device_type: SmartDeviceType,
+
+    /// Whether the port has already been taken from the [`DynamicPeripherals`].
+    pub taken: bool,
+}
+
+/// A smart device that can be created automatically by [`DynamicPeripherals`].
+///
+/// Devices that need more than a port to be created use sensible defaults, which are listed
+/// on each implementation.
+pub trait Discoverable: SmartDevice + Sized {
+    /// The type of device reported by a port with this device plugged in.
+    const DEVICE_TYPE: SmartDeviceType;
+
+    /// Creates the device on a port.
+    fn from_port(port: SmartPort) -> Self;
+}
+
+macro_rules! impl_discoverable {
+    ($($device:ty => $device_type:ident),* $(,)?) => {
+        $(
+            impl Discoverable for $device {
+                const DEVICE_TYPE: SmartDeviceType = SmartDeviceType::$device_type;
+
+                fn from_port(port: SmartPort) -> Self {
+                    Self::new(port)
+                }
+            }
+        )*
+    };
+}
+
+impl_discoverable! {
+    AdiExpander => Adi,
+    AiVisionSensor => AiVision,
+    Arm => Arm,
+    DistanceSensor => Distance,
+    Electromagnet => Magnet,
+    InertialSensor => Imu,
+    LightTower => LightTower,
+    OpticalSensor => Optical,
+    VisionSensor => Vision,
+}
+
+/// Motors are created with a [`Gearset::Green`] cartridge in the [`Direction::Forward`]
+/// direction, since the cartridge can't be detected. Both can be changed after the motor is
+/// created.
+impl Discoverable for Motor {
+    const DEVICE_TYPE: SmartDeviceType = SmartDeviceType::Motor;
+
+    fn from_port(port: SmartPort) -> Self {
+        Self::new(port, Gearset::Green, Direction::Forward)
+    }
+}
+
+/// Rotation sensors are created in the [`Direction::Forward`] direction.
+impl Discoverable for RotationSensor {
+    const DEVICE_TYPE: SmartDeviceType = SmartDeviceType::Rotation;
+
+    fn from_port(port: SmartPort) -> Self {
+        Self::new(port, Direction::Forward)
+    }
+}
+
+/// A device created by [`DynamicPeripherals`] that returns its port to the pool when dropped.
+///
+/// This dereferences to the device, so it can be used like the device itself.
+#[derive(Debug)]
+pub struct PooledDevice<T: SmartDevice> {
+    device: Box<T>,
+    pool: Arc<AtomicU32>,
+}
+
+impl<T: SmartDevice> Deref for PooledDevice<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.device
+    }
+}
+
+impl<T: SmartDevice> DerefMut for PooledDevice<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.device
+    }
+}
+
+impl<T: SmartDevice> Drop for PooledDevice<T> {
+    fn drop(&mut self) {
+        self.pool
+            .fetch_and(!smart_port_bit(self.device.port_index()), Ordering::AcqRel);
+    }
+}
 impl From<Peripherals> for DynamicPeripherals {
     fn from(peripherals: Peripherals) -> Self {
         Self::new(peripherals)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A device that never touches the hardware.
+    #[derive(Debug)]
+    struct FakeDevice {
+        port: SmartPort,
+    }
+
+    impl SmartDevice for FakeDevice {
+        fn port_index(&self) -> u8 {
+            self.port.index()
+        }
+
+        fn device_type(&self) -> SmartDeviceType {
+            Self::DEVICE_TYPE
+        }
+    }
+
+    impl Discoverable for FakeDevice {
+        const DEVICE_TYPE: SmartDeviceType = SmartDeviceType::Distance;
+
+        fn from_port(port: SmartPort) -> Self {
+            Self { port }
+        }
+    }
+
+    fn peripherals() -> DynamicPeripherals {
+        DynamicPeripherals::new(unsafe { Peripherals::steal() })
+    }
+
+    #[test]
+    fn smart_ports_can_only_be_taken_once() {
+        let mut peripherals = peripherals();
+
+        assert!(!peripherals.is_smart_port_taken(5));
+        let port = peripherals.take_smart_port(5).unwrap();
+        assert_eq!(port.index(), 5);
+        assert!(peripherals.is_smart_port_taken(5));
+        assert!(peripherals.take_smart_port(5).is_none());
+
+        assert!(!peripherals.is_smart_port_taken(4));
+        assert!(!peripherals.is_smart_port_taken(6));
+        assert!(peripherals.take_smart_port(21).is_some());
+        assert!(peripherals.is_smart_port_taken(21));
+    }
+
+    #[test]
+    fn returned_smart_port_can_be_taken_again() {
+        let mut peripherals = peripherals();
+        let port = peripherals.take_smart_port(3).unwrap();
+        peripherals.take_smart_port(4).unwrap();
+
+        peripherals.return_smart_port(port);
+
+        assert!(!peripherals.is_smart_port_taken(3));
+        assert!(peripherals.is_smart_port_taken(4));
+        assert!(peripherals.take_smart_port(3).is_some());
+    }
+
+    #[test]
+    fn pooled_device_returns_port_when_dropped() {
+        let mut peripherals = peripherals();
+
+        let device = peripherals.take_pooled::<FakeDevice>(7).unwrap();
+        assert_eq!(device.port_index(), 7);
+        assert!(peripherals.is_smart_port_taken(7));
+        assert!(peripherals.take_pooled::<FakeDevice>(7).is_none());
+        assert!(peripherals.take_smart_port(7).is_none());
+
+        drop(device);
+
+        assert!(!peripherals.is_smart_port_taken(7));
+        assert!(peripherals.take_pooled::<FakeDevice>(7).is_some());
+    }
+
+    #[test]
+    fn pooled_device_outlives_peripherals() {
+        let mut peripherals = peripherals();
+        let device = peripherals.take_pooled::<FakeDevice>(2).unwrap();
+        let pool = peripherals.smart_ports.clone();
+
+        drop(peripherals);
+        assert_eq!(pool.load(Ordering::Acquire), smart_port_bit(2));
+
+        drop(device);
+        assert_eq!(pool.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    #[should_panic = "outside the range 1-21"]
+    fn invalid_smart_port_panics() {
+        peripherals().is_smart_port_taken(22);
+    }
+
+    #[test]
+    fn take_adi_port_leaves_smart_port_available() {
+        let mut peripherals = DynamicPeripherals::new(unsafe { Peripherals::steal() });
+
+        assert!(peripherals.take_adi_port(1).is_some());
+        assert!(peripherals.take_adi_port(1).is_none());
+
+        // Taking an ADI port used to mark the smart port with the same number as taken.
+        assert!(peripherals.take_smart_port(1).is_some());
+        assert!(peripherals.take_smart_port(1).is_none());
+        assert!(peripherals.take_adi_port(2).is_some());
+    }
+}
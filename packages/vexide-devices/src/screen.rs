@@ -16,6 +16,7 @@ use vex_sdk::{
     vexDisplayStringHeightGet, vexDisplayStringWidthGet, vexTouchDataGet, V5_TouchEvent,
     V5_TouchStatus,
 };
+use vexide_core::competition::{self, MatchScript, PracticeMatchError};
 
 use crate::{color::IntoRgb, geometry::Point2};
 
@@ -648,6 +649,109 @@ impl Screen {
     }
 }
 
+/// Starts and stops practice matches when the brain's screen is tapped.
+///
+/// Each tap starts a practice match playing a [`MatchScript`] with
+/// [`competition::start_practice_match`], or stops the running one with
+/// [`competition::stop_practice_match`]. Taps can be limited to a region of the screen with
+/// [`PracticeMatchButton::within`], so that the rest of the screen stays free for other uses.
+///
+/// Taps are detected when [`PracticeMatchButton::update`] is called, so it should be called
+/// regularly, such as from a loop in the disabled or driver control periods.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut button = PracticeMatchButton::new(MatchScript::from_durations(
+///     MatchDurations::MATCH,
+///     Duration::from_secs(3),
+/// ))
+/// .within(Rect::new((380, 0), (480, 60)));
+///
+/// loop {
+///     if let Err(err) = button.update(&peripherals.screen) {
+///         println!("Can't start a practice match: {err}");
+///     }
+///
+///     sleep(Duration::from_millis(20)).await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PracticeMatchButton {
+    script: MatchScript,
+    region: Option<Rect>,
+    last_press_count: Option<i32>,
+    running: bool,
+}
+
+impl PracticeMatchButton {
+    /// Creates a button that plays `script` when anywhere on the screen is tapped.
+    pub const fn new(script: MatchScript) -> Self {
+        Self {
+            script,
+            region: None,
+            last_press_count: None,
+            running: false,
+        }
+    }
+
+    /// Returns the same button, only responding to taps inside `region`.
+    pub const fn within(mut self, region: Rect) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Returns `true` if a practice match started by this button is running.
+    pub const fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Checks the screen for a new tap, and starts or stops a practice match if there was one.
+    ///
+    /// Taps made before the first call are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PracticeMatchError::CompetitionConnected`] if the screen was tapped to start a
+    /// practice match while the robot is connected to a competition switch or field
+    /// controller.
+    pub fn update(&mut self, screen: &Screen) -> Result<(), PracticeMatchError> {
+        if !self.observe(&screen.touch_status()) {
+            return Ok(());
+        }
+
+        if self.running {
+            competition::stop_practice_match();
+            self.running = false;
+        } else {
+            competition::start_practice_match(self.script.clone())?;
+            self.running = true;
+        }
+
+        Ok(())
+    }
+
+    /// Records a touch reading, returning `true` if it is a new tap on the button.
+    fn observe(&mut self, touch: &TouchEvent) -> bool {
+        let last_press_count = self.last_press_count.replace(touch.press_count);
+        let pressed = last_press_count.is_some_and(|count| count != touch.press_count);
+
+        pressed
+            && self.region.map_or(true, |region| {
+                let (left, right) = (
+                    region.start.x.min(region.end.x),
+                    region.start.x.max(region.end.x),
+                );
+                let (top, bottom) = (
+                    region.start.y.min(region.end.y),
+                    region.start.y.max(region.end.y),
+                );
+
+                (left..=right).contains(&touch.x) && (top..=bottom).contains(&touch.y)
+            })
+    }
+}
+
 #[derive(Debug, Snafu)]
 /// Errors that can occur when interacting with the screen.
 pub enum ScreenError {
@@ -659,3 +763,39 @@ pub enum ScreenError {
         expected_size: usize,
     },
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const fn tap(x: i16, y: i16, press_count: i32) -> TouchEvent {
+        TouchEvent {
+            state: TouchState::Pressed,
+            x,
+            y,
+            press_count,
+            release_count: press_count - 1,
+        }
+    }
+
+    #[test]
+    fn practice_button_ignores_taps_before_first_update() {
+        let mut button = PracticeMatchButton::new(MatchScript::new());
+
+        assert!(!button.observe(&tap(10, 10, 4)));
+        assert!(!button.observe(&tap(10, 10, 4)));
+        assert!(button.observe(&tap(10, 10, 5)));
+        assert!(!button.observe(&tap(10, 10, 5)));
+    }
+
+    #[test]
+    fn practice_button_only_responds_inside_region() {
+        let mut button =
+            PracticeMatchButton::new(MatchScript::new()).within(Rect::new((100, 50), (0, 0)));
+
+        button.observe(&tap(0, 0, 0));
+        assert!(!button.observe(&tap(200, 20, 1)));
+        assert!(button.observe(&tap(100, 50, 2)));
+        assert!(button.observe(&tap(40, 0, 3)));
+    }
+}
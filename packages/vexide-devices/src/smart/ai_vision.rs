@@ -0,0 +1,562 @@
+//! AI Vision sensor device module.
+//!
+//! This module provides an interface for interacting with the VEX AI Vision Sensor.
+//!
+//! # Hardware Overview
+//!
+//! The AI Vision Sensor is a camera with an onboard processor that can detect objects in three
+//! ways at once:
+//!
+//! - By color, using up to seven [`AiVisionColor`]s. Colors can also be grouped into
+//!   [`AiVisionCode`]s to detect objects made of several colors next to each other.
+//! - By AprilTag, reporting the ID and the four corners of each tag it sees.
+//! - Using an onboard machine learning model trained to classify game objects.
+//!
+//! Which of these are active is controlled by the sensor's [`AiVisionDetectionMode`].
+//! Detected objects are returned by [`AiVisionSensor::objects`].
+//!
+//! Like the original Vision Sensor, the AI Vision Sensor's color and code configuration is
+//! stored in *volatile* memory, so it should be set every time the program starts.
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use bitflags::bitflags;
+use snafu::Snafu;
+use vex_sdk::{
+    vexDeviceAiVisionClassNameGet, vexDeviceAiVisionCodeGet, vexDeviceAiVisionCodeSet,
+    vexDeviceAiVisionColorGet, vexDeviceAiVisionColorSet, vexDeviceAiVisionModeGet,
+    vexDeviceAiVisionModeSet, vexDeviceAiVisionObjectCountGet, vexDeviceAiVisionObjectGet,
+    vexDeviceAiVisionSensorSet, vexDeviceAiVisionTemperatureGet, V5_DeviceAiVisionCode,
+    V5_DeviceAiVisionColor, V5_DeviceAiVisionObject, V5_DeviceT,
+};
+
+use super::{SmartDevice, SmartDeviceType, SmartPort};
+use crate::{color::Rgb, geometry::Point2, PortError};
+
+/// The object type reported for objects detected by color.
+const OBJECT_TYPE_COLOR: u8 = 1 << 0;
+
+/// The object type reported for objects detected by a color code.
+const OBJECT_TYPE_CODE: u8 = 1 << 1;
+
+/// The object type reported for objects detected by the onboard model.
+const OBJECT_TYPE_MODEL: u8 = 1 << 2;
+
+/// The object type reported for AprilTags.
+const OBJECT_TYPE_TAG: u8 = 1 << 3;
+
+/// VEX AI Vision Sensor
+///
+/// This struct represents an AI vision sensor plugged into a smart port.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AiVisionSensor {
+    port: SmartPort,
+    device: V5_DeviceT,
+}
+
+// SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
+// SDK each device function. Simply sharing a raw pointer across threads is not inherently unsafe.
+unsafe impl Send for AiVisionSensor {}
+unsafe impl Sync for AiVisionSensor {}
+
+impl AiVisionSensor {
+    /// The horizontal resolution of the AI vision sensor.
+    pub const HORIZONTAL_RESOLUTION: u16 = 320;
+
+    /// The vertical resolution of the AI vision sensor.
+    pub const VERTICAL_RESOLUTION: u16 = 240;
+
+    /// The maximum number of colors that can be stored on the sensor.
+    pub const MAX_COLORS: u8 = 7;
+
+    /// The maximum number of color codes that can be stored on the sensor.
+    pub const MAX_CODES: u8 = 8;
+
+    /// Bit that must be set for the sensor to accept a new detection mode.
+    const MODE_MAGIC_BIT: u32 = 1 << 29;
+
+    /// The bits of the sensor's mode that select which detection methods are enabled.
+    const DETECTION_MODE_MASK: u32 = 0xFF;
+
+    /// Creates a new AI vision sensor on a smart port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Register an AI vision sensor on port 1.
+    /// let mut sensor = AiVisionSensor::new(peripherals.port_1);
+    /// ```
+    pub fn new(port: SmartPort) -> Self {
+        Self {
+            device: unsafe { port.device_handle() },
+            port,
+        }
+    }
+
+    /// Stores a color on the sensor. Objects of this color will be returned by
+    /// [`AiVisionSensor::objects`] while [`AiVisionDetectionMode::COLOR`] is enabled.
+    ///
+    /// The sensor can store up to 7 colors, with each color slot denoted by `id` (from 1 to 7).
+    /// If a color with the same ID is already stored, it is overwritten.
+    ///
+    /// # Volatile Memory
+    ///
+    /// The memory on the AI Vision Sensor is *volatile* and will therefore be wiped when the
+    /// sensor loses power. As a result, this function should be called every time the sensor is
+    /// used on program start.
+    pub fn set_color(&mut self, id: u8, color: AiVisionColor) -> Result<(), AiVisionError> {
+        self.validate_port()?;
+        validate_id(id, Self::MAX_COLORS)?;
+
+        let mut raw = V5_DeviceAiVisionColor {
+            id,
+            red: color.rgb.r,
+            grn: color.rgb.g,
+            blu: color.rgb.b,
+            hangle: color.hue_range,
+            hdsat: color.saturation_range,
+            reserved: 0,
+        };
+
+        unsafe { vexDeviceAiVisionColorSet(self.device, &mut raw) };
+
+        Ok(())
+    }
+
+    /// Gets a color stored on the sensor, or `None` if no color is stored with that ID.
+    pub fn color(&self, id: u8) -> Result<Option<AiVisionColor>, AiVisionError> {
+        self.validate_port()?;
+        validate_id(id, Self::MAX_COLORS)?;
+
+        let mut raw = V5_DeviceAiVisionColor::default();
+        if !unsafe { vexDeviceAiVisionColorGet(self.device, id as u32, &mut raw) } {
+            return Ok(None);
+        }
+
+        Ok(Some(AiVisionColor {
+            rgb: Rgb::new(raw.red, raw.grn, raw.blu),
+            hue_range: raw.hangle,
+            saturation_range: raw.hdsat,
+        }))
+    }
+
+    /// Gets every color stored on the sensor.
+    pub fn colors(&self) -> Result<[Option<AiVisionColor>; 7], AiVisionError> {
+        Ok([
+            self.color(1)?,
+            self.color(2)?,
+            self.color(3)?,
+            self.color(4)?,
+            self.color(5)?,
+            self.color(6)?,
+            self.color(7)?,
+        ])
+    }
+
+    /// Stores a color code on the sensor. Objects made of the code's colors next to each other
+    /// will be returned by [`AiVisionSensor::objects`] while [`AiVisionDetectionMode::COLOR`] is
+    /// enabled.
+    ///
+    /// The sensor can store up to 8 codes, with each code slot denoted by `id` (from 1 to 8).
+    /// If a code with the same ID is already stored, it is overwritten.
+    ///
+    /// # Volatile Memory
+    ///
+    /// The memory on the AI Vision Sensor is *volatile* and will therefore be wiped when the
+    /// sensor loses power. As a result, this function should be called every time the sensor is
+    /// used on program start.
+    pub fn set_code(&mut self, id: u8, code: AiVisionCode) -> Result<(), AiVisionError> {
+        self.validate_port()?;
+        validate_id(id, Self::MAX_CODES)?;
+
+        let colors = code.colors.map(i16::from);
+        let mut raw = V5_DeviceAiVisionCode {
+            id,
+            len: code.len,
+            c1: colors[0],
+            c2: colors[1],
+            c3: colors[2],
+            c4: colors[3],
+            c5: colors[4],
+            c6: colors[5],
+            c7: colors[6],
+        };
+
+        unsafe { vexDeviceAiVisionCodeSet(self.device, &mut raw) };
+
+        Ok(())
+    }
+
+    /// Gets a color code stored on the sensor, or `None` if no code is stored with that ID.
+    pub fn code(&self, id: u8) -> Result<Option<AiVisionCode>, AiVisionError> {
+        self.validate_port()?;
+        validate_id(id, Self::MAX_CODES)?;
+
+        let mut raw = V5_DeviceAiVisionCode::default();
+        if !unsafe { vexDeviceAiVisionCodeGet(self.device, id as u32, &mut raw) } {
+            return Ok(None);
+        }
+
+        let colors = [raw.c1, raw.c2, raw.c3, raw.c4, raw.c5, raw.c6, raw.c7];
+        let len = (raw.len as usize).min(colors.len());
+
+        Ok(AiVisionCode::new(
+            &colors[..len]
+                .iter()
+                .map(|&color| color as u8)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    /// Sets which detection methods the sensor uses.
+    pub fn set_detection_mode(&mut self, mode: AiVisionDetectionMode) -> Result<(), AiVisionError> {
+        self.validate_port()?;
+
+        let current = unsafe { vexDeviceAiVisionModeGet(self.device) };
+        let new = (current & !Self::DETECTION_MODE_MASK) | mode.bits() as u32;
+
+        unsafe { vexDeviceAiVisionModeSet(self.device, new | Self::MODE_MAGIC_BIT) };
+
+        Ok(())
+    }
+
+    /// Gets which detection methods the sensor is currently using.
+    pub fn detection_mode(&self) -> Result<AiVisionDetectionMode, AiVisionError> {
+        self.validate_port()?;
+
+        // Read from the same register that `set_detection_mode` writes, so the two round-trip.
+        let mode = unsafe { vexDeviceAiVisionModeGet(self.device) };
+
+        Ok(AiVisionDetectionMode::from_bits_truncate(
+            (mode & Self::DETECTION_MODE_MASK) as u8,
+        ))
+    }
+
+    /// Sets the brightness and contrast of the camera.
+    ///
+    /// Both values are from `0.0` to `1.0`.
+    pub fn set_brightness_and_contrast(
+        &mut self,
+        brightness: f64,
+        contrast: f64,
+    ) -> Result<(), AiVisionError> {
+        self.validate_port()?;
+
+        unsafe {
+            vexDeviceAiVisionSensorSet(
+                self.device,
+                brightness.clamp(0.0, 1.0) * 100.0,
+                contrast.clamp(0.0, 1.0) * 100.0,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the temperature of the sensor in degrees Celsius.
+    pub fn temperature(&self) -> Result<f64, AiVisionError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceAiVisionTemperatureGet(self.device) })
+    }
+
+    /// Returns the name of a class that the onboard model can detect, such as the name of a
+    /// game object.
+    ///
+    /// Class IDs are reported by [`AiVisionObject::Model`].
+    pub fn class_name(&self, id: u8) -> Result<String, AiVisionError> {
+        self.validate_port()?;
+
+        let mut buffer = [0u8; 32];
+        let len =
+            unsafe { vexDeviceAiVisionClassNameGet(self.device, id as i32, buffer.as_mut_ptr()) };
+        if len < 0 {
+            return Err(AiVisionError::InvalidId);
+        }
+
+        let name = buffer.split(|&byte| byte == 0).next().unwrap_or_default();
+
+        Ok(String::from_utf8_lossy(name).into())
+    }
+
+    /// Returns a [`Vec`] of objects detected by the sensor.
+    pub fn objects(&self) -> Result<Vec<AiVisionObject>, AiVisionError> {
+        let object_count = self.object_count()?;
+        let mut objects = Vec::with_capacity(object_count);
+
+        for i in 0..object_count {
+            let mut raw: V5_DeviceAiVisionObject = unsafe { core::mem::zeroed() };
+
+            if unsafe { vexDeviceAiVisionObjectGet(self.device, i as u32, &mut raw) } == 0 {
+                return Err(AiVisionError::ReadingFailed);
+            }
+
+            if let Some(object) = AiVisionObject::from_raw(raw) {
+                objects.push(object);
+            }
+        }
+
+        Ok(objects)
+    }
+
+    /// Returns the number of objects detected by the sensor.
+    pub fn object_count(&self) -> Result<usize, AiVisionError> {
+        self.validate_port()?;
+
+        let count = unsafe { vexDeviceAiVisionObjectCountGet(self.device) };
+        if count < 0 {
+            return Err(AiVisionError::ReadingFailed);
+        }
+
+        Ok(count as usize)
+    }
+}
+
+impl SmartDevice for AiVisionSensor {
+    fn port_index(&self) -> u8 {
+        self.port.index()
+    }
+
+    fn device_type(&self) -> SmartDeviceType {
+        SmartDeviceType::AiVision
+    }
+}
+
+/// Checks that a color or code ID is within `1..=max`.
+const fn validate_id(id: u8, max: u8) -> Result<(), AiVisionError> {
+    if id == 0 || id > max {
+        return Err(AiVisionError::InvalidId);
+    }
+
+    Ok(())
+}
+
+/// A color that the AI vision sensor can detect.
+///
+/// The sensor matches pixels whose hue and saturation are close to those of `rgb`. These are
+/// typically found using the AI Vision Utility rather than written by hand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiVisionColor {
+    /// The color to detect.
+    pub rgb: Rgb,
+
+    /// How far (in degrees) a pixel's hue can be from the color's hue and still match.
+    pub hue_range: f32,
+
+    /// How far (from 0.0 to 1.0) a pixel's saturation can be from the color's saturation and
+    /// still match.
+    pub saturation_range: f32,
+}
+
+impl AiVisionColor {
+    /// Creates a new color.
+    pub const fn new(rgb: Rgb, hue_range: f32, saturation_range: f32) -> Self {
+        Self {
+            rgb,
+            hue_range,
+            saturation_range,
+        }
+    }
+}
+
+/// A group of colors that the AI vision sensor detects as one object when they appear next to
+/// each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AiVisionCode {
+    colors: [u8; 7],
+    len: u8,
+}
+
+impl AiVisionCode {
+    /// Creates a new code from the IDs of 2 to 7 colors stored on the sensor.
+    ///
+    /// Returns `None` if too few or too many colors are given, or if any ID is not from 1 to 7.
+    pub fn new(colors: &[u8]) -> Option<Self> {
+        if !(2..=7).contains(&colors.len())
+            || colors
+                .iter()
+                .any(|&id| validate_id(id, AiVisionSensor::MAX_COLORS).is_err())
+        {
+            return None;
+        }
+
+        let mut code = Self {
+            colors: [0; 7],
+            len: colors.len() as u8,
+        };
+        code.colors[..colors.len()].copy_from_slice(colors);
+
+        Some(code)
+    }
+
+    /// Returns the IDs of the colors in this code.
+    pub fn colors(&self) -> &[u8] {
+        &self.colors[..self.len as usize]
+    }
+}
+
+bitflags! {
+    /// The detection methods used by an AI vision sensor.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AiVisionDetectionMode: u8 {
+        /// Detect AprilTags.
+        const APRILTAG = 1 << 0;
+
+        /// Detect objects by color and color code.
+        const COLOR = 1 << 1;
+
+        /// Detect objects using the onboard model.
+        const MODEL = 1 << 2;
+
+        /// Merge adjacent blobs of the same color into a single object.
+        const COLOR_MERGE = 1 << 4;
+    }
+}
+
+/// An object detected by an AI vision sensor.
+///
+/// Coordinates are in pixels relative to the top-left of the camera's field of view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiVisionObject {
+    /// An object detected by one of the sensor's colors.
+    Color {
+        /// The ID of the color.
+        id: u8,
+
+        /// The top-left coordinate of the object's bounding box.
+        offset: Point2<u16>,
+
+        /// The width of the object's bounding box.
+        width: u16,
+
+        /// The height of the object's bounding box.
+        height: u16,
+    },
+
+    /// An object detected by one of the sensor's color codes.
+    Code {
+        /// The ID of the code.
+        id: u8,
+
+        /// The top-left coordinate of the object's bounding box.
+        offset: Point2<u16>,
+
+        /// The width of the object's bounding box.
+        width: u16,
+
+        /// The height of the object's bounding box.
+        height: u16,
+
+        /// The angle of the object in degrees.
+        angle: f64,
+    },
+
+    /// An object classified by the sensor's onboard model.
+    Model {
+        /// The ID of the object's class. See [`AiVisionSensor::class_name`].
+        id: u8,
+
+        /// The top-left coordinate of the object's bounding box.
+        offset: Point2<u16>,
+
+        /// The width of the object's bounding box.
+        width: u16,
+
+        /// The height of the object's bounding box.
+        height: u16,
+
+        /// How confident the model is in the classification.
+        confidence: u16,
+    },
+
+    /// An AprilTag.
+    AprilTag {
+        /// The ID encoded in the tag.
+        id: u8,
+
+        /// The four corners of the tag, in order.
+        corners: [Point2<i16>; 4],
+    },
+}
+
+impl AiVisionObject {
+    /// Converts an object returned by the SDK, or returns `None` if its type is unknown.
+    fn from_raw(raw: V5_DeviceAiVisionObject) -> Option<Self> {
+        let id = raw.id;
+
+        // SAFETY: The union variant read is selected by the object type reported by the sensor.
+        Some(match raw.r#type {
+            OBJECT_TYPE_COLOR => {
+                let data = unsafe { raw.object.color };
+                Self::Color {
+                    id,
+                    offset: Point2::new(data.xoffset, data.yoffset),
+                    width: data.width,
+                    height: data.height,
+                }
+            }
+            OBJECT_TYPE_CODE => {
+                let data = unsafe { raw.object.color };
+                Self::Code {
+                    id,
+                    offset: Point2::new(data.xoffset, data.yoffset),
+                    width: data.width,
+                    height: data.height,
+                    angle: data.angle as f64 / 10.0,
+                }
+            }
+            OBJECT_TYPE_MODEL => {
+                let data = unsafe { raw.object.model };
+                Self::Model {
+                    id,
+                    offset: Point2::new(data.xoffset, data.yoffset),
+                    width: data.width,
+                    height: data.height,
+                    confidence: data.score,
+                }
+            }
+            OBJECT_TYPE_TAG => {
+                let data = unsafe { raw.object.tag };
+                Self::AprilTag {
+                    id,
+                    corners: [
+                        Point2::new(data.x0, data.y0),
+                        Point2::new(data.x1, data.y1),
+                        Point2::new(data.x2, data.y2),
+                        Point2::new(data.x3, data.y3),
+                    ],
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Returns the ID of the color, code, model class or AprilTag that detected this object.
+    pub const fn id(&self) -> u8 {
+        match self {
+            Self::Color { id, .. }
+            | Self::Code { id, .. }
+            | Self::Model { id, .. }
+            | Self::AprilTag { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when using an AI vision sensor.
+pub enum AiVisionError {
+    /// The given color, code or class ID is out of range.
+    InvalidId,
+
+    /// The camera could not be read.
+    ReadingFailed,
+
+    /// Generic port related error.
+    #[snafu(display("{source}"), context(false))]
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+}
@@ -0,0 +1,345 @@
+//! CTE Workcell arm device.
+//!
+//! This module provides an interface for the six-axis robotic arm included with the CTE
+//! Workcell kit.
+//!
+//! # Hardware Overview
+//!
+//! The arm has six joints, numbered from the base outward. Its end effector can be moved
+//! either by commanding each joint directly or by commanding a tip position, in which case the
+//! arm solves for the joint angles itself. Tip positions are measured in millimeters from the
+//! base of the arm.
+//!
+//! Tip moves can follow a straight line ([`Arm::move_tip_linear`]) or whatever path results
+//! from moving each joint at once ([`Arm::move_tip_joint`]). Either way, the command returns
+//! immediately while the arm carries out the move.
+
+use vex_sdk::{
+    vexDeviceArmDropCommand, vexDeviceArmEnableProfiler, vexDeviceArmFullStop,
+    vexDeviceArmJointErrorsGet, vexDeviceArmJointInfoGet, vexDeviceArmMoveTipCommandJoint,
+    vexDeviceArmMoveTipCommandLinear, vexDeviceArmMoveVoltsCommand, vexDeviceArmPickUpCommand,
+    vexDeviceArmPoseSet, vexDeviceArmProfilerVelocitySet, vexDeviceArmSpinJoints,
+    vexDeviceArmStatusGet, vexDeviceArmTipPositionGetAdv, V5MotorBrakeMode,
+    V5_DeviceArmTipPosition, V5_DeviceT,
+};
+
+use super::{motor::BrakeMode, SmartDevice, SmartDeviceType, SmartPort};
+use crate::PortError;
+
+/// A CTE Workcell arm plugged into a smart port.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Arm {
+    port: SmartPort,
+    device: V5_DeviceT,
+}
+
+// SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
+// SDK each device function. Simply sharing a raw pointer across threads is not inherently unsafe.
+unsafe impl Send for Arm {}
+unsafe impl Sync for Arm {}
+
+impl Arm {
+    /// The number of joints on the arm.
+    pub const JOINT_COUNT: usize = 6;
+
+    /// Creates a new arm from a smart port index.
+    pub fn new(port: SmartPort) -> Self {
+        Self {
+            device: unsafe { port.device_handle() },
+            port,
+        }
+    }
+
+    /// Returns the current position and orientation of the arm's end effector.
+    pub fn tip_position(&self) -> Result<ArmTipPosition, PortError> {
+        self.validate_port()?;
+
+        let mut raw = V5_DeviceArmTipPosition::default();
+        unsafe { vexDeviceArmTipPositionGetAdv(self.device, &mut raw) };
+
+        Ok(raw.into())
+    }
+
+    /// Returns the angle of each joint in degrees.
+    pub fn joint_positions(&self) -> Result<[f64; Self::JOINT_COUNT], PortError> {
+        Ok(self.joint_info()?.0)
+    }
+
+    /// Returns the velocity of each joint in degrees per second.
+    pub fn joint_velocities(&self) -> Result<[f64; Self::JOINT_COUNT], PortError> {
+        Ok(self.joint_info()?.1)
+    }
+
+    /// Returns the current drawn by each joint in milliamps.
+    pub fn joint_currents(&self) -> Result<[i32; Self::JOINT_COUNT], PortError> {
+        Ok(self.joint_info()?.2)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn joint_info(
+        &self,
+    ) -> Result<
+        (
+            [f64; Self::JOINT_COUNT],
+            [f64; Self::JOINT_COUNT],
+            [i32; Self::JOINT_COUNT],
+        ),
+        PortError,
+    > {
+        self.validate_port()?;
+
+        let mut positions = [0.0; Self::JOINT_COUNT];
+        let mut velocities = [0.0; Self::JOINT_COUNT];
+        let mut currents = [0; Self::JOINT_COUNT];
+        unsafe {
+            vexDeviceArmJointInfoGet(
+                self.device,
+                positions.as_mut_ptr(),
+                velocities.as_mut_ptr(),
+                currents.as_mut_ptr(),
+            );
+        }
+
+        Ok((positions, velocities, currents))
+    }
+
+    /// Returns the error flags reported by each joint's servo. A joint with no errors
+    /// reports zero.
+    pub fn joint_errors(&self) -> Result<[u8; Self::JOINT_COUNT], PortError> {
+        self.validate_port()?;
+
+        let mut errors = [0; Self::JOINT_COUNT];
+        unsafe { vexDeviceArmJointErrorsGet(self.device, errors.as_mut_ptr()) };
+
+        Ok(errors)
+    }
+
+    /// Moves the end effector along a straight line to a target.
+    ///
+    /// If `relative` is `true`, the target is relative to the current tip position.
+    pub fn move_tip_linear(&mut self, target: ArmTarget, relative: bool) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe {
+            vexDeviceArmMoveTipCommandLinear(
+                self.device,
+                target.x,
+                target.y,
+                target.z,
+                target.pose,
+                target.velocity,
+                target.rotation,
+                target.rotation_velocity,
+                relative,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Moves the end effector to a target by moving every joint at once.
+    ///
+    /// This is usually faster than [`Arm::move_tip_linear`], but the end effector doesn't
+    /// follow a straight line. If `relative` is `true`, the target is relative to the current
+    /// tip position.
+    pub fn move_tip_joint(&mut self, target: ArmTarget, relative: bool) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe {
+            vexDeviceArmMoveTipCommandJoint(
+                self.device,
+                target.x,
+                target.y,
+                target.z,
+                target.pose,
+                target.velocity,
+                target.rotation,
+                target.rotation_velocity,
+                relative,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Changes the orientation of the end effector without moving its tip.
+    pub fn set_pose(&mut self, pose: u8, velocity: u16) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmPoseSet(self.device, pose, velocity) };
+
+        Ok(())
+    }
+
+    /// Spins each joint at a velocity in degrees per second until another command is given.
+    pub fn spin_joints(&mut self, velocities: [f64; Self::JOINT_COUNT]) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        let mut velocities = velocities;
+        unsafe { vexDeviceArmSpinJoints(self.device, velocities.as_mut_ptr()) };
+
+        Ok(())
+    }
+
+    /// Applies a voltage to each joint until another command is given.
+    pub fn set_joint_voltages(
+        &mut self,
+        voltages: [f64; Self::JOINT_COUNT],
+    ) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        let mut voltages = voltages;
+        unsafe { vexDeviceArmMoveVoltsCommand(self.device, voltages.as_mut_ptr()) };
+
+        Ok(())
+    }
+
+    /// Stops all motion of the arm, braking each joint with the given mode.
+    pub fn stop(&mut self, brake_mode: BrakeMode) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmFullStop(self.device, V5MotorBrakeMode::from(brake_mode).0) };
+
+        Ok(())
+    }
+
+    /// Picks up an object with the arm's end effector.
+    pub fn pick_up(&mut self) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmPickUpCommand(self.device) };
+
+        Ok(())
+    }
+
+    /// Releases the object held by the arm's end effector.
+    pub fn release(&mut self) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmDropCommand(self.device) };
+
+        Ok(())
+    }
+
+    /// Enables or disables motion profiling, which smooths out the acceleration of moves.
+    pub fn set_profiler_enabled(&mut self, enabled: bool) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmEnableProfiler(self.device, enabled as u8) };
+
+        Ok(())
+    }
+
+    /// Sets the maximum velocities used by the motion profiler for linear and joint moves.
+    pub fn set_profiler_velocity(
+        &mut self,
+        linear_velocity: u16,
+        joint_velocity: u16,
+    ) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceArmProfilerVelocitySet(self.device, linear_velocity, joint_velocity) };
+
+        Ok(())
+    }
+
+    /// Returns the status code of the arm.
+    pub fn status(&self) -> Result<u32, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceArmStatusGet(self.device) })
+    }
+}
+
+impl SmartDevice for Arm {
+    fn port_index(&self) -> u8 {
+        self.port.index()
+    }
+
+    fn device_type(&self) -> SmartDeviceType {
+        SmartDeviceType::Arm
+    }
+}
+
+/// The position and orientation of an arm's end effector.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ArmTipPosition {
+    /// The X coordinate of the tip in millimeters.
+    pub x: i32,
+
+    /// The Y coordinate of the tip in millimeters.
+    pub y: i32,
+
+    /// The Z coordinate of the tip in millimeters.
+    pub z: i32,
+
+    /// The roll of the end effector in degrees.
+    pub roll: i32,
+
+    /// The pitch of the end effector in degrees.
+    pub pitch: i32,
+
+    /// The yaw of the end effector in degrees.
+    pub yaw: i32,
+
+    /// The pose of the end effector.
+    pub pose: i8,
+
+    /// The velocity of the tip.
+    pub velocity: i16,
+}
+
+impl From<V5_DeviceArmTipPosition> for ArmTipPosition {
+    fn from(value: V5_DeviceArmTipPosition) -> Self {
+        Self {
+            x: value.tip_x,
+            y: value.tip_y,
+            z: value.tip_z,
+            roll: value.tip_roll,
+            pitch: value.tip_pitch,
+            yaw: value.tip_yaw,
+            pose: value.pose,
+            velocity: value.velocity,
+        }
+    }
+}
+
+/// A target for the end effector of an arm.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ArmTarget {
+    /// The X coordinate of the target in millimeters.
+    pub x: i32,
+
+    /// The Y coordinate of the target in millimeters.
+    pub y: i32,
+
+    /// The Z coordinate of the target in millimeters.
+    pub z: i32,
+
+    /// The pose of the end effector at the target.
+    pub pose: u8,
+
+    /// The velocity of the move.
+    pub velocity: u16,
+
+    /// The rotation of the end effector at the target in degrees.
+    pub rotation: f64,
+
+    /// The velocity of the end effector's rotation.
+    pub rotation_velocity: u16,
+}
+
+impl ArmTarget {
+    /// Creates a target at a position, keeping the other fields at their defaults.
+    pub const fn new(x: i32, y: i32, z: i32, velocity: u16) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            pose: 0,
+            velocity,
+            rotation: 0.0,
+            rotation_velocity: 0,
+        }
+    }
+}
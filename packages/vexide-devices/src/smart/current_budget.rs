@@ -0,0 +1,491 @@
+//! Brain-wide motor current budgeting.
+//!
+//! The brain shares a limited amount of current between every motor plugged into it. Once
+//! more than a handful of motors are plugged in, VEXos lowers every motor's current limit to
+//! stay within that total, so a robot with many motors ends up with its drivetrain throttled
+//! just as much as a rarely used mechanism.
+//!
+//! A [`CurrentBudget`] takes control of this by setting each motor's current limit itself.
+//! Motors are sorted into [`CurrentGroup`]s, each with a guaranteed minimum current and a
+//! weight for each [`CompetitionMode`]. The budget is then split between the groups so that,
+//! for example, the drivetrain gets most of it during driver control while a lift gets more
+//! during autonomous.
+//!
+//! A budget overwrites every motor's current limit each time it is applied. When a
+//! [`MotorHealthMonitor`] derates hot motors, use [`CurrentBudget::apply_with_health`] so that
+//! derated motors stay at their lowered limit.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! let mut budget = CurrentBudget::default();
+//! let drive = budget.add_group(
+//!     CurrentGroup::new("drive")
+//!         .minimum(1.0)
+//!         .weight(CompetitionMode::Driver, 3.0),
+//! );
+//! let lift = budget.add_group(
+//!     CurrentGroup::new("lift")
+//!         .minimum(0.5)
+//!         .weight(CompetitionMode::Autonomous, 2.0),
+//! );
+//!
+//! loop {
+//!     budget.apply(&mut [
+//!         (drive, &mut left_motor),
+//!         (drive, &mut right_motor),
+//!         (lift, &mut lift_motor),
+//!     ]);
+//!
+//!     sleep(Duration::from_millis(100)).await;
+//! }
+//! ```
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+use vexide_core::{competition, competition::CompetitionMode};
+
+use super::{motor::Motor, motor_health::MotorHealthMonitor, SmartDevice};
+
+/// Allocations smaller than this are treated as zero when distributing leftover current.
+const EPSILON: f64 = 1e-6;
+
+/// A group of motors that share a priority in a [`CurrentBudget`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurrentGroup {
+    name: &'static str,
+    minimum: f64,
+    weights: [f64; 3],
+}
+
+impl CurrentGroup {
+    /// Creates a new group with no guaranteed minimum and a weight of `1.0` in every
+    /// competition mode.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            minimum: 0.0,
+            weights: [1.0; 3],
+        }
+    }
+
+    /// Sets the current limit (in amps) each motor in this group is guaranteed to receive.
+    ///
+    /// If the minimums of every group don't fit within the budget, they are all scaled down
+    /// by the same factor.
+    pub const fn minimum(mut self, minimum: f64) -> Self {
+        self.minimum = minimum;
+        self
+    }
+
+    /// Sets how strongly this group is favored when splitting the current left over after
+    /// minimums during a competition mode.
+    ///
+    /// Leftover current is split in proportion to each motor's group weight, so a group with a
+    /// weight of `2.0` receives twice as much per motor as a group with a weight of `1.0`. A
+    /// weight of `0.0` gives the group only its minimum.
+    pub const fn weight(mut self, mode: CompetitionMode, weight: f64) -> Self {
+        self.weights[mode_index(mode)] = weight;
+        self
+    }
+
+    /// Returns the name of this group.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the current limit (in amps) each motor in this group is guaranteed to receive.
+    pub const fn guaranteed_minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    /// Returns this group's weight during a competition mode.
+    pub const fn weight_in(&self, mode: CompetitionMode) -> f64 {
+        self.weights[mode_index(mode)]
+    }
+}
+
+/// A handle to a group added to a [`CurrentBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupId(usize);
+
+/// Distributes a total current budget between groups of motors.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrentBudget {
+    total: f64,
+    groups: Vec<CurrentGroup>,
+}
+
+impl CurrentBudget {
+    /// The total current (in amps) the brain can supply to motors.
+    pub const BRAIN_CURRENT: f64 = 20.0;
+
+    /// The highest current limit (in amps) a single motor can use.
+    pub const MOTOR_MAX_CURRENT: f64 = 2.5;
+
+    /// Creates a new budget distributing `total` amps between motors.
+    pub const fn new(total: f64) -> Self {
+        Self {
+            total,
+            groups: Vec::new(),
+        }
+    }
+
+    /// Returns the total current (in amps) distributed by this budget.
+    pub const fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Adds a group of motors to the budget.
+    pub fn add_group(&mut self, group: CurrentGroup) -> GroupId {
+        self.groups.push(group);
+        GroupId(self.groups.len() - 1)
+    }
+
+    /// Returns a group in this budget.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `id` was returned by a different budget with fewer groups.
+    pub fn group(&self, id: GroupId) -> &CurrentGroup {
+        &self.groups[id.0]
+    }
+
+    /// Calculates the current limit for each motor in every group.
+    ///
+    /// `motor_counts` is the number of motors in each group, indexed in the order the groups
+    /// were added. Missing entries are treated as empty groups. Returns the current limit (in
+    /// amps) for a single motor in each group, in the same order.
+    ///
+    /// Each motor first receives its group's minimum. The rest of the budget is then split in
+    /// proportion to group weights, with any current that would take a motor past
+    /// [`CurrentBudget::MOTOR_MAX_CURRENT`] passed on to the other groups.
+    pub fn allocate(&self, mode: CompetitionMode, motor_counts: &[usize]) -> Vec<f64> {
+        let count = |index: usize| motor_counts.get(index).copied().unwrap_or(0) as f64;
+
+        let mut limits: Vec<f64> = self
+            .groups
+            .iter()
+            .map(|group| group.minimum.clamp(0.0, Self::MOTOR_MAX_CURRENT))
+            .collect();
+
+        let minimum_total: f64 = limits
+            .iter()
+            .enumerate()
+            .map(|(index, limit)| limit * count(index))
+            .sum();
+
+        if minimum_total >= self.total {
+            let scale = if minimum_total > 0.0 {
+                self.total.max(0.0) / minimum_total
+            } else {
+                0.0
+            };
+
+            for limit in &mut limits {
+                *limit *= scale;
+            }
+
+            return limits;
+        }
+
+        let mut remaining = self.total - minimum_total;
+        let mut active: Vec<usize> = (0..self.groups.len())
+            .filter(|&index| {
+                count(index) > 0.0
+                    && self.groups[index].weight_in(mode) > 0.0
+                    && limits[index] < Self::MOTOR_MAX_CURRENT
+            })
+            .collect();
+
+        while remaining > EPSILON && !active.is_empty() {
+            let total_weight: f64 = active
+                .iter()
+                .map(|&index| self.groups[index].weight_in(mode) * count(index))
+                .sum();
+            let per_weight = remaining / total_weight;
+
+            // Fill any groups that would be pushed past the per-motor maximum first, then split
+            // what's left between the rest.
+            let saturated: Vec<usize> = active
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    limits[index] + per_weight * self.groups[index].weight_in(mode)
+                        >= Self::MOTOR_MAX_CURRENT
+                })
+                .collect();
+
+            if saturated.is_empty() {
+                for &index in &active {
+                    limits[index] += per_weight * self.groups[index].weight_in(mode);
+                }
+                break;
+            }
+
+            for &index in &saturated {
+                remaining -= (Self::MOTOR_MAX_CURRENT - limits[index]) * count(index);
+                limits[index] = Self::MOTOR_MAX_CURRENT;
+            }
+            active.retain(|index| !saturated.contains(index));
+        }
+
+        limits
+    }
+
+    /// Sets the current limit of each motor according to the current competition mode.
+    ///
+    /// Each motor is paired with the group it belongs to. Motors that are disconnected are
+    /// left out of the budget, so their share goes to the motors that are still plugged in.
+    /// Returns the current limit (in amps) given to a single motor in each group.
+    ///
+    /// This should be called regularly, such as whenever the competition mode changes and
+    /// after motors are reconnected.
+    ///
+    /// This overwrites any limits set by a [`MotorHealthMonitor`] that derates motors, so
+    /// [`CurrentBudget::apply_with_health`] should be used instead in that case.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a [`GroupId`] was returned by a different budget with fewer
+    /// groups.
+    pub fn apply(&self, motors: &mut [(GroupId, &mut Motor)]) -> Vec<f64> {
+        self.apply_for_mode(competition::mode(), motors)
+    }
+
+    /// Sets the current limit of each motor according to the current competition mode,
+    /// keeping motors derated by `health` at their lowered limit.
+    ///
+    /// Derated motors are restored to the limit given here once they cool down. See
+    /// [`CurrentBudget::apply`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a [`GroupId`] was returned by a different budget with fewer
+    /// groups.
+    pub fn apply_with_health(
+        &self,
+        health: &mut MotorHealthMonitor,
+        motors: &mut [(GroupId, &mut Motor)],
+    ) -> Vec<f64> {
+        self.set_limits(competition::mode(), Some(health), motors)
+    }
+
+    /// Sets the current limit of each motor as if the robot were in the given competition
+    /// mode.
+    ///
+    /// See [`CurrentBudget::apply`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if a [`GroupId`] was returned by a different budget with fewer
+    /// groups.
+    pub fn apply_for_mode(
+        &self,
+        mode: CompetitionMode,
+        motors: &mut [(GroupId, &mut Motor)],
+    ) -> Vec<f64> {
+        self.set_limits(mode, None, motors)
+    }
+
+    fn set_limits(
+        &self,
+        mode: CompetitionMode,
+        mut health: Option<&mut MotorHealthMonitor>,
+        motors: &mut [(GroupId, &mut Motor)],
+    ) -> Vec<f64> {
+        let mut motor_counts = vec![0; self.groups.len()];
+        for (group, motor) in motors.iter() {
+            if motor.is_connected() {
+                motor_counts[group.0] += 1;
+            }
+        }
+
+        let limits = self.allocate(mode, &motor_counts);
+
+        for (group, motor) in motors.iter_mut() {
+            let limit = match &mut health {
+                Some(health) => health.cap_current_limit(motor.port_index(), limits[group.0]),
+                None => limits[group.0],
+            };
+
+            // Disconnected motors weren't counted, and will pick up their limit on the next
+            // call after they reconnect.
+            _ = motor.set_current_limit(limit);
+        }
+
+        limits
+    }
+}
+
+impl Default for CurrentBudget {
+    fn default() -> Self {
+        Self::new(Self::BRAIN_CURRENT)
+    }
+}
+
+/// Returns the index of a competition mode's weight in a [`CurrentGroup`].
+const fn mode_index(mode: CompetitionMode) -> usize {
+    match mode {
+        CompetitionMode::Disabled => 0,
+        CompetitionMode::Autonomous => 1,
+        CompetitionMode::Driver => 2,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_limits(limits: &[f64], expected: &[f64]) {
+        assert_eq!(limits.len(), expected.len());
+        for (limit, expected) in limits.iter().zip(expected) {
+            assert!(
+                (limit - expected).abs() < 1e-9,
+                "{limits:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_by_weight() {
+        let mut budget = CurrentBudget::new(8.0);
+        budget.add_group(CurrentGroup::new("drive").weight(CompetitionMode::Driver, 3.0));
+        budget.add_group(CurrentGroup::new("lift"));
+
+        // 4 drive motors at weight 3 and 4 lift motors at weight 1 split 8 amps 6:2.
+        assert_limits(
+            &budget.allocate(CompetitionMode::Driver, &[4, 4]),
+            &[1.5, 0.5],
+        );
+        assert_limits(
+            &budget.allocate(CompetitionMode::Autonomous, &[4, 4]),
+            &[1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn minimums_scaled_down_when_over_budget() {
+        let mut budget = CurrentBudget::new(4.0);
+        budget.add_group(CurrentGroup::new("drive").minimum(1.0));
+        budget.add_group(CurrentGroup::new("lift").minimum(0.75));
+
+        // The minimums need 3 * 1.0 + 2 * 0.75 = 4.5 amps, so are scaled by 4.0 / 4.5.
+        let limits = budget.allocate(CompetitionMode::Driver, &[3, 2]);
+        assert_limits(&limits, &[1.0 * 4.0 / 4.5, 0.75 * 4.0 / 4.5]);
+        assert!((limits[0] * 3.0 + limits[1] * 2.0 - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn minimums_given_before_weights() {
+        let mut budget = CurrentBudget::new(6.0);
+        budget.add_group(CurrentGroup::new("drive").minimum(1.0));
+        budget.add_group(CurrentGroup::new("lift"));
+
+        // 2 amps go to the drive's minimums, and the other 4 are split evenly per motor.
+        assert_limits(
+            &budget.allocate(CompetitionMode::Driver, &[2, 2]),
+            &[2.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn saturated_groups_pass_on_leftover() {
+        let mut budget = CurrentBudget::new(10.0);
+        budget.add_group(CurrentGroup::new("drive").weight(CompetitionMode::Driver, 3.0));
+        budget.add_group(CurrentGroup::new("lift"));
+
+        // The drive's share would be 3 amps per motor, so it's capped at the maximum and the
+        // rest goes to the lift.
+        let limits = budget.allocate(CompetitionMode::Driver, &[2, 4]);
+        assert_limits(&limits, &[CurrentBudget::MOTOR_MAX_CURRENT, 1.25]);
+    }
+
+    #[test]
+    fn everything_saturated() {
+        let mut budget = CurrentBudget::new(100.0);
+        budget.add_group(CurrentGroup::new("drive"));
+        budget.add_group(CurrentGroup::new("lift"));
+
+        assert_limits(
+            &budget.allocate(CompetitionMode::Driver, &[4, 2]),
+            &[CurrentBudget::MOTOR_MAX_CURRENT; 2],
+        );
+    }
+
+    #[test]
+    fn zero_weight_gets_only_minimum() {
+        let mut budget = CurrentBudget::new(4.0);
+        budget.add_group(
+            CurrentGroup::new("lift")
+                .minimum(0.5)
+                .weight(CompetitionMode::Driver, 0.0),
+        );
+        budget.add_group(CurrentGroup::new("drive"));
+
+        assert_limits(
+            &budget.allocate(CompetitionMode::Driver, &[2, 2]),
+            &[0.5, 1.5],
+        );
+    }
+
+    #[test]
+    fn empty_groups_take_nothing() {
+        let mut budget = CurrentBudget::new(4.0);
+        budget.add_group(CurrentGroup::new("drive"));
+        budget.add_group(
+            CurrentGroup::new("lift")
+                .minimum(1.0)
+                .weight(CompetitionMode::Driver, 5.0),
+        );
+        budget.add_group(CurrentGroup::new("intake"));
+
+        // The lift has no motors and the intake is missing from the counts, so the drive
+        // gets everything.
+        assert_limits(
+            &budget.allocate(CompetitionMode::Driver, &[2, 0]),
+            &[2.0, 1.0, 0.0],
+        );
+    }
+
+    #[test]
+    fn no_groups() {
+        let budget = CurrentBudget::default();
+        assert!(budget.allocate(CompetitionMode::Driver, &[]).is_empty());
+    }
+
+    #[test]
+    fn never_exceeds_total() {
+        let mut budget = CurrentBudget::new(12.0);
+        budget.add_group(
+            CurrentGroup::new("drive")
+                .minimum(0.8)
+                .weight(CompetitionMode::Driver, 2.0),
+        );
+        budget.add_group(CurrentGroup::new("lift").minimum(0.3));
+        budget.add_group(CurrentGroup::new("intake").weight(CompetitionMode::Driver, 0.5));
+
+        for drive in 0..8 {
+            for lift in 0..4 {
+                for intake in 0..3 {
+                    let counts = [drive, lift, intake];
+                    let limits = budget.allocate(CompetitionMode::Driver, &counts);
+                    let used: f64 = limits
+                        .iter()
+                        .zip(counts)
+                        .map(|(limit, count)| limit * count as f64)
+                        .sum();
+
+                    assert!(used <= budget.total() + 1e-9, "{counts:?} used {used}");
+                    assert!(limits
+                        .iter()
+                        .all(|limit| (0.0..=CurrentBudget::MOTOR_MAX_CURRENT).contains(limit)));
+                }
+            }
+        }
+    }
+}
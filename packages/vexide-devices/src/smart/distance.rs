@@ -7,6 +7,8 @@ use vex_sdk::{
 };
 
 use super::{SmartDevice, SmartDeviceType, SmartPort};
+#[cfg(feature = "units")]
+use crate::units::Length;
 use crate::PortError;
 
 /// A physical distance sensor plugged into a port.
@@ -103,6 +105,17 @@ impl DistanceSensor {
     }
 }
 
+#[cfg(feature = "units")]
+impl DistanceSensor {
+    /// Returns the distance to the object the sensor detects or None if the distance is out
+    /// of range.
+    pub fn distance_typed(&self) -> Result<Option<Length>, DistanceError> {
+        Ok(self
+            .distance()?
+            .map(|distance| Length::from_millimeters(distance as f64)))
+    }
+}
+
 impl SmartDevice for DistanceSensor {
     fn port_index(&self) -> u8 {
         self.port.index()
@@ -0,0 +1,193 @@
+//! Light tower device.
+//!
+//! This module provides an interface for the signal light tower included with the CTE Workcell
+//! kit. The tower has red, green, blue, white and yellow segments that can be lit
+//! individually or made to blink.
+
+use core::time::Duration;
+
+use bitflags::bitflags;
+use vex_sdk::{
+    vexDeviceLightTowerBlinkSet, vexDeviceLightTowerColorSet, vexDeviceLightTowerRgbGet,
+    vexDeviceLightTowerRgbSet, vexDeviceLightTowerStatusGet, vexDeviceLightTowerXywGet, V5_DeviceT,
+};
+
+use super::{SmartDevice, SmartDeviceType, SmartPort};
+use crate::{color::Rgb, PortError};
+
+/// A light tower plugged into a smart port.
+#[derive(Debug, Eq, PartialEq)]
+pub struct LightTower {
+    port: SmartPort,
+    device: V5_DeviceT,
+}
+
+// SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
+// SDK each device function. Simply sharing a raw pointer across threads is not inherently unsafe.
+unsafe impl Send for LightTower {}
+unsafe impl Sync for LightTower {}
+
+impl LightTower {
+    /// Creates a new light tower from a smart port index.
+    pub fn new(port: SmartPort) -> Self {
+        Self {
+            device: unsafe { port.device_handle() },
+            port,
+        }
+    }
+
+    /// Sets the brightness of a single segment of the tower, from 0 (off) to 255 (full
+    /// brightness).
+    pub fn set_brightness(
+        &mut self,
+        color: LightTowerColor,
+        brightness: u8,
+    ) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceLightTowerColorSet(self.device, color as u32, brightness as u32) };
+
+        Ok(())
+    }
+
+    /// Turns a single segment of the tower fully on or off.
+    pub fn set_lit(&mut self, color: LightTowerColor, lit: bool) -> Result<(), PortError> {
+        self.set_brightness(color, if lit { u8::MAX } else { 0 })
+    }
+
+    /// Sets the brightness of every segment at once.
+    ///
+    /// The red, green and blue segments are set from `rgb`, while the white and yellow
+    /// segments are set from `white` and `yellow`.
+    pub fn set_all(&mut self, rgb: Rgb, white: u8, yellow: u8) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        let xyw = ((yellow as u32) << 8) | white as u32;
+        unsafe { vexDeviceLightTowerRgbSet(self.device, rgb.into(), xyw) };
+
+        Ok(())
+    }
+
+    /// Turns off every segment of the tower.
+    pub fn clear(&mut self) -> Result<(), PortError> {
+        self.set_all(Rgb::new(0, 0, 0), 0, 0)
+    }
+
+    /// Returns the brightness of the red, green and blue segments.
+    pub fn rgb(&self) -> Result<Rgb, PortError> {
+        self.validate_port()?;
+
+        Ok(Rgb::from(unsafe { vexDeviceLightTowerRgbGet(self.device) }))
+    }
+
+    /// Returns the brightness of the white segment.
+    pub fn white(&self) -> Result<u8, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceLightTowerXywGet(self.device) } as u8)
+    }
+
+    /// Returns the brightness of the yellow segment.
+    pub fn yellow(&self) -> Result<u8, PortError> {
+        self.validate_port()?;
+
+        Ok((unsafe { vexDeviceLightTowerXywGet(self.device) } >> 8) as u8)
+    }
+
+    /// Makes a set of segments blink, staying lit for `on_time` then unlit for `off_time`.
+    ///
+    /// Segments that aren't in `colors` stop blinking.
+    pub fn set_blink(
+        &mut self,
+        colors: LightTowerColors,
+        on_time: Duration,
+        off_time: Duration,
+    ) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe {
+            vexDeviceLightTowerBlinkSet(
+                self.device,
+                1,
+                colors.bits(),
+                on_time.as_millis() as i32,
+                off_time.as_millis() as i32,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Stops every segment from blinking.
+    pub fn stop_blink(&mut self) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceLightTowerBlinkSet(self.device, 0, 0, 0, 0) };
+
+        Ok(())
+    }
+
+    /// Returns the status code of the light tower.
+    pub fn status(&self) -> Result<u32, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceLightTowerStatusGet(self.device) })
+    }
+}
+
+impl SmartDevice for LightTower {
+    fn port_index(&self) -> u8 {
+        self.port.index()
+    }
+
+    fn device_type(&self) -> SmartDeviceType {
+        SmartDeviceType::LightTower
+    }
+}
+
+/// A segment of a light tower.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[repr(u8)]
+pub enum LightTowerColor {
+    /// The red segment.
+    Red = 0,
+
+    /// The green segment.
+    Green = 1,
+
+    /// The blue segment.
+    Blue = 2,
+
+    /// The white segment.
+    White = 3,
+
+    /// The yellow segment.
+    Yellow = 4,
+}
+
+bitflags! {
+    /// A set of light tower segments.
+    #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+    pub struct LightTowerColors: u8 {
+        /// The red segment.
+        const RED = 1 << LightTowerColor::Red as u8;
+
+        /// The green segment.
+        const GREEN = 1 << LightTowerColor::Green as u8;
+
+        /// The blue segment.
+        const BLUE = 1 << LightTowerColor::Blue as u8;
+
+        /// The white segment.
+        const WHITE = 1 << LightTowerColor::White as u8;
+
+        /// The yellow segment.
+        const YELLOW = 1 << LightTowerColor::Yellow as u8;
+    }
+}
+
+impl From<LightTowerColor> for LightTowerColors {
+    fn from(value: LightTowerColor) -> Self {
+        Self::from_bits_retain(1 << value as u8)
+    }
+}
@@ -0,0 +1,145 @@
+//! Electromagnet device.
+//!
+//! This module provides an interface for the electromagnet included with the CTE Workcell kit,
+//! which is used to pick up and drop metal discs.
+
+use core::time::Duration;
+
+use vex_sdk::{
+    vexDeviceMagnetCurrentGet, vexDeviceMagnetDrop, vexDeviceMagnetPickup, vexDeviceMagnetPowerGet,
+    vexDeviceMagnetPowerSet, vexDeviceMagnetStatusGet, vexDeviceMagnetTemperatureGet,
+    V5_DeviceMagnetDuration, V5_DeviceT,
+};
+
+use super::{SmartDevice, SmartDeviceType, SmartPort};
+use crate::PortError;
+
+/// An electromagnet plugged into a smart port.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Electromagnet {
+    port: SmartPort,
+    device: V5_DeviceT,
+}
+
+// SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
+// SDK each device function. Simply sharing a raw pointer across threads is not inherently unsafe.
+unsafe impl Send for Electromagnet {}
+unsafe impl Sync for Electromagnet {}
+
+impl Electromagnet {
+    /// The longest time that the electromagnet can be powered for with a single call to
+    /// [`Electromagnet::set_power`].
+    pub const MAX_POWER_DURATION: Duration = Duration::from_secs(2);
+
+    /// Creates a new electromagnet from a smart port index.
+    pub fn new(port: SmartPort) -> Self {
+        Self {
+            device: unsafe { port.device_handle() },
+            port,
+        }
+    }
+
+    /// Powers the electromagnet for a period of time.
+    ///
+    /// `power` is from -1.0 to 1.0, where positive values attract and negative values repel.
+    /// `duration` is capped at [`Electromagnet::MAX_POWER_DURATION`].
+    pub fn set_power(&mut self, power: f64, duration: Duration) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        let duration = duration.min(Self::MAX_POWER_DURATION);
+
+        unsafe {
+            vexDeviceMagnetPowerSet(
+                self.device,
+                (power.clamp(-1.0, 1.0) * 100.0) as i32,
+                duration.as_millis() as i32,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the power currently being applied to the electromagnet, from -1.0 to 1.0.
+    pub fn power(&self) -> Result<f64, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceMagnetPowerGet(self.device) } as f64 / 100.0)
+    }
+
+    /// Energizes the electromagnet to pick up an object.
+    pub fn pick_up(&mut self, duration: MagnetDuration) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceMagnetPickup(self.device, duration.into()) };
+
+        Ok(())
+    }
+
+    /// Briefly reverses the electromagnet to release the object it is holding.
+    pub fn release(&mut self, duration: MagnetDuration) -> Result<(), PortError> {
+        self.validate_port()?;
+
+        unsafe { vexDeviceMagnetDrop(self.device, duration.into()) };
+
+        Ok(())
+    }
+
+    /// Returns the temperature of the electromagnet in degrees Celsius.
+    pub fn temperature(&self) -> Result<f64, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceMagnetTemperatureGet(self.device) })
+    }
+
+    /// Returns the current drawn by the electromagnet in amps.
+    pub fn current(&self) -> Result<f64, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceMagnetCurrentGet(self.device) })
+    }
+
+    /// Returns the status code of the electromagnet.
+    pub fn status(&self) -> Result<u32, PortError> {
+        self.validate_port()?;
+
+        Ok(unsafe { vexDeviceMagnetStatusGet(self.device) })
+    }
+}
+
+impl SmartDevice for Electromagnet {
+    fn port_index(&self) -> u8 {
+        self.port.index()
+    }
+
+    fn device_type(&self) -> SmartDeviceType {
+        SmartDeviceType::Magnet
+    }
+}
+
+/// How long the electromagnet is powered for when picking up or releasing an object.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MagnetDuration {
+    /// A short pulse.
+    Short,
+
+    /// A medium pulse.
+    #[default]
+    Medium,
+
+    /// A long pulse.
+    Long,
+
+    /// An extra long pulse.
+    ExtraLong,
+}
+
+impl From<MagnetDuration> for V5_DeviceMagnetDuration {
+    fn from(value: MagnetDuration) -> Self {
+        match value {
+            MagnetDuration::Short => Self::kMagnetDurationShort,
+            MagnetDuration::Medium => Self::kMagnetDurationMedium,
+            MagnetDuration::Long => Self::kMagnetDurationLong,
+            MagnetDuration::ExtraLong => Self::kMagnetDurationExtraLong,
+        }
+    }
+}
@@ -17,24 +17,36 @@
 //!
 //! More specific info for each device is availible in their respective modules.
 
+pub mod ai_vision;
+pub mod arm;
+pub mod current_budget;
 pub mod distance;
 pub mod expander;
 pub mod gps;
 pub mod imu;
+pub mod light_tower;
 pub mod link;
+pub mod magnet;
+pub mod monitor;
 pub mod motor;
+pub mod motor_health;
 pub mod optical;
+mod reconnect;
 pub mod rotation;
 pub mod serial;
 pub mod vision;
 
 use core::fmt;
 
+pub use ai_vision::AiVisionSensor;
+pub use arm::Arm;
 pub use distance::DistanceSensor;
 pub use expander::AdiExpander;
 pub use gps::GpsSensor;
 pub use imu::InertialSensor;
+pub use light_tower::LightTower;
 pub use link::RadioLink;
+pub use magnet::Electromagnet;
 pub use motor::Motor;
 pub use optical::OpticalSensor;
 pub use rotation::RotationSensor;
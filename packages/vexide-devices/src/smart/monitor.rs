@@ -0,0 +1,251 @@
+//! Hot-plug monitoring for smart ports.
+//!
+//! Devices only check that they are connected when they are used, so a cable coming loose
+//! isn't noticed until the next reading fails with [`PortError::Disconnected`]. A
+//! [`PortMonitor`] instead watches smart ports in the background and produces a
+//! [`PortEvent`] as soon as a device is plugged in, unplugged, or replaced with a different
+//! type of device.
+//!
+//! ADI expanders are smart devices, so they are monitored like any other device.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//!
+//! let mut monitor = PortMonitor::new();
+//!
+//! while let Some(event) = monitor.next().await {
+//!     if let PortEvent::Disconnected { port, .. } = event {
+//!         println!("Lost connection to the device on port {port}!");
+//!     }
+//! }
+//! ```
+//!
+//! [`PortError::Disconnected`]: crate::PortError::Disconnected
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use vex_sdk::vexDeviceGetByIndex;
+use vexide_core::time::Instant;
+
+use super::SmartDeviceType;
+
+/// The number of smart ports on the brain.
+const SMART_PORT_COUNT: u8 = 21;
+
+/// An event produced by a [`PortMonitor`] when the device plugged into a smart port changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortEvent {
+    /// A device was plugged into an empty port.
+    Connected {
+        /// The index of the port.
+        port: u8,
+
+        /// The type of device that was plugged in.
+        device_type: SmartDeviceType,
+
+        /// When the change was noticed.
+        timestamp: Instant,
+    },
+
+    /// A device was unplugged, leaving the port empty.
+    Disconnected {
+        /// The index of the port.
+        port: u8,
+
+        /// The type of device that was unplugged.
+        device_type: SmartDeviceType,
+
+        /// When the change was noticed.
+        timestamp: Instant,
+    },
+
+    /// The device plugged into a port was replaced with a different type of device between
+    /// scans.
+    TypeChanged {
+        /// The index of the port.
+        port: u8,
+
+        /// The type of device that was previously plugged in.
+        from: SmartDeviceType,
+
+        /// The type of device that is now plugged in.
+        to: SmartDeviceType,
+
+        /// When the change was noticed.
+        timestamp: Instant,
+    },
+}
+
+impl PortEvent {
+    /// Returns the index of the port that changed.
+    pub const fn port(&self) -> u8 {
+        match self {
+            Self::Connected { port, .. }
+            | Self::Disconnected { port, .. }
+            | Self::TypeChanged { port, .. } => *port,
+        }
+    }
+
+    /// Returns when the change was noticed.
+    pub const fn timestamp(&self) -> Instant {
+        match self {
+            Self::Connected { timestamp, .. }
+            | Self::Disconnected { timestamp, .. }
+            | Self::TypeChanged { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// A stream of [`PortEvent`]s for changes to the devices plugged into smart ports.
+///
+/// The monitor takes a snapshot of the ports when it is created, so devices that are already
+/// plugged in don't produce events. Ports are scanned at most once every
+/// [`PortMonitor::SCAN_INTERVAL`] while the stream is polled.
+///
+/// Any number of monitors can be created, and each will receive every event independently.
+/// Monitoring doesn't take ownership of any ports.
+#[derive(Debug, Clone)]
+pub struct PortMonitor {
+    watched: u32,
+    known: [SmartDeviceType; SMART_PORT_COUNT as usize],
+    pending: VecDeque<PortEvent>,
+    last_scan: Option<Instant>,
+}
+
+impl PortMonitor {
+    /// The minimum time between scans of the ports.
+    pub const SCAN_INTERVAL: Duration = Duration::from_millis(20);
+
+    /// Creates a monitor watching every smart port.
+    pub fn new() -> Self {
+        Self::for_ports(1..=SMART_PORT_COUNT)
+    }
+
+    /// Creates a monitor watching only the given smart ports.
+    ///
+    /// Ports are indexed starting from 1. Indices outside of `1..=21` are ignored.
+    pub fn for_ports(ports: impl IntoIterator<Item = u8>) -> Self {
+        let watched = ports
+            .into_iter()
+            .filter(|port| (1..=SMART_PORT_COUNT).contains(port))
+            .fold(0, |watched, port| watched | (1 << (port - 1)));
+
+        let mut known = [SmartDeviceType::None; SMART_PORT_COUNT as usize];
+        for (index, device_type) in known.iter_mut().enumerate() {
+            *device_type = connected_type(index as u8 + 1);
+        }
+
+        Self {
+            watched,
+            known,
+            pending: VecDeque::new(),
+            last_scan: None,
+        }
+    }
+
+    /// Returns `true` if this monitor is watching a port.
+    pub const fn is_watching(&self, port: u8) -> bool {
+        port >= 1 && port <= SMART_PORT_COUNT && self.watched & (1 << (port - 1)) != 0
+    }
+
+    /// Returns the type of device that was plugged into a port as of the last scan, or `None`
+    /// if the port index is out of range.
+    ///
+    /// Ports with nothing plugged in report [`SmartDeviceType::None`].
+    pub fn device_type(&self, port: u8) -> Option<SmartDeviceType> {
+        self.known.get(port.checked_sub(1)? as usize).copied()
+    }
+
+    /// Scans the watched ports, queueing an event for each port that changed.
+    fn scan(&mut self, now: Instant) {
+        for port in 1..=SMART_PORT_COUNT {
+            if !self.is_watching(port) {
+                continue;
+            }
+
+            let current = connected_type(port);
+            let previous = core::mem::replace(&mut self.known[port as usize - 1], current);
+
+            let event = match (previous, current) {
+                (previous, current) if previous == current => continue,
+                (SmartDeviceType::None, device_type) => PortEvent::Connected {
+                    port,
+                    device_type,
+                    timestamp: now,
+                },
+                (device_type, SmartDeviceType::None) => PortEvent::Disconnected {
+                    port,
+                    device_type,
+                    timestamp: now,
+                },
+                (from, to) => PortEvent::TypeChanged {
+                    port,
+                    from,
+                    to,
+                    timestamp: now,
+                },
+            };
+
+            self.pending.push_back(event);
+        }
+    }
+}
+
+impl Default for PortMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stream for PortMonitor {
+    type Item = PortEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(event) = this.pending.pop_front() {
+            return Poll::Ready(Some(event));
+        }
+
+        // TODO: This should probably be done on a timer in the reactor.
+        cx.waker().wake_by_ref();
+
+        let now = Instant::now();
+        if this
+            .last_scan
+            .is_some_and(|last_scan| now - last_scan < Self::SCAN_INTERVAL)
+        {
+            return Poll::Pending;
+        }
+        this.last_scan = Some(now);
+
+        this.scan(now);
+
+        match this.pending.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Returns the type of device plugged into a smart port, or [`SmartDeviceType::None`] if the
+/// port is empty.
+fn connected_type(port: u8) -> SmartDeviceType {
+    let device = unsafe { *vexDeviceGetByIndex((port - 1) as u32) };
+
+    if device.installed {
+        device.device_type.into()
+    } else {
+        SmartDeviceType::None
+    }
+}
@@ -5,29 +5,77 @@ use core::time::Duration;
 use bitflags::bitflags;
 use snafu::Snafu;
 use vex_sdk::{
-    vexDeviceMotorAbsoluteTargetSet, vexDeviceMotorBrakeModeSet, vexDeviceMotorCurrentGet,
-    vexDeviceMotorCurrentLimitGet, vexDeviceMotorCurrentLimitSet, vexDeviceMotorEfficiencyGet,
-    vexDeviceMotorEncoderUnitsSet, vexDeviceMotorFaultsGet, vexDeviceMotorFlagsGet,
-    vexDeviceMotorGearingGet, vexDeviceMotorGearingSet, vexDeviceMotorPositionGet,
-    vexDeviceMotorPositionRawGet, vexDeviceMotorPositionReset, vexDeviceMotorPositionSet,
-    vexDeviceMotorPowerGet, vexDeviceMotorReverseFlagGet, vexDeviceMotorReverseFlagSet,
-    vexDeviceMotorTemperatureGet, vexDeviceMotorTorqueGet, vexDeviceMotorVelocityGet,
-    vexDeviceMotorVelocitySet, vexDeviceMotorVelocityUpdate, vexDeviceMotorVoltageGet,
-    vexDeviceMotorVoltageLimitGet, vexDeviceMotorVoltageLimitSet, vexDeviceMotorVoltageSet,
-    V5MotorBrakeMode, V5MotorGearset, V5_DeviceT,
+    vexDeviceMotorAbsoluteTargetSet, vexDeviceMotorActualVelocityGet, vexDeviceMotorBrakeModeSet,
+    vexDeviceMotorCurrentGet, vexDeviceMotorCurrentLimitGet, vexDeviceMotorCurrentLimitSet,
+    vexDeviceMotorEfficiencyGet, vexDeviceMotorEncoderUnitsSet, vexDeviceMotorFaultsGet,
+    vexDeviceMotorFlagsGet, vexDeviceMotorGearingGet, vexDeviceMotorGearingSet,
+    vexDeviceMotorPositionGet, vexDeviceMotorPositionRawGet, vexDeviceMotorPositionReset,
+    vexDeviceMotorPositionSet, vexDeviceMotorPowerGet, vexDeviceMotorReverseFlagGet,
+    vexDeviceMotorReverseFlagSet, vexDeviceMotorTemperatureGet, vexDeviceMotorTorqueGet,
+    vexDeviceMotorVelocityGet, vexDeviceMotorVelocitySet, vexDeviceMotorVelocityUpdate,
+    vexDeviceMotorVoltageGet, vexDeviceMotorVoltageLimitGet, vexDeviceMotorVoltageLimitSet,
+    vexDeviceMotorVoltageSet, V5MotorBrakeMode, V5MotorGearset, V5_DeviceT,
 };
 #[cfg(feature = "dangerous_motor_tuning")]
 use vex_sdk::{vexDeviceMotorPositionPidSet, vexDeviceMotorVelocityPidSet, V5_DeviceMotorPid};
+#[cfg(feature = "units")]
+use vexide_core::float::Float;
 
-use super::{SmartDevice, SmartDeviceTimestamp, SmartDeviceType, SmartPort};
+use super::{
+    reconnect::ReconnectTracker, SmartDevice, SmartDeviceTimestamp, SmartDeviceType, SmartPort,
+};
+#[cfg(feature = "units")]
+use crate::units::{AngularVelocity, Current, Temperature, Voltage};
 use crate::{position::Position, PortError};
 
 /// The basic motor struct.
+///
+/// # Reconnection
+///
+/// Motors lose their gearset, direction, and current and voltage limits when they lose power,
+/// such as when a cable comes loose. The motor keeps track of this configuration and re-applies
+/// it the next time it is used after reconnecting, so a loose cable can't silently reverse a
+/// motor. The motor's target is not re-applied, since it is usually set again every loop.
+//
+// Two motors compare equal if they're on the same port with the same target and configuration.
+// `ReconnectTracker` always compares equal, so reconnect counts and callbacks are ignored.
 #[derive(Debug, PartialEq)]
 pub struct Motor {
     port: SmartPort,
     target: MotorControl,
     device: V5_DeviceT,
+    config: MotorConfig,
+    reconnect: ReconnectTracker,
+}
+
+/// Configuration stored in a motor's firmware, which is lost when the motor loses power.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MotorConfig {
+    gearset: Gearset,
+    direction: Direction,
+    current_limit: Option<f64>,
+    voltage_limit: Option<f64>,
+}
+
+impl MotorConfig {
+    /// Applies this configuration to a motor.
+    fn apply(&self, device: V5_DeviceT) {
+        unsafe {
+            vexDeviceMotorEncoderUnitsSet(
+                device,
+                vex_sdk::V5MotorEncoderUnits::kMotorEncoderCounts,
+            );
+            vexDeviceMotorGearingSet(device, self.gearset.into());
+            vexDeviceMotorReverseFlagSet(device, self.direction.is_reverse());
+
+            if let Some(limit) = self.current_limit {
+                vexDeviceMotorCurrentLimitSet(device, (limit * 1000.0) as i32);
+            }
+            if let Some(limit) = self.voltage_limit {
+                vexDeviceMotorVoltageLimitSet(device, (limit * 1000.0) as i32);
+            }
+        }
+    }
 }
 
 // SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
@@ -104,25 +152,41 @@ impl Motor {
     pub fn new(port: SmartPort, gearset: Gearset, direction: Direction) -> Self {
         let device = unsafe { port.device_handle() }; // SAFETY: This function is only called once on this port.
 
+        let config = MotorConfig {
+            gearset,
+            direction,
+            current_limit: None,
+            voltage_limit: None,
+        };
+
         // NOTE: SDK properly stores device state when unplugged, meaning that we can safely
         // set these without consequence even if the device is not available. This is an edge
         // case for the SDK though, and seems to just be a thing for motors and rotation sensors.
-        unsafe {
-            vexDeviceMotorEncoderUnitsSet(
-                device,
-                vex_sdk::V5MotorEncoderUnits::kMotorEncoderCounts,
-            );
-            vexDeviceMotorGearingSet(device, gearset.into());
-            vexDeviceMotorReverseFlagSet(device, direction.is_reverse());
-        }
+        config.apply(device);
 
         Self {
+            reconnect: ReconnectTracker::new(port.index(), SmartDeviceType::Motor, device),
             port,
             target: MotorControl::Voltage(0.0),
             device,
+            config,
         }
     }
 
+    /// Returns the number of times the motor has been reconnected since it was created.
+    ///
+    /// Reconnections are only noticed when the motor is used, so this may lag behind the
+    /// physical connection.
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect.reconnects()
+    }
+
+    /// Sets a function to run whenever the motor reconnects, after its configuration has been
+    /// re-applied.
+    pub fn on_reconnect(&mut self, callback: impl Fn() + Send + Sync + 'static) {
+        self.reconnect.set_callback(callback);
+    }
+
     /// Sets the target that the motor should attempt to reach.
     ///
     /// This could be a voltage, velocity, position, or even brake mode.
@@ -223,6 +287,7 @@ impl Motor {
     /// Sets the gearset of the motor.
     pub fn set_gearset(&mut self, gearset: Gearset) -> Result<(), MotorError> {
         self.validate_port()?;
+        self.config.gearset = gearset;
         unsafe {
             vexDeviceMotorGearingSet(self.device, gearset.into());
         }
@@ -241,6 +306,15 @@ impl Motor {
         Ok(unsafe { vexDeviceMotorVelocityGet(self.device) })
     }
 
+    /// Gets the measured angular velocity (RPM) of the motor.
+    ///
+    /// Unlike [`Motor::velocity`], this isn't rounded to a whole RPM, so it is better suited
+    /// to measuring acceleration.
+    pub fn actual_velocity(&self) -> Result<f64, MotorError> {
+        self.validate_port()?;
+        Ok(unsafe { vexDeviceMotorActualVelocityGet(self.device) })
+    }
+
     /// Returns the power drawn by the motor in Watts.
     pub fn power(&self) -> Result<f64, MotorError> {
         self.validate_port()?;
@@ -316,6 +390,7 @@ impl Motor {
     /// Sets the current limit for the motor in amps.
     pub fn set_current_limit(&mut self, limit: f64) -> Result<(), MotorError> {
         self.validate_port()?;
+        self.config.current_limit = Some(limit);
         unsafe { vexDeviceMotorCurrentLimitSet(self.device, (limit * 1000.0) as i32) }
         Ok(())
     }
@@ -323,6 +398,7 @@ impl Motor {
     /// Sets the voltage limit for the motor in volts.
     pub fn set_voltage_limit(&mut self, limit: f64) -> Result<(), MotorError> {
         self.validate_port()?;
+        self.config.voltage_limit = Some(limit);
 
         unsafe {
             vexDeviceMotorVoltageLimitSet(self.device, (limit * 1000.0) as i32);
@@ -396,6 +472,7 @@ impl Motor {
     /// Set the [`Direction`] of this motor.
     pub fn set_direction(&mut self, direction: Direction) -> Result<(), MotorError> {
         self.validate_port()?;
+        self.config.direction = direction;
 
         unsafe {
             vexDeviceMotorReverseFlagSet(self.device, direction.is_reverse());
@@ -463,6 +540,63 @@ impl Motor {
     }
 }
 
+#[cfg(feature = "units")]
+impl Motor {
+    /// Spins the motor at a target velocity.
+    ///
+    /// This is equivalent to [`Motor::set_velocity`], with the velocity rounded to the nearest RPM.
+    pub fn set_velocity_typed(&mut self, velocity: AngularVelocity) -> Result<(), MotorError> {
+        self.set_velocity(velocity.as_rpm().round() as i32)
+    }
+
+    /// Sets the motor's ouput voltage.
+    ///
+    /// This is equivalent to [`Motor::set_voltage`].
+    pub fn set_voltage_typed(&mut self, voltage: Voltage) -> Result<(), MotorError> {
+        self.set_voltage(voltage.as_volts())
+    }
+
+    /// Gets the estimated angular velocity of the motor.
+    pub fn velocity_typed(&self) -> Result<AngularVelocity, MotorError> {
+        Ok(AngularVelocity::from_rpm(self.velocity()? as f64))
+    }
+
+    /// Returns the voltage the motor is drawing.
+    pub fn voltage_typed(&self) -> Result<Voltage, MotorError> {
+        Ok(Voltage::from_volts(self.voltage()?))
+    }
+
+    /// Returns the electrical current draw of the motor.
+    pub fn current_typed(&self) -> Result<Current, MotorError> {
+        Ok(Current::from_amps(self.current()?))
+    }
+
+    /// Sets the current limit for the motor.
+    pub fn set_current_limit_typed(&mut self, limit: Current) -> Result<(), MotorError> {
+        self.set_current_limit(limit.as_amps())
+    }
+
+    /// Sets the voltage limit for the motor.
+    pub fn set_voltage_limit_typed(&mut self, limit: Voltage) -> Result<(), MotorError> {
+        self.set_voltage_limit(limit.as_volts())
+    }
+
+    /// Gets the current limit for the motor.
+    pub fn current_limit_typed(&self) -> Result<Current, MotorError> {
+        Ok(Current::from_amps(self.current_limit()?))
+    }
+
+    /// Gets the voltage limit for the motor if one has been explicitly set.
+    pub fn voltage_limit_typed(&self) -> Result<Voltage, MotorError> {
+        Ok(Voltage::from_volts(self.voltage_limit()?))
+    }
+
+    /// Returns the internal teperature recorded by the motor in increments of 5°C.
+    pub fn temperature_typed(&self) -> Result<Temperature, MotorError> {
+        Ok(Temperature::from_celsius(self.temperature()?))
+    }
+}
+
 impl SmartDevice for Motor {
     fn port_index(&self) -> u8 {
         self.port.index()
@@ -471,6 +605,13 @@ impl SmartDevice for Motor {
     fn device_type(&self) -> SmartDeviceType {
         SmartDeviceType::Motor
     }
+
+    fn validate_port(&self) -> Result<(), PortError> {
+        self.reconnect
+            .check(self.port_index(), self.device_type(), self.device, || {
+                self.config.apply(self.device);
+            })
+    }
 }
 
 /// Determines how a motor should act when braking.
@@ -0,0 +1,802 @@
+//! Motor health monitoring.
+//!
+//! V5 motors protect themselves by throttling their output as they heat up, and cut out
+//! entirely if they get too hot. This usually happens partway through a match, after a
+//! mechanism has been stalled against a wall or a drivetrain has been pushing for too long.
+//!
+//! A [`MotorHealthMonitor`] watches a set of motors for these problems and reports them as
+//! [`MotorHealthEvent`]s:
+//!
+//! - **Stalls**, where a motor draws a high current without moving.
+//! - **Overheating**, predicting how long is left before a motor reaches the temperature at
+//!   which it starts throttling.
+//! - **Faults** reported by the motor's firmware.
+//!
+//! The monitor can also *derate* hot motors by lowering their current limit, reducing the heat
+//! they produce before the firmware throttles them, and restoring the limit once they cool.
+//! If a [`CurrentBudget`](super::current_budget::CurrentBudget) also sets the motors' current
+//! limits, apply it with
+//! [`CurrentBudget::apply_with_health`](super::current_budget::CurrentBudget::apply_with_health)
+//! so that it keeps derated motors at their lowered limit, and so that they are restored to
+//! the budget's latest limit.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! let mut health = MotorHealthMonitor::new(MotorHealthConfig {
+//!     derating: Some(Derating::default()),
+//!     ..Default::default()
+//! });
+//!
+//! loop {
+//!     for event in health.update(&mut [&mut left_motor, &mut right_motor]) {
+//!         println!("{event:?}");
+//!     }
+//!
+//!     sleep(Motor::DATA_READ_INTERVAL).await;
+//! }
+//! ```
+
+extern crate alloc;
+
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap},
+    vec::Vec,
+};
+use core::time::Duration;
+
+use vexide_core::time::Instant;
+
+use super::{
+    motor::{Motor, MotorFaults},
+    SmartDevice,
+};
+
+/// Thresholds used by a [`MotorHealthMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorHealthConfig {
+    /// The current (in amps) above which a motor that isn't moving is considered stalled.
+    pub stall_current: f64,
+
+    /// The velocity (in RPM) below which a motor is considered not to be moving.
+    pub stall_velocity: i32,
+
+    /// How long a motor must meet the stall conditions before a stall is reported.
+    pub stall_time: Duration,
+
+    /// The temperature (in degrees Celsius) at which the motor's firmware starts throttling
+    /// its output.
+    pub throttle_temperature: f64,
+
+    /// How far ahead of reaching [`MotorHealthConfig::throttle_temperature`] a
+    /// [`MotorHealthEvent::Overheating`] warning is raised.
+    pub warning_time: Duration,
+
+    /// How to derate hot motors, or `None` to never change their current limits.
+    pub derating: Option<Derating>,
+}
+
+impl Default for MotorHealthConfig {
+    fn default() -> Self {
+        Self {
+            stall_current: 2.0,
+            stall_velocity: 5,
+            stall_time: Duration::from_millis(250),
+            throttle_temperature: 55.0,
+            warning_time: Duration::from_secs(20),
+            derating: None,
+        }
+    }
+}
+
+/// How a [`MotorHealthMonitor`] derates hot motors.
+///
+/// Motors report their temperature in increments of 5°C, so temperatures here should be
+/// multiples of 5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Derating {
+    /// The temperature (in degrees Celsius) at which a motor's current limit is lowered.
+    pub temperature: f64,
+
+    /// The current limit (in amps) applied to a derated motor.
+    pub current_limit: f64,
+
+    /// The temperature (in degrees Celsius) at or below which a derated motor's original
+    /// current limit is restored.
+    pub recovery_temperature: f64,
+}
+
+impl Default for Derating {
+    fn default() -> Self {
+        Self {
+            temperature: 50.0,
+            current_limit: 1.25,
+            recovery_temperature: 45.0,
+        }
+    }
+}
+
+/// An event raised by a [`MotorHealthMonitor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MotorHealthEvent {
+    /// The motor has been drawing a high current without moving for at least
+    /// [`MotorHealthConfig::stall_time`].
+    Stalled {
+        /// The port of the motor.
+        port: u8,
+
+        /// The current drawn by the motor in amps.
+        current: f64,
+    },
+
+    /// A stalled motor has started moving or stopped drawing a high current.
+    StallCleared {
+        /// The port of the motor.
+        port: u8,
+    },
+
+    /// The motor is expected to start throttling within [`MotorHealthConfig::warning_time`],
+    /// or already has.
+    ///
+    /// This is raised once each time the motor's temperature rises while it is overheating.
+    Overheating {
+        /// The port of the motor.
+        port: u8,
+
+        /// The temperature of the motor in degrees Celsius.
+        temperature: f64,
+
+        /// The estimated time until the motor starts throttling, or zero if it already has.
+        time_to_throttle: Duration,
+    },
+
+    /// The motor's firmware reported new faults.
+    Fault {
+        /// The port of the motor.
+        port: u8,
+
+        /// The faults that were newly reported.
+        faults: MotorFaults,
+    },
+
+    /// The motor's current limit was lowered because it is hot.
+    Derated {
+        /// The port of the motor.
+        port: u8,
+
+        /// The new current limit in amps.
+        current_limit: f64,
+    },
+
+    /// A derated motor has cooled down and its original current limit was restored.
+    Restored {
+        /// The port of the motor.
+        port: u8,
+
+        /// The restored current limit in amps.
+        current_limit: f64,
+    },
+}
+
+impl MotorHealthEvent {
+    /// Returns the port of the motor that raised this event.
+    pub const fn port(&self) -> u8 {
+        match self {
+            Self::Stalled { port, .. }
+            | Self::StallCleared { port }
+            | Self::Overheating { port, .. }
+            | Self::Fault { port, .. }
+            | Self::Derated { port, .. }
+            | Self::Restored { port, .. } => *port,
+        }
+    }
+}
+
+/// A snapshot of a motor's health, as of the last [`MotorHealthMonitor::update`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorHealth {
+    /// The temperature of the motor in degrees Celsius.
+    pub temperature: f64,
+
+    /// The current drawn by the motor in amps.
+    pub current: f64,
+
+    /// The faults reported by the motor.
+    pub faults: MotorFaults,
+
+    /// Whether the motor is stalled.
+    pub stalled: bool,
+
+    /// The original current limit of the motor if it is derated, or `None` if it isn't.
+    pub derated_from: Option<f64>,
+
+    /// The estimated time until the motor starts throttling, or `None` if its temperature
+    /// isn't rising.
+    pub time_to_throttle: Option<Duration>,
+}
+
+/// A single read of a motor's sensors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MotorReading {
+    temperature: f64,
+    current: f64,
+    velocity: i32,
+    faults: MotorFaults,
+}
+
+/// A change to a motor's current limit requested by [`Derating`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DerateAction {
+    /// Lower the motor's current limit.
+    Derate,
+
+    /// Restore the motor's original current limit.
+    Restore(f64),
+}
+
+/// The state tracked for each motor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MotorState {
+    health: MotorHealth,
+
+    /// How long the motor has met the stall conditions, or `None` if it currently doesn't.
+    stalled_for: Option<Duration>,
+
+    /// How long ago the motor's reported temperature last changed, or `None` if it hasn't
+    /// changed since the motor was registered.
+    since_temperature_change: Option<Duration>,
+}
+
+impl MotorState {
+    /// Creates the state for a newly registered motor.
+    const fn new(reading: &MotorReading) -> Self {
+        Self {
+            health: MotorHealth {
+                temperature: reading.temperature,
+                current: reading.current,
+                faults: MotorFaults::empty(),
+                stalled: false,
+                derated_from: None,
+                time_to_throttle: None,
+            },
+            stalled_for: None,
+            since_temperature_change: None,
+        }
+    }
+
+    /// Updates the state with a new reading taken `elapsed` after the previous one.
+    fn step(
+        &mut self,
+        config: &MotorHealthConfig,
+        port: u8,
+        reading: MotorReading,
+        elapsed: Duration,
+        events: &mut Vec<MotorHealthEvent>,
+    ) {
+        let MotorReading {
+            temperature,
+            current,
+            velocity,
+            faults,
+        } = reading;
+
+        // Faults
+        let new_faults = faults - self.health.faults;
+        if !new_faults.is_empty() {
+            events.push(MotorHealthEvent::Fault {
+                port,
+                faults: new_faults,
+            });
+        }
+        self.health.faults = faults;
+        self.health.current = current;
+
+        // Stalls
+        if current >= config.stall_current && velocity.abs() <= config.stall_velocity {
+            let stalled_for = self
+                .stalled_for
+                .map_or(Duration::ZERO, |stalled_for| stalled_for + elapsed);
+            self.stalled_for = Some(stalled_for);
+
+            if !self.health.stalled && stalled_for >= config.stall_time {
+                self.health.stalled = true;
+                events.push(MotorHealthEvent::Stalled { port, current });
+            }
+        } else {
+            self.stalled_for = None;
+
+            if self.health.stalled {
+                self.health.stalled = false;
+                events.push(MotorHealthEvent::StallCleared { port });
+            }
+        }
+
+        // Temperature trend. Temperatures are reported in 5°C steps, so the heating rate is
+        // measured between changes rather than between updates. The first change only starts
+        // the measurement, since the temperature may have been about to change when the motor
+        // was registered.
+        if let Some(since) = &mut self.since_temperature_change {
+            *since += elapsed;
+        }
+
+        if temperature != self.health.temperature {
+            let heating_rate = self
+                .since_temperature_change
+                .filter(|since| !since.is_zero())
+                .map(|since| (temperature - self.health.temperature) / since.as_secs_f64());
+            self.since_temperature_change = Some(Duration::ZERO);
+
+            let rising = temperature > self.health.temperature;
+            self.health.temperature = temperature;
+
+            self.health.time_to_throttle = if temperature >= config.throttle_temperature {
+                Some(Duration::ZERO)
+            } else {
+                heating_rate.filter(|&rate| rate > 0.0).map(|rate| {
+                    Duration::from_secs_f64((config.throttle_temperature - temperature) / rate)
+                })
+            };
+
+            if let Some(time_to_throttle) = self.health.time_to_throttle {
+                if rising && time_to_throttle <= config.warning_time {
+                    events.push(MotorHealthEvent::Overheating {
+                        port,
+                        temperature,
+                        time_to_throttle,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the change to the motor's current limit needed for its temperature, if any.
+    fn derate_action(&self, derating: &Derating) -> Option<DerateAction> {
+        match self.health.derated_from {
+            None if self.health.temperature >= derating.temperature => Some(DerateAction::Derate),
+            Some(original_limit) if self.health.temperature <= derating.recovery_temperature => {
+                Some(DerateAction::Restore(original_limit))
+            }
+            _ => None,
+        }
+    }
+
+    /// Records that the motor's current limit was lowered from `original_limit`.
+    fn derated(
+        &mut self,
+        port: u8,
+        original_limit: f64,
+        current_limit: f64,
+        events: &mut Vec<MotorHealthEvent>,
+    ) {
+        self.health.derated_from = Some(original_limit);
+        events.push(MotorHealthEvent::Derated {
+            port,
+            current_limit,
+        });
+    }
+
+    /// Records that the motor's original current limit was restored.
+    fn restored(&mut self, port: u8, events: &mut Vec<MotorHealthEvent>) {
+        if let Some(current_limit) = self.health.derated_from.take() {
+            events.push(MotorHealthEvent::Restored {
+                port,
+                current_limit,
+            });
+        }
+    }
+}
+
+/// Watches motors for stalls, overheating and faults.
+///
+/// Motors are tracked by port, and are registered the first time they are passed to
+/// [`MotorHealthMonitor::update`]. Motors that can't be read, such as ones that have been
+/// unplugged, are skipped until they can be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotorHealthMonitor {
+    config: MotorHealthConfig,
+    motors: BTreeMap<u8, MotorState>,
+    last_update: Option<Instant>,
+}
+
+impl MotorHealthMonitor {
+    /// Creates a new monitor with the given thresholds.
+    pub const fn new(config: MotorHealthConfig) -> Self {
+        Self {
+            config,
+            motors: BTreeMap::new(),
+            last_update: None,
+        }
+    }
+
+    /// Returns the thresholds used by this monitor.
+    pub const fn config(&self) -> &MotorHealthConfig {
+        &self.config
+    }
+
+    /// Returns the health of the motor on a port, or `None` if no motor on that port has been
+    /// read yet.
+    pub fn health(&self, port: u8) -> Option<MotorHealth> {
+        self.motors.get(&port).map(|state| state.health)
+    }
+
+    /// Stops tracking the motor on a port.
+    ///
+    /// This doesn't restore its current limit if it is derated.
+    pub fn forget(&mut self, port: u8) {
+        self.motors.remove(&port);
+    }
+
+    /// Limits a current limit chosen elsewhere, such as by a
+    /// [`CurrentBudget`](super::current_budget::CurrentBudget), to the derated limit if the
+    /// motor on `port` is derated.
+    ///
+    /// The given limit is the one restored once the motor cools down, replacing the limit it
+    /// had when it was derated.
+    pub(super) fn cap_current_limit(&mut self, port: u8, limit: f64) -> f64 {
+        let (Some(derating), Some(state)) = (self.config.derating, self.motors.get_mut(&port))
+        else {
+            return limit;
+        };
+
+        match &mut state.health.derated_from {
+            Some(original_limit) => {
+                *original_limit = limit;
+                limit.min(derating.current_limit)
+            }
+            None => limit,
+        }
+    }
+
+    /// Reads the state of each motor, returning any events raised since the last update.
+    ///
+    /// This should be called regularly, such as every [`Motor::DATA_READ_INTERVAL`]. Stalls
+    /// and temperature trends are measured across calls.
+    pub fn update(&mut self, motors: &mut [&mut Motor]) -> Vec<MotorHealthEvent> {
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map_or(Duration::ZERO, |last_update| now - last_update);
+        self.last_update = Some(now);
+
+        let mut events = Vec::new();
+
+        for motor in motors.iter_mut() {
+            self.update_motor(motor, elapsed, &mut events);
+        }
+
+        events
+    }
+
+    fn update_motor(
+        &mut self,
+        motor: &mut Motor,
+        elapsed: Duration,
+        events: &mut Vec<MotorHealthEvent>,
+    ) {
+        let port = motor.port_index();
+
+        let (Ok(temperature), Ok(current), Ok(velocity), Ok(faults)) = (
+            motor.temperature(),
+            motor.current(),
+            motor.velocity(),
+            motor.faults(),
+        ) else {
+            return;
+        };
+        let reading = MotorReading {
+            temperature,
+            current,
+            velocity,
+            faults,
+        };
+
+        // Newly registered motors have nothing to measure trends against yet.
+        let (state, elapsed) = match self.motors.entry(port) {
+            Entry::Occupied(entry) => (entry.into_mut(), elapsed),
+            Entry::Vacant(entry) => (entry.insert(MotorState::new(&reading)), Duration::ZERO),
+        };
+        state.step(&self.config, port, reading, elapsed, events);
+
+        let Some(derating) = self.config.derating else {
+            return;
+        };
+
+        match state.derate_action(&derating) {
+            Some(DerateAction::Derate) => {
+                let Ok(original_limit) = motor.current_limit() else {
+                    return;
+                };
+
+                if motor.set_current_limit(derating.current_limit).is_ok() {
+                    state.derated(port, original_limit, derating.current_limit, events);
+                }
+            }
+            Some(DerateAction::Restore(original_limit)) => {
+                if motor.set_current_limit(original_limit).is_ok() {
+                    state.restored(port, events);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PORT: u8 = 1;
+    const TICK: Duration = Duration::from_millis(10);
+
+    const fn reading(temperature: f64, current: f64, velocity: i32) -> MotorReading {
+        MotorReading {
+            temperature,
+            current,
+            velocity,
+            faults: MotorFaults::empty(),
+        }
+    }
+
+    /// Steps `state` with the same reading every [`TICK`] for `duration`.
+    fn run(
+        state: &mut MotorState,
+        config: &MotorHealthConfig,
+        reading: MotorReading,
+        duration: Duration,
+    ) -> Vec<MotorHealthEvent> {
+        let mut events = Vec::new();
+        for _ in 0..(duration.as_millis() / TICK.as_millis()) {
+            state.step(config, PORT, reading, TICK, &mut events);
+        }
+        events
+    }
+
+    #[test]
+    fn stall_reported_after_stall_time() {
+        let config = MotorHealthConfig::default();
+        let stalled = reading(30.0, 2.5, 0);
+        let mut state = MotorState::new(&stalled);
+
+        assert!(run(&mut state, &config, stalled, Duration::from_millis(200)).is_empty());
+        assert!(!state.health.stalled);
+
+        let events = run(&mut state, &config, stalled, Duration::from_millis(100));
+        assert_eq!(
+            events,
+            [MotorHealthEvent::Stalled {
+                port: PORT,
+                current: 2.5
+            }]
+        );
+        assert!(state.health.stalled);
+
+        // Reported only once.
+        assert!(run(&mut state, &config, stalled, Duration::from_secs(1)).is_empty());
+
+        let events = run(&mut state, &config, reading(30.0, 2.5, 100), TICK);
+        assert_eq!(events, [MotorHealthEvent::StallCleared { port: PORT }]);
+    }
+
+    #[test]
+    fn brief_stall_restarts_timer() {
+        let config = MotorHealthConfig::default();
+        let stalled = reading(30.0, 2.5, 0);
+        let mut state = MotorState::new(&stalled);
+
+        run(&mut state, &config, stalled, Duration::from_millis(200));
+        run(&mut state, &config, reading(30.0, 0.5, 0), TICK);
+
+        assert!(run(&mut state, &config, stalled, Duration::from_millis(200)).is_empty());
+        assert!(!state.health.stalled);
+    }
+
+    #[test]
+    fn first_temperature_change_has_no_rate() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(40.0, 0.0, 0));
+
+        // The motor may have been about to change temperature when it was registered, so
+        // this change alone doesn't say how fast it's heating.
+        run(
+            &mut state,
+            &config,
+            reading(40.0, 0.0, 0),
+            Duration::from_millis(100),
+        );
+        let events = run(&mut state, &config, reading(45.0, 0.0, 0), TICK);
+
+        assert!(events.is_empty());
+        assert_eq!(state.health.time_to_throttle, None);
+    }
+
+    #[test]
+    fn heating_rate_measured_between_changes() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(35.0, 0.0, 0));
+
+        run(&mut state, &config, reading(40.0, 0.0, 0), TICK);
+        // 5°C in 8 seconds, leaving 10°C (16 seconds) until throttling at 55°C.
+        run(
+            &mut state,
+            &config,
+            reading(40.0, 0.0, 0),
+            Duration::from_secs(8) - TICK,
+        );
+        let events = run(&mut state, &config, reading(45.0, 0.0, 0), TICK);
+
+        let time_to_throttle = state.health.time_to_throttle.unwrap().as_secs_f64();
+        assert!((time_to_throttle - 16.0).abs() < 1e-3);
+        assert!(matches!(
+            events[..],
+            [MotorHealthEvent::Overheating {
+                port: PORT,
+                temperature: 45.0,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn slow_heating_doesnt_warn() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(35.0, 0.0, 0));
+
+        run(&mut state, &config, reading(40.0, 0.0, 0), TICK);
+        run(
+            &mut state,
+            &config,
+            reading(40.0, 0.0, 0),
+            Duration::from_secs(60),
+        );
+        let events = run(&mut state, &config, reading(45.0, 0.0, 0), TICK);
+
+        assert!(events.is_empty());
+        assert!(state.health.time_to_throttle.unwrap() > config.warning_time);
+    }
+
+    #[test]
+    fn cooling_clears_time_to_throttle() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(35.0, 0.0, 0));
+
+        run(&mut state, &config, reading(40.0, 0.0, 0), TICK);
+        run(
+            &mut state,
+            &config,
+            reading(45.0, 0.0, 0),
+            Duration::from_secs(1),
+        );
+        assert!(state.health.time_to_throttle.is_some());
+
+        run(
+            &mut state,
+            &config,
+            reading(40.0, 0.0, 0),
+            Duration::from_secs(1),
+        );
+        assert_eq!(state.health.time_to_throttle, None);
+    }
+
+    #[test]
+    fn throttling_reported_immediately() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(50.0, 0.0, 0));
+
+        let events = run(&mut state, &config, reading(55.0, 0.0, 0), TICK);
+
+        assert_eq!(
+            events,
+            [MotorHealthEvent::Overheating {
+                port: PORT,
+                temperature: 55.0,
+                time_to_throttle: Duration::ZERO,
+            }]
+        );
+    }
+
+    #[test]
+    fn derating_hysteresis() {
+        let config = MotorHealthConfig::default();
+        let derating = Derating::default();
+        let mut state = MotorState::new(&reading(40.0, 0.0, 0));
+        let mut events = Vec::new();
+
+        // Steps through temperatures, applying any derating as if setting the limit worked.
+        let mut step_to = |state: &mut MotorState, temperature: f64| {
+            state.step(
+                &config,
+                PORT,
+                reading(temperature, 0.0, 0),
+                TICK,
+                &mut events,
+            );
+            match state.derate_action(&derating) {
+                Some(DerateAction::Derate) => {
+                    state.derated(PORT, 2.5, derating.current_limit, &mut events);
+                }
+                Some(DerateAction::Restore(_)) => state.restored(PORT, &mut events),
+                None => {}
+            }
+        };
+
+        step_to(&mut state, 45.0);
+        assert_eq!(state.health.derated_from, None);
+
+        step_to(&mut state, 50.0);
+        assert_eq!(state.health.derated_from, Some(2.5));
+
+        // Cooling below the derating temperature isn't enough to restore the limit.
+        step_to(&mut state, 50.0);
+        step_to(&mut state, 47.5);
+        assert_eq!(state.health.derated_from, Some(2.5));
+
+        step_to(&mut state, 45.0);
+        assert_eq!(state.health.derated_from, None);
+
+        // Heating back up past the recovery temperature doesn't derate again until the
+        // derating temperature.
+        step_to(&mut state, 47.5);
+        assert_eq!(state.health.derated_from, None);
+
+        // The quick temperature changes also raise overheating warnings, which aren't relevant.
+        events.retain(|event| {
+            matches!(
+                event,
+                MotorHealthEvent::Derated { .. } | MotorHealthEvent::Restored { .. }
+            )
+        });
+        assert_eq!(
+            events,
+            [
+                MotorHealthEvent::Derated {
+                    port: PORT,
+                    current_limit: derating.current_limit,
+                },
+                MotorHealthEvent::Restored {
+                    port: PORT,
+                    current_limit: 2.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn budget_limit_capped_while_derated() {
+        let mut monitor = MotorHealthMonitor::new(MotorHealthConfig {
+            derating: Some(Derating::default()),
+            ..Default::default()
+        });
+        let mut state = MotorState::new(&reading(50.0, 0.0, 0));
+        state.health.derated_from = Some(2.5);
+        monitor.motors.insert(PORT, state);
+
+        assert_eq!(monitor.cap_current_limit(PORT, 2.0), 1.25);
+        assert_eq!(monitor.health(PORT).unwrap().derated_from, Some(2.0));
+        assert_eq!(monitor.cap_current_limit(PORT, 1.0), 1.0);
+        assert_eq!(monitor.health(PORT).unwrap().derated_from, Some(1.0));
+
+        // Motors that aren't derated or tracked keep the budget's limit.
+        monitor.motors.get_mut(&PORT).unwrap().health.derated_from = None;
+        assert_eq!(monitor.cap_current_limit(PORT, 2.0), 2.0);
+        assert_eq!(monitor.cap_current_limit(PORT + 1, 2.0), 2.0);
+    }
+
+    #[test]
+    fn new_faults_reported_once() {
+        let config = MotorHealthConfig::default();
+        let mut state = MotorState::new(&reading(30.0, 0.0, 0));
+        let faulted = MotorReading {
+            faults: MotorFaults::OVER_TEMPERATURE,
+            ..reading(30.0, 0.0, 0)
+        };
+
+        let events = run(&mut state, &config, faulted, Duration::from_millis(30));
+        assert_eq!(
+            events,
+            [MotorHealthEvent::Fault {
+                port: PORT,
+                faults: MotorFaults::OVER_TEMPERATURE,
+            }]
+        );
+    }
+}
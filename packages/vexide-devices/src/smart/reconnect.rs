@@ -0,0 +1,196 @@
+//! Reconnection tracking for smart devices.
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use vex_sdk::{vexDeviceGetTimestamp, V5_DeviceT};
+
+use super::{validate_port, SmartDeviceType};
+use crate::PortError;
+
+/// Detects when a smart device has been unplugged and plugged back in.
+///
+/// Devices that keep configuration in firmware (such as motors) lose it when they lose power,
+/// so they use this to know when to re-apply it. A reconnection is noticed either by seeing
+/// the device disconnected, or by the device's internal clock going backwards, which happens
+/// when it restarts between two checks.
+pub(crate) struct ReconnectTracker {
+    last_timestamp: AtomicU32,
+    disconnected: AtomicBool,
+    reconnects: AtomicU32,
+    callback: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl ReconnectTracker {
+    /// Creates a new tracker for a device.
+    pub fn new(port_index: u8, device_type: SmartDeviceType, device: V5_DeviceT) -> Self {
+        let timestamp = validate_port(port_index, device_type)
+            .ok()
+            .map(|()| unsafe { vexDeviceGetTimestamp(device) });
+
+        Self::with_timestamp(timestamp)
+    }
+
+    /// Creates a new tracker for a device whose clock reads `timestamp`, or `None` if the
+    /// device is disconnected.
+    ///
+    /// A device that starts out disconnected counts as reconnecting once it is first seen.
+    const fn with_timestamp(timestamp: Option<u32>) -> Self {
+        let (last_timestamp, disconnected) = match timestamp {
+            Some(timestamp) => (timestamp, false),
+            None => (0, true),
+        };
+
+        Self {
+            last_timestamp: AtomicU32::new(last_timestamp),
+            disconnected: AtomicBool::new(disconnected),
+            reconnects: AtomicU32::new(0),
+            callback: None,
+        }
+    }
+
+    /// Validates that the device is connected.
+    ///
+    /// If the device has reconnected since the last check, `reconfigure` is called to re-apply
+    /// its configuration, followed by the reconnect callback.
+    pub fn check(
+        &self,
+        port_index: u8,
+        device_type: SmartDeviceType,
+        device: V5_DeviceT,
+        reconfigure: impl FnOnce(),
+    ) -> Result<(), PortError> {
+        if let Err(err) = validate_port(port_index, device_type) {
+            self.observe(None);
+            return Err(err);
+        }
+
+        let timestamp = unsafe { vexDeviceGetTimestamp(device) };
+
+        if self.observe(Some(timestamp)) {
+            reconfigure();
+
+            if let Some(callback) = &self.callback {
+                callback();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records a check of the device, returning `true` if it has reconnected since the
+    /// previous check.
+    ///
+    /// `timestamp` is the device's internal clock, or `None` if the device is disconnected.
+    fn observe(&self, timestamp: Option<u32>) -> bool {
+        let Some(timestamp) = timestamp else {
+            self.disconnected.store(true, Ordering::Relaxed);
+            return false;
+        };
+
+        let last_timestamp = self.last_timestamp.swap(timestamp, Ordering::Relaxed);
+        let reconnected =
+            self.disconnected.swap(false, Ordering::Relaxed) || timestamp < last_timestamp;
+
+        if reconnected {
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+
+        reconnected
+    }
+
+    /// Returns the number of times the device has reconnected.
+    pub fn reconnects(&self) -> u32 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Sets a function to run whenever the device reconnects.
+    pub fn set_callback(&mut self, callback: impl Fn() + Send + Sync + 'static) {
+        self.callback = Some(Box::new(callback));
+    }
+}
+
+impl fmt::Debug for ReconnectTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectTracker")
+            .field("reconnects", &self.reconnects())
+            .field("has_callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for ReconnectTracker {
+    fn eq(&self, _other: &Self) -> bool {
+        // Reconnection bookkeeping doesn't affect whether two devices are equal.
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn steady_connection_is_not_a_reconnect() {
+        let tracker = ReconnectTracker::with_timestamp(Some(100));
+
+        assert!(!tracker.observe(Some(110)));
+        assert!(!tracker.observe(Some(110)));
+        assert!(!tracker.observe(Some(120)));
+        assert_eq!(tracker.reconnects(), 0);
+    }
+
+    #[test]
+    fn reconnects_after_disconnect() {
+        let tracker = ReconnectTracker::with_timestamp(Some(100));
+
+        assert!(!tracker.observe(None));
+        assert!(!tracker.observe(None));
+        assert!(tracker.observe(Some(200)));
+        assert!(!tracker.observe(Some(210)));
+        assert_eq!(tracker.reconnects(), 1);
+    }
+
+    #[test]
+    fn reconnects_when_clock_goes_backwards() {
+        // The device restarted between two checks without ever being seen disconnected.
+        let tracker = ReconnectTracker::with_timestamp(Some(5000));
+
+        assert!(tracker.observe(Some(20)));
+        assert!(!tracker.observe(Some(30)));
+        assert_eq!(tracker.reconnects(), 1);
+    }
+
+    #[test]
+    fn disconnect_and_restart_count_once() {
+        let tracker = ReconnectTracker::with_timestamp(Some(5000));
+
+        tracker.observe(None);
+        assert!(tracker.observe(Some(20)));
+        assert_eq!(tracker.reconnects(), 1);
+    }
+
+    #[test]
+    fn reconnects_when_first_seen_after_starting_disconnected() {
+        let tracker = ReconnectTracker::with_timestamp(None);
+
+        assert!(!tracker.observe(None));
+        assert!(tracker.observe(Some(50)));
+        assert!(!tracker.observe(Some(60)));
+        assert_eq!(tracker.reconnects(), 1);
+    }
+
+    #[test]
+    fn trackers_compare_equal() {
+        let tracker = ReconnectTracker::with_timestamp(Some(0));
+        tracker.observe(None);
+        tracker.observe(Some(10));
+
+        assert_eq!(tracker, ReconnectTracker::with_timestamp(Some(1000)));
+    }
+}
@@ -9,10 +9,20 @@ use vex_sdk::{
     vexDeviceAbsEncPositionSet, vexDeviceAbsEncStatusGet, vexDeviceAbsEncVelocityGet, V5_DeviceT,
 };
 
-use super::{motor::Direction, SmartDevice, SmartDeviceType, SmartPort};
+use super::{
+    motor::Direction, reconnect::ReconnectTracker, SmartDevice, SmartDeviceType, SmartPort,
+};
+#[cfg(feature = "units")]
+use crate::units::{Angle, AngularVelocity};
 use crate::{position::Position, PortError};
 
 /// A physical rotation sensor plugged into a port.
+///
+/// # Reconnection
+///
+/// Rotation sensors lose their data rate when they lose power, such as when a cable comes
+/// loose. The sensor keeps track of the data rate set with [`RotationSensor::set_data_rate`]
+/// and re-applies it the next time it is used after reconnecting.
 #[derive(Debug, PartialEq)]
 pub struct RotationSensor {
     /// Smart Port
@@ -29,6 +39,12 @@ pub struct RotationSensor {
 
     /// The raw position data recorded by the SDK at the time the sensor is reversed.
     raw_direction_offset: Position,
+
+    /// The data rate in milliseconds set by [`Self::set_data_rate`], re-applied on reconnection.
+    data_rate: Option<u32>,
+
+    /// Detects when the sensor has been reconnected.
+    reconnect: ReconnectTracker,
 }
 
 // SAFETY: Required because we store a raw pointer to the device handle to avoid it getting from the
@@ -49,14 +65,30 @@ impl RotationSensor {
         let device = unsafe { port.device_handle() };
 
         Self {
+            reconnect: ReconnectTracker::new(port.index(), SmartDeviceType::Rotation, device),
             device,
             port,
             direction,
             direction_offset: Position::default(),
             raw_direction_offset: Position::default(),
+            data_rate: None,
         }
     }
 
+    /// Returns the number of times the sensor has been reconnected since it was created.
+    ///
+    /// Reconnections are only noticed when the sensor is used, so this may lag behind the
+    /// physical connection.
+    pub fn reconnect_count(&self) -> u32 {
+        self.reconnect.reconnects()
+    }
+
+    /// Sets a function to run whenever the sensor reconnects, after its configuration has been
+    /// re-applied.
+    pub fn on_reconnect(&mut self, callback: impl Fn() + Send + Sync + 'static) {
+        self.reconnect.set_callback(callback);
+    }
+
     /// Sets the position to zero.
     pub fn reset_position(&mut self) -> Result<(), PortError> {
         // NOTE: We don't use vexDeviceAbsEncReset, since that doesn't actually
@@ -127,6 +159,7 @@ impl RotationSensor {
             .as_millis()
             .max(Self::MIN_DATA_INTERVAL.as_millis()) as u32;
         time_ms -= time_ms % 5; // Rate is in increments of 5ms - not sure if this is necessary, but PROS does it.
+        self.data_rate = Some(time_ms);
 
         unsafe { vexDeviceAbsEncDataRateSet(self.device, time_ms) }
 
@@ -195,6 +228,26 @@ impl RotationSensor {
     }
 }
 
+#[cfg(feature = "units")]
+impl RotationSensor {
+    /// Get the total angle rotated by the sensor based on direction.
+    pub fn position_typed(&self) -> Result<Angle, PortError> {
+        Ok(self.position()?.into())
+    }
+
+    /// Get the angle of rotation measured by the sensor.
+    ///
+    /// This value is reported from 0-360 degrees.
+    pub fn angle_typed(&self) -> Result<Angle, PortError> {
+        Ok(self.angle()?.into())
+    }
+
+    /// Get the sensor's current velocity.
+    pub fn velocity_typed(&self) -> Result<AngularVelocity, PortError> {
+        Ok(AngularVelocity::from_degrees_per_second(self.velocity()?))
+    }
+}
+
 impl SmartDevice for RotationSensor {
     fn port_index(&self) -> u8 {
         self.port.index()
@@ -203,4 +256,13 @@ impl SmartDevice for RotationSensor {
     fn device_type(&self) -> SmartDeviceType {
         SmartDeviceType::Rotation
     }
+
+    fn validate_port(&self) -> Result<(), PortError> {
+        self.reconnect
+            .check(self.port_index(), self.device_type(), self.device, || {
+                if let Some(data_rate) = self.data_rate {
+                    unsafe { vexDeviceAbsEncDataRateSet(self.device, data_rate) }
+                }
+            })
+    }
 }
@@ -0,0 +1,562 @@
+//! Feedforward characterization for motors and drivetrains.
+//!
+//! A mechanism driven by DC motors follows the feedforward model
+//!
+//! ```text
+//! V = kS * sign(v) + kV * v + kA * a
+//! ```
+//!
+//! where `V` is the applied voltage, `v` is velocity and `a` is acceleration. Rather than
+//! guessing `kS`, `kV` and `kA`, this module measures them by driving the motors with known
+//! voltages and recording how they respond:
+//!
+//! - A *quasistatic* test slowly ramps the voltage up, so the mechanism barely accelerates and
+//!   the relationship between voltage and velocity can be measured on its own.
+//! - A *dynamic* test applies a sudden voltage step, so the mechanism accelerates hard and the
+//!   effect of acceleration can be measured.
+//!
+//! [`FeedforwardGains::fit`] then fits the gains to the recorded [`SysIdLog`]s by least
+//! squares. Each log can also be saved to the SD card as CSV for analysis elsewhere.
+//!
+//! Velocities are measured in RPM and positions in revolutions, matching
+//! [`Motor::actual_velocity`] and [`Motor::position`], so the fitted gains are in volts per RPM
+//! and volts per RPM per second.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! let mut drive = [&mut left_motor, &mut right_motor];
+//!
+//! let mut logs = Vec::new();
+//! for test in [
+//!     SysIdTest::quasistatic(0.5, Duration::from_secs(10)),
+//!     SysIdTest::quasistatic(-0.5, Duration::from_secs(10)),
+//!     SysIdTest::dynamic(7.0, Duration::from_secs(2)),
+//!     SysIdTest::dynamic(-7.0, Duration::from_secs(2)),
+//! ] {
+//!     let log = test.run(&mut drive).await?;
+//!     log.save(&format!("sysid-{}.csv", logs.len()))?;
+//!     logs.push(log);
+//!
+//!     // Give the robot time to be reset before the next test.
+//!     sleep(Duration::from_secs(5)).await;
+//! }
+//!
+//! if let Some(gains) = FeedforwardGains::fit(&logs) {
+//!     println!("kS = {}, kV = {}, kA = {}", gains.ks, gains.kv, gains.ka);
+//! }
+//! ```
+//!
+//! [`Motor::actual_velocity`]: crate::smart::motor::Motor::actual_velocity
+//! [`Motor::position`]: crate::smart::motor::Motor::position
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::Write,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use snafu::Snafu;
+use vexide_core::{float::Float, fs, io, time::Instant};
+
+use crate::smart::motor::{BrakeMode, Motor, MotorError};
+
+/// Pivots smaller than this are treated as zero when solving for gains.
+const EPSILON: f64 = 1e-9;
+
+/// A characterization test that drives motors with a known voltage over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SysIdTest {
+    kind: TestKind,
+    duration: Duration,
+}
+
+/// The voltage profile applied by a [`SysIdTest`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TestKind {
+    Quasistatic { ramp_rate: f64 },
+    Dynamic { voltage: f64 },
+}
+
+impl SysIdTest {
+    /// Creates a quasistatic test, which ramps the voltage from zero by `ramp_rate` volts per
+    /// second.
+    ///
+    /// A negative `ramp_rate` runs the motors in reverse. The test should be stopped before
+    /// the mechanism runs out of room, typically by choosing a shorter `duration`.
+    pub const fn quasistatic(ramp_rate: f64, duration: Duration) -> Self {
+        Self {
+            kind: TestKind::Quasistatic { ramp_rate },
+            duration,
+        }
+    }
+
+    /// Creates a dynamic test, which applies a constant `voltage` from the start of the test.
+    ///
+    /// A negative `voltage` runs the motors in reverse.
+    pub const fn dynamic(voltage: f64, duration: Duration) -> Self {
+        Self {
+            kind: TestKind::Dynamic { voltage },
+            duration,
+        }
+    }
+
+    /// Returns how long the test runs for.
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the voltage applied at a time since the start of the test.
+    ///
+    /// This is limited to [`Motor::MAX_VOLTAGE`] in either direction.
+    pub fn voltage_at(&self, elapsed: Duration) -> f64 {
+        let voltage = match self.kind {
+            TestKind::Quasistatic { ramp_rate } => ramp_rate * elapsed.as_secs_f64(),
+            TestKind::Dynamic { voltage } => voltage,
+        };
+
+        voltage.clamp(-Motor::MAX_VOLTAGE, Motor::MAX_VOLTAGE)
+    }
+
+    /// Runs the test on a group of motors, recording their average position and velocity
+    /// every [`Motor::DATA_READ_INTERVAL`].
+    ///
+    /// The motors are set to coast once the test finishes, fails, or is dropped. To test a
+    /// single motor, pass `&mut [&mut motor]`.
+    pub fn run<'a, 'b>(self, motors: &'a mut [&'b mut Motor]) -> SysIdFuture<'a, 'b> {
+        SysIdFuture {
+            test: self,
+            motors,
+            start: None,
+            last_sample: None,
+            voltage: 0.0,
+            log: SysIdLog::new(),
+            finished: false,
+        }
+    }
+}
+
+/// A measurement taken during a [`SysIdTest`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SysIdSample {
+    /// The time since the start of the test.
+    pub time: Duration,
+
+    /// The voltage applied to the motors leading up to this sample.
+    pub voltage: f64,
+
+    /// The average position of the motors in revolutions.
+    pub position: f64,
+
+    /// The average velocity of the motors in RPM.
+    pub velocity: f64,
+}
+
+/// The samples recorded by a [`SysIdTest`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SysIdLog {
+    samples: Vec<SysIdSample>,
+}
+
+impl SysIdLog {
+    /// Creates an empty log.
+    pub const fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+
+    /// Returns the samples in the log, ordered by time.
+    pub fn samples(&self) -> &[SysIdSample] {
+        &self.samples
+    }
+
+    /// Adds a sample to the end of the log.
+    pub fn push(&mut self, sample: SysIdSample) {
+        self.samples.push(sample);
+    }
+
+    /// Formats the log as CSV, with a header row followed by one row per sample.
+    ///
+    /// Times are written in seconds.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time,voltage,position,velocity\n");
+
+        for sample in &self.samples {
+            _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                sample.time.as_secs_f64(),
+                sample.voltage,
+                sample.position,
+                sample.velocity
+            );
+        }
+
+        csv
+    }
+
+    /// Saves the log to a CSV file on the SD card.
+    pub fn save(&self, path: &str) -> Result<(), SysIdError> {
+        fs::write(path, self.to_csv()).map_err(|error| SysIdError::Io { error })
+    }
+}
+
+/// Future that runs a [`SysIdTest`], created with [`SysIdTest::run`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SysIdFuture<'a, 'b> {
+    test: SysIdTest,
+    motors: &'a mut [&'b mut Motor],
+    start: Option<Instant>,
+    last_sample: Option<Instant>,
+    voltage: f64,
+    log: SysIdLog,
+    finished: bool,
+}
+
+impl SysIdFuture<'_, '_> {
+    /// Reads the average position and velocity of the motors.
+    fn measure(&self) -> Result<(f64, f64), MotorError> {
+        let mut position = 0.0;
+        let mut velocity = 0.0;
+
+        for motor in self.motors.iter() {
+            position += motor.position()?.as_revolutions();
+            // The whole-RPM `velocity` is too coarse to estimate acceleration from.
+            velocity += motor.actual_velocity()?;
+        }
+
+        let count = self.motors.len().max(1) as f64;
+        Ok((position / count, velocity / count))
+    }
+
+    /// Records a sample, then applies the voltage for the next interval.
+    fn step(&mut self, elapsed: Duration) -> Result<(), MotorError> {
+        let (position, velocity) = self.measure()?;
+        self.log.push(SysIdSample {
+            time: elapsed,
+            voltage: self.voltage,
+            position,
+            velocity,
+        });
+
+        self.voltage = self.test.voltage_at(elapsed);
+        for motor in self.motors.iter_mut() {
+            motor.set_voltage(self.voltage)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets every motor to coast, ignoring errors from disconnected motors.
+    fn stop(&mut self) {
+        self.finished = true;
+
+        for motor in self.motors.iter_mut() {
+            _ = motor.brake(BrakeMode::Coast);
+        }
+    }
+}
+
+impl Future for SysIdFuture<'_, '_> {
+    type Output = Result<SysIdLog, SysIdError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let now = Instant::now();
+        let elapsed = now - *this.start.get_or_insert(now);
+
+        if this.finished || elapsed >= this.test.duration {
+            this.stop();
+            return Poll::Ready(Ok(core::mem::take(&mut this.log)));
+        }
+
+        if this.last_sample.map_or(true, |last_sample| {
+            now - last_sample >= Motor::DATA_READ_INTERVAL
+        }) {
+            this.last_sample = Some(now);
+
+            if let Err(source) = this.step(elapsed) {
+                this.stop();
+                return Poll::Ready(Err(SysIdError::Motor { source }));
+            }
+        }
+
+        // TODO: This should probably be done on a timer in the reactor.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl Drop for SysIdFuture<'_, '_> {
+    fn drop(&mut self) {
+        if self.start.is_some() && !self.finished {
+            self.stop();
+        }
+    }
+}
+
+/// Feedforward gains fitted from characterization data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeedforwardGains {
+    /// The voltage needed to overcome static friction.
+    pub ks: f64,
+
+    /// The voltage needed per RPM of velocity.
+    pub kv: f64,
+
+    /// The voltage needed per RPM per second of acceleration.
+    pub ka: f64,
+
+    /// The coefficient of determination of the fit, where `1.0` means the model explains the
+    /// data perfectly.
+    pub r_squared: f64,
+}
+
+impl FeedforwardGains {
+    /// Fits feedforward gains to the samples in a set of logs by least squares.
+    ///
+    /// Acceleration is estimated from the change in velocity between neighboring samples, and
+    /// samples where the motors aren't moving are ignored. Both quasistatic and dynamic tests
+    /// should be included, in both directions, for `kA` to be meaningful.
+    ///
+    /// Returns `None` if there isn't enough data to determine every gain.
+    pub fn fit(logs: &[SysIdLog]) -> Option<Self> {
+        // Rows of (sign(v), v, a) paired with the measured voltage.
+        let rows: Vec<([f64; 3], f64)> = logs
+            .iter()
+            .flat_map(|log| log.samples.windows(3))
+            .filter_map(|window| {
+                let [previous, sample, next] = window else {
+                    return None;
+                };
+
+                let dt = (next.time - previous.time).as_secs_f64();
+                if sample.velocity == 0.0 || dt <= 0.0 {
+                    return None;
+                }
+
+                let acceleration = (next.velocity - previous.velocity) / dt;
+                Some((
+                    [sample.velocity.signum(), sample.velocity, acceleration],
+                    sample.voltage,
+                ))
+            })
+            .collect();
+
+        if rows.len() < 3 {
+            return None;
+        }
+
+        // Solve the normal equations (XᵀX) g = Xᵀy.
+        let mut xtx = [[0.0; 3]; 3];
+        let mut xty = [0.0; 3];
+        for (x, y) in &rows {
+            for i in 0..3 {
+                for j in 0..3 {
+                    xtx[i][j] += x[i] * x[j];
+                }
+                xty[i] += x[i] * y;
+            }
+        }
+
+        let [ks, kv, ka] = solve(xtx, xty)?;
+
+        let mean = rows.iter().map(|(_, y)| y).sum::<f64>() / rows.len() as f64;
+        let (residual, total) = rows.iter().fold((0.0, 0.0), |(residual, total), (x, y)| {
+            let predicted = ks * x[0] + kv * x[1] + ka * x[2];
+            (
+                residual + (y - predicted).powi(2),
+                total + (y - mean).powi(2),
+            )
+        });
+
+        Some(Self {
+            ks,
+            kv,
+            ka,
+            r_squared: if total > 0.0 {
+                1.0 - residual / total
+            } else {
+                1.0
+            },
+        })
+    }
+
+    /// Returns the voltage predicted to produce a velocity (in RPM) and acceleration (in RPM
+    /// per second).
+    pub fn voltage(&self, velocity: f64, acceleration: f64) -> f64 {
+        let static_friction = if velocity == 0.0 {
+            0.0
+        } else {
+            self.ks * velocity.signum()
+        };
+
+        static_friction + self.kv * velocity + self.ka * acceleration
+    }
+}
+
+/// Solves a 3x3 linear system by Gaussian elimination with partial pivoting.
+fn solve(mut a: [[f64; 3]; 3], mut b: [f64; 3]) -> Option<[f64; 3]> {
+    for column in 0..3 {
+        let pivot =
+            (column..3).max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))?;
+        if a[pivot][column].abs() < EPSILON {
+            return None;
+        }
+
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+
+        for row in column + 1..3 {
+            let factor = a[row][column] / a[column][column];
+            for k in column..3 {
+                a[row][k] -= factor * a[column][k];
+            }
+            b[row] -= factor * b[column];
+        }
+    }
+
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let sum: f64 = (row + 1..3).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+
+    Some(x)
+}
+
+#[derive(Debug, Snafu)]
+/// Errors that can occur when running a [`SysIdTest`] or saving a [`SysIdLog`].
+pub enum SysIdError {
+    /// The log could not be written to the SD card.
+    #[snafu(display("{error}"))]
+    Io {
+        /// The underlying I/O error.
+        error: io::Error,
+    },
+
+    /// A motor could not be driven or read during the test.
+    #[snafu(display("{source}"), context(false))]
+    Motor {
+        /// The source of the error.
+        source: MotorError,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KS: f64 = 0.6;
+    const KV: f64 = 0.012;
+    const KA: f64 = 0.003;
+
+    /// Runs a test against a simulated mechanism with known gains, sampling it the same way
+    /// as [`SysIdFuture`].
+    fn simulate(test: SysIdTest) -> SysIdLog {
+        const STEPS_PER_SAMPLE: u32 = 20;
+        let dt = Motor::DATA_READ_INTERVAL.as_secs_f64() / f64::from(STEPS_PER_SAMPLE);
+
+        let mut log = SysIdLog::new();
+        let (mut position, mut velocity, mut voltage) = (0.0, 0.0, 0.0);
+        let mut elapsed = Duration::ZERO;
+
+        while elapsed < test.duration() {
+            log.push(SysIdSample {
+                time: elapsed,
+                voltage,
+                position,
+                velocity,
+            });
+            voltage = test.voltage_at(elapsed);
+
+            for _ in 0..STEPS_PER_SAMPLE {
+                let friction = if velocity == 0.0 {
+                    // Static friction holds the mechanism still until it is overcome.
+                    voltage.clamp(-KS, KS)
+                } else {
+                    KS * velocity.signum()
+                };
+                let acceleration = (voltage - friction - KV * velocity) / KA;
+
+                position += velocity / 60.0 * dt;
+                velocity += acceleration * dt;
+            }
+            elapsed += Motor::DATA_READ_INTERVAL;
+        }
+
+        log
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn recovers_known_gains() {
+        let logs = [
+            simulate(SysIdTest::quasistatic(1.0, Duration::from_secs(8))),
+            simulate(SysIdTest::quasistatic(-1.0, Duration::from_secs(8))),
+            simulate(SysIdTest::dynamic(7.0, Duration::from_secs(2))),
+            simulate(SysIdTest::dynamic(-7.0, Duration::from_secs(2))),
+        ];
+
+        let gains = FeedforwardGains::fit(&logs).unwrap();
+
+        assert_close(gains.ks, KS, 0.02);
+        assert_close(gains.kv, KV, 0.01);
+        assert_close(gains.ka, KA, 0.02);
+        assert!(gains.r_squared > 0.99);
+    }
+
+    #[test]
+    fn predicts_voltage_from_gains() {
+        let gains = FeedforwardGains {
+            ks: KS,
+            kv: KV,
+            ka: KA,
+            r_squared: 1.0,
+        };
+
+        assert_eq!(gains.voltage(0.0, 0.0), 0.0);
+        assert_close(gains.voltage(100.0, 0.0), KS + KV * 100.0, 1e-12);
+        assert_close(
+            gains.voltage(-100.0, 50.0),
+            -KS - KV * 100.0 + KA * 50.0,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn not_enough_data() {
+        assert_eq!(FeedforwardGains::fit(&[]), None);
+
+        // The mechanism never moves, so no gains can be determined.
+        let stalled = simulate(SysIdTest::quasistatic(0.1, Duration::from_secs(1)));
+        assert_eq!(FeedforwardGains::fit(&[stalled]), None);
+    }
+
+    #[test]
+    fn csv_has_header_and_rows() {
+        let mut log = SysIdLog::new();
+        log.push(SysIdSample {
+            time: Duration::from_millis(500),
+            voltage: 1.5,
+            position: 2.0,
+            velocity: -3.25,
+        });
+
+        assert_eq!(
+            log.to_csv(),
+            "time,voltage,position,velocity\n0.5,1.5,2,-3.25\n"
+        );
+    }
+}
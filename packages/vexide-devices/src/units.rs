@@ -0,0 +1,389 @@
+//! Strongly-typed physical quantities.
+//!
+//! Device APIs normally return plain numbers, with their units listed in each function's
+//! documentation. When the `units` feature is enabled, devices additionally provide `_typed`
+//! variants of these functions that use the quantity types in this module instead, so mixing
+//! up units (such as volts and millivolts) becomes a compile error rather than a runtime bug.
+//!
+//! Each quantity stores its value in a fixed base unit, and can be created from or converted
+//! into any of its supported units.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vexide_devices::units::{Voltage, Length};
+//!
+//! let voltage = Voltage::from_millivolts(12_800.0);
+//! assert_eq!(voltage.as_volts(), 12.8);
+//!
+//! let wheel_diameter = Length::from_inches(3.25);
+//! let wheel_circumference = wheel_diameter * core::f64::consts::PI;
+//! ```
+
+use core::{
+    f64::consts::TAU,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::position::Position;
+
+/// Implements arithmetic operators shared by all quantities.
+macro_rules! quantity_ops {
+    ($name:ident) => {
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+
+            /// Returns the ratio between two quantities.
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl MulAssign<f64> for $name {
+            fn mul_assign(&mut self, rhs: f64) {
+                self.0 *= rhs;
+            }
+        }
+
+        impl DivAssign<f64> for $name {
+            fn div_assign(&mut self, rhs: f64) {
+                self.0 /= rhs;
+            }
+        }
+    };
+}
+
+/// An electric potential difference.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Voltage(f64);
+
+impl Voltage {
+    /// Creates a voltage from a number of volts.
+    pub const fn from_volts(volts: f64) -> Self {
+        Self(volts)
+    }
+
+    /// Creates a voltage from a number of millivolts.
+    pub fn from_millivolts(millivolts: f64) -> Self {
+        Self(millivolts / 1000.0)
+    }
+
+    /// Returns this voltage in volts.
+    pub const fn as_volts(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this voltage in millivolts.
+    pub fn as_millivolts(&self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+impl Display for Voltage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} V", self.0)
+    }
+}
+
+quantity_ops!(Voltage);
+
+/// An electric current.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Current(f64);
+
+impl Current {
+    /// Creates a current from a number of amps.
+    pub const fn from_amps(amps: f64) -> Self {
+        Self(amps)
+    }
+
+    /// Creates a current from a number of milliamps.
+    pub fn from_milliamps(milliamps: f64) -> Self {
+        Self(milliamps / 1000.0)
+    }
+
+    /// Returns this current in amps.
+    pub const fn as_amps(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this current in milliamps.
+    pub fn as_milliamps(&self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+impl Display for Current {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} A", self.0)
+    }
+}
+
+quantity_ops!(Current);
+
+/// A rate of rotation.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct AngularVelocity(f64);
+
+impl AngularVelocity {
+    /// Creates an angular velocity from a number of radians per second.
+    pub const fn from_radians_per_second(radians_per_second: f64) -> Self {
+        Self(radians_per_second)
+    }
+
+    /// Creates an angular velocity from a number of degrees per second.
+    pub fn from_degrees_per_second(degrees_per_second: f64) -> Self {
+        Self(degrees_per_second.to_radians())
+    }
+
+    /// Creates an angular velocity from a number of revolutions per minute.
+    pub fn from_rpm(rpm: f64) -> Self {
+        Self(rpm * TAU / 60.0)
+    }
+
+    /// Returns this angular velocity in radians per second.
+    pub const fn as_radians_per_second(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this angular velocity in degrees per second.
+    pub fn as_degrees_per_second(&self) -> f64 {
+        self.0.to_degrees()
+    }
+
+    /// Returns this angular velocity in revolutions per minute.
+    pub fn as_rpm(&self) -> f64 {
+        self.0 * 60.0 / TAU
+    }
+}
+
+impl Display for AngularVelocity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rad/s", self.0)
+    }
+}
+
+quantity_ops!(AngularVelocity);
+
+/// A distance.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Length(f64);
+
+impl Length {
+    const METERS_PER_INCH: f64 = 0.0254;
+
+    /// Creates a length from a number of meters.
+    pub const fn from_meters(meters: f64) -> Self {
+        Self(meters)
+    }
+
+    /// Creates a length from a number of centimeters.
+    pub fn from_centimeters(centimeters: f64) -> Self {
+        Self(centimeters / 100.0)
+    }
+
+    /// Creates a length from a number of millimeters.
+    pub fn from_millimeters(millimeters: f64) -> Self {
+        Self(millimeters / 1000.0)
+    }
+
+    /// Creates a length from a number of inches.
+    pub fn from_inches(inches: f64) -> Self {
+        Self(inches * Self::METERS_PER_INCH)
+    }
+
+    /// Creates a length from a number of feet.
+    pub fn from_feet(feet: f64) -> Self {
+        Self::from_inches(feet * 12.0)
+    }
+
+    /// Returns this length in meters.
+    pub const fn as_meters(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this length in centimeters.
+    pub fn as_centimeters(&self) -> f64 {
+        self.0 * 100.0
+    }
+
+    /// Returns this length in millimeters.
+    pub fn as_millimeters(&self) -> f64 {
+        self.0 * 1000.0
+    }
+
+    /// Returns this length in inches.
+    pub fn as_inches(&self) -> f64 {
+        self.0 / Self::METERS_PER_INCH
+    }
+
+    /// Returns this length in feet.
+    pub fn as_feet(&self) -> f64 {
+        self.as_inches() / 12.0
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} m", self.0)
+    }
+}
+
+quantity_ops!(Length);
+
+/// A rotational displacement.
+///
+/// Unlike [`Position`], angles are stored as floating point and are not limited to the precision
+/// of a sensor's encoder, making them better suited for calculations.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Angle(f64);
+
+impl Angle {
+    /// Creates an angle from a number of radians.
+    pub const fn from_radians(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    /// Creates an angle from a number of degrees.
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self(degrees.to_radians())
+    }
+
+    /// Creates an angle from a number of revolutions.
+    pub fn from_revolutions(revolutions: f64) -> Self {
+        Self(revolutions * TAU)
+    }
+
+    /// Returns this angle in radians.
+    pub const fn as_radians(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this angle in degrees.
+    pub fn as_degrees(&self) -> f64 {
+        self.0.to_degrees()
+    }
+
+    /// Returns this angle in revolutions.
+    pub fn as_revolutions(&self) -> f64 {
+        self.0 / TAU
+    }
+}
+
+impl Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rad", self.0)
+    }
+}
+
+quantity_ops!(Angle);
+
+impl From<Position> for Angle {
+    fn from(position: Position) -> Self {
+        Self::from_radians(position.as_radians())
+    }
+}
+
+impl From<Angle> for Position {
+    fn from(angle: Angle) -> Self {
+        Self::from_radians(angle.as_radians())
+    }
+}
+
+/// A temperature.
+///
+/// Arithmetic operators are not implemented for temperatures, since adding or scaling absolute
+/// temperatures is not meaningful.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Temperature(f64);
+
+impl Temperature {
+    /// Creates a temperature from a number of degrees Celsius.
+    pub const fn from_celsius(celsius: f64) -> Self {
+        Self(celsius)
+    }
+
+    /// Creates a temperature from a number of degrees Fahrenheit.
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    /// Creates a temperature from a number of kelvins.
+    pub fn from_kelvin(kelvin: f64) -> Self {
+        Self(kelvin - 273.15)
+    }
+
+    /// Returns this temperature in degrees Celsius.
+    pub const fn as_celsius(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns this temperature in degrees Fahrenheit.
+    pub fn as_fahrenheit(&self) -> f64 {
+        self.0 * 9.0 / 5.0 + 32.0
+    }
+
+    /// Returns this temperature in kelvins.
+    pub fn as_kelvin(&self) -> f64 {
+        self.0 + 273.15
+    }
+}
+
+impl Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} °C", self.0)
+    }
+}
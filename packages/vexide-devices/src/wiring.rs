@@ -0,0 +1,339 @@
+//! Declarative port maps for checking robot wiring at startup.
+//!
+//! A [`PortMap`] lists which device is expected on each port. Checking it against the devices
+//! that are actually plugged in catches swapped or loose cables before a match starts, rather
+//! than partway through one.
+//!
+//! # Limitations
+//!
+//! Smart ports report the type of device plugged into them, so they can be fully checked.
+//! Three-wire (ADI) ports can't detect what is plugged into them. Instead,
+//! [`PortMap::validate`] configures each declared ADI port as its declared [`AdiDeviceType`]
+//! and reads the configuration back, which catches ports on an [`AdiExpander`] that is
+//! unplugged or plugged into a different port. A sensor plugged into the wrong ADI port can't
+//! be detected.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! let (mut peripherals, report) = PortMap::new()
+//!     .smart(3, SmartDeviceType::Motor)
+//!     .smart(7, SmartDeviceType::Imu)
+//!     .adi(1, AdiDeviceType::DigitalOut)
+//!     .validate(peripherals);
+//!
+//! if !report.is_ok() {
+//!     // The problems have already been printed. Decide whether to continue anyway.
+//! }
+//!
+//! let motor = Motor::new(
+//!     peripherals.take_smart_port(3).unwrap(),
+//!     Gearset::Green,
+//!     Direction::Forward,
+//! );
+//! ```
+//!
+//! [`AdiExpander`]: crate::smart::AdiExpander
+
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use vexide_core::print;
+
+use crate::{
+    adi::{AdiDeviceType, AdiPort},
+    peripherals::{DynamicPeripherals, Peripherals},
+    smart::{SmartDeviceType, SmartPort},
+};
+
+/// The number of smart ports on the brain.
+const SMART_PORT_COUNT: usize = 21;
+
+/// The number of ADI ports on the brain or an ADI expander.
+const ADI_PORT_COUNT: usize = 8;
+
+/// A declaration of which device is expected on each port of a robot.
+///
+/// Ports that aren't declared are not checked.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PortMap {
+    smart: [Option<SmartDeviceType>; SMART_PORT_COUNT],
+    adi: Vec<AdiDeclaration>,
+}
+
+/// An ADI port declared in a [`PortMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AdiDeclaration {
+    expander: Option<u8>,
+    port: u8,
+    device_type: AdiDeviceType,
+}
+
+impl AdiDeclaration {
+    /// Creates the declared port.
+    ///
+    /// # Safety
+    ///
+    /// See [`AdiPort::new`].
+    const unsafe fn port(&self) -> AdiPort {
+        unsafe { AdiPort::new(self.port, self.expander) }
+    }
+}
+
+impl PortMap {
+    /// Creates an empty port map.
+    pub const fn new() -> Self {
+        Self {
+            smart: [None; SMART_PORT_COUNT],
+            adi: Vec::new(),
+        }
+    }
+
+    /// Declares the type of device expected on a smart port.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided port is outside the range 1-21.
+    pub fn smart(mut self, port: u8, device_type: SmartDeviceType) -> Self {
+        assert!(
+            (1..=SMART_PORT_COUNT as u8).contains(&port),
+            "smart port {port} is outside the range 1-21"
+        );
+
+        self.smart[port as usize - 1] = Some(device_type);
+        self
+    }
+
+    /// Declares the type of device expected on one of the brain's ADI ports.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the provided port is outside the range 1-8.
+    pub fn adi(self, port: u8, device_type: AdiDeviceType) -> Self {
+        self.declare_adi(None, port, device_type)
+    }
+
+    /// Declares the type of device expected on an ADI port of an ADI expander.
+    ///
+    /// This also declares that an ADI expander is plugged into `expander_port`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `expander_port` is outside the range 1-21, or if `port` is
+    /// outside the range 1-8.
+    pub fn expander_adi(self, expander_port: u8, port: u8, device_type: AdiDeviceType) -> Self {
+        self.smart(expander_port, SmartDeviceType::Adi).declare_adi(
+            Some(expander_port),
+            port,
+            device_type,
+        )
+    }
+
+    fn declare_adi(mut self, expander: Option<u8>, port: u8, device_type: AdiDeviceType) -> Self {
+        assert!(
+            (1..=ADI_PORT_COUNT as u8).contains(&port),
+            "ADI port {port} is outside the range 1-8"
+        );
+
+        self.adi
+            .retain(|declaration| declaration.expander != expander || declaration.port != port);
+        self.adi.push(AdiDeclaration {
+            expander,
+            port,
+            device_type,
+        });
+        self
+    }
+
+    /// Returns the type of device declared on a smart port, if any.
+    pub fn smart_port(&self, port: u8) -> Option<SmartDeviceType> {
+        self.smart
+            .get(port.checked_sub(1)? as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Returns the type of device declared on an ADI port, if any.
+    ///
+    /// `expander` is the smart port of the ADI expander the port belongs to, or `None` for the
+    /// brain's own ADI ports.
+    pub fn adi_port(&self, expander: Option<u8>, port: u8) -> Option<AdiDeviceType> {
+        self.adi
+            .iter()
+            .find(|declaration| declaration.expander == expander && declaration.port == port)
+            .map(|declaration| declaration.device_type)
+    }
+
+    /// Checks the declared ports against the devices that are currently plugged in.
+    ///
+    /// ADI ports are compared against the type they are currently configured as. This doesn't
+    /// configure them, so ports that no device has been created on yet are reported as
+    /// mismatched unless [`PortMap::validate`] is used instead.
+    pub fn check(&self) -> WiringReport {
+        let mismatches = self
+            .smart
+            .iter()
+            .enumerate()
+            .filter_map(|(index, expected)| {
+                let expected = (*expected)?;
+                let port = index as u8 + 1;
+
+                // SAFETY: The port is only used to read the type of the connected device.
+                let found = unsafe { SmartPort::new(port) }
+                    .device_type()
+                    .unwrap_or(SmartDeviceType::None);
+
+                (found != expected).then_some(WiringMismatch {
+                    port,
+                    expected,
+                    found,
+                })
+            })
+            .collect();
+
+        let adi_mismatches = self
+            .adi
+            .iter()
+            .filter_map(|declaration| {
+                // SAFETY: The port is only used to read its configuration.
+                let found = unsafe { declaration.port() }.configured_type().ok();
+
+                (found != Some(declaration.device_type)).then_some(AdiWiringMismatch {
+                    expander: declaration.expander,
+                    port: declaration.port,
+                    expected: declaration.device_type,
+                    found,
+                })
+            })
+            .collect();
+
+        WiringReport {
+            mismatches,
+            adi_mismatches,
+        }
+    }
+
+    /// Configures the declared ADI ports and checks every declared port, then reports any
+    /// problems over serial and on the brain's screen.
+    ///
+    /// This consumes the robot's [`Peripherals`] so that the check happens before any devices
+    /// are created. Ports can then be taken from the returned [`DynamicPeripherals`] to create
+    /// devices, whether or not the check passed.
+    pub fn validate(&self, mut peripherals: Peripherals) -> (DynamicPeripherals, WiringReport) {
+        for declaration in &self.adi {
+            // SAFETY: No devices have been created yet, since `peripherals` hasn't been used.
+            let port = unsafe { declaration.port() };
+            if port.validate_expander().is_ok() {
+                port.configure(declaration.device_type);
+            }
+        }
+
+        let report = self.check();
+
+        if !report.is_ok() {
+            print!("{report}");
+            _ = write!(peripherals.screen, "{report}");
+        }
+
+        (DynamicPeripherals::new(peripherals), report)
+    }
+}
+
+/// A smart port with a different device plugged in than was declared in a [`PortMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WiringMismatch {
+    /// The index of the port.
+    pub port: u8,
+
+    /// The type of device declared on the port.
+    pub expected: SmartDeviceType,
+
+    /// The type of device plugged into the port, or [`SmartDeviceType::None`] if the port is
+    /// empty.
+    pub found: SmartDeviceType,
+}
+
+impl fmt::Display for WiringMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Port {}: expected {:?}, ", self.port, self.expected)?;
+
+        match self.found {
+            SmartDeviceType::None => write!(f, "found nothing"),
+            found => write!(f, "found {found:?}"),
+        }
+    }
+}
+
+/// An ADI port that isn't configured as the type declared in a [`PortMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdiWiringMismatch {
+    /// The smart port of the ADI expander the port belongs to, or `None` for the brain's own
+    /// ADI ports.
+    pub expander: Option<u8>,
+
+    /// The index of the ADI port.
+    pub port: u8,
+
+    /// The type of device declared on the port.
+    pub expected: AdiDeviceType,
+
+    /// The type the port is configured as, or `None` if its ADI expander isn't plugged in.
+    pub found: Option<AdiDeviceType>,
+}
+
+impl fmt::Display for AdiWiringMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expander {
+            Some(expander) => write!(f, "ADI port {}-{}", expander, self.port)?,
+            None => write!(f, "ADI port {}", self.port)?,
+        }
+        write!(f, ": expected {:?}, ", self.expected)?;
+
+        match self.found {
+            Some(found) => write!(f, "configured as {found:?}"),
+            None => write!(f, "expander not connected"),
+        }
+    }
+}
+
+/// The result of checking a [`PortMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WiringReport {
+    mismatches: Vec<WiringMismatch>,
+    adi_mismatches: Vec<AdiWiringMismatch>,
+}
+
+impl WiringReport {
+    /// Returns `true` if every declared port has the expected device plugged in.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty() && self.adi_mismatches.is_empty()
+    }
+
+    /// Returns the smart ports that don't have the expected device plugged in.
+    pub fn mismatches(&self) -> &[WiringMismatch] {
+        &self.mismatches
+    }
+
+    /// Returns the ADI ports that aren't configured as the expected device.
+    pub fn adi_mismatches(&self) -> &[AdiWiringMismatch] {
+        &self.adi_mismatches
+    }
+}
+
+impl fmt::Display for WiringReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return writeln!(f, "Wiring OK");
+        }
+
+        writeln!(f, "Wiring errors:")?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "  {mismatch}")?;
+        }
+        for mismatch in &self.adi_mismatches {
+            writeln!(f, "  {mismatch}")?;
+        }
+
+        Ok(())
+    }
+}
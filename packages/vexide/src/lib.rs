@@ -80,13 +80,16 @@ pub mod prelude {
             analog::AdiAnalogIn,
             digital::{AdiDigitalIn, AdiDigitalOut},
             encoder::AdiEncoder,
+            gyro::AdiGyro,
             light_sensor::AdiLightSensor,
             line_tracker::AdiLineTracker,
             motor::AdiMotor,
             potentiometer::{AdiPotentiometer, PotentiometerType},
             pwm::AdiPwmOut,
             range_finder::AdiRangeFinder,
+            servo::AdiServo,
             solenoid::AdiSolenoid,
+            switch::{AdiSwitch, SwitchType},
             AdiDevice, AdiPort,
         },
         battery,
@@ -96,10 +99,16 @@ pub mod prelude {
         position::Position,
         screen::Screen,
         smart::{
+            ai_vision::{
+                AiVisionCode, AiVisionColor, AiVisionDetectionMode, AiVisionObject, AiVisionSensor,
+            },
+            arm::{Arm, ArmTarget},
             distance::DistanceSensor,
             expander::AdiExpander,
             imu::InertialSensor,
+            light_tower::{LightTower, LightTowerColor},
             link::{LinkType, RadioLink},
+            magnet::{Electromagnet, MagnetDuration},
             motor::{BrakeMode, Direction, Gearset, Motor, MotorControl},
             optical::OpticalSensor,
             rotation::RotationSensor,